@@ -0,0 +1,110 @@
+//! Benchmarks comparing compressors on JSON payloads
+//! Run with `--all-features` to include every compressor
+//! Compression ratios are printed after the benchmarks
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use hitbox_backend::{Compressor, PassthroughCompressor};
+use std::hint::black_box;
+
+/// Generate a JSON array of user records of roughly `size_bytes`
+fn generate_payload(size_bytes: usize) -> Vec<u8> {
+    let mut users = Vec::new();
+    let mut payload = Vec::new();
+    let mut id = 0u64;
+    while payload.len() < size_bytes {
+        users.push(serde_json::json!({
+            "id": id,
+            "name": format!("User {id}"),
+            "email": format!("user{id}@example.com"),
+            "active": !id.is_multiple_of(3),
+            "score": (id * 7919) % 1000,
+            "tags": ["customer", if id.is_multiple_of(2) { "premium" } else { "basic" }],
+        }));
+        payload = serde_json::to_vec(&users).unwrap();
+        id += 1;
+    }
+    payload
+}
+
+fn compressors() -> Vec<(&'static str, Box<dyn Compressor>)> {
+    #[allow(unused_mut)]
+    let mut compressors: Vec<(&'static str, Box<dyn Compressor>)> =
+        vec![("passthrough", Box::new(PassthroughCompressor))];
+    #[cfg(feature = "gzip")]
+    compressors.push(("gzip", Box::new(hitbox_backend::GzipCompressor::new())));
+    #[cfg(feature = "zstd")]
+    compressors.push(("zstd", Box::new(hitbox_backend::ZstdCompressor::new())));
+    #[cfg(feature = "lz4")]
+    compressors.push(("lz4", Box::new(hitbox_backend::Lz4Compressor::new())));
+    #[cfg(feature = "snappy")]
+    compressors.push(("snappy", Box::new(hitbox_backend::SnappyCompressor)));
+    #[cfg(feature = "brotli")]
+    compressors.push(("brotli", Box::new(hitbox_backend::BrotliCompressor::new())));
+    compressors
+}
+
+const SIZES: [(&str, usize); 3] = [("1KB", 1024), ("10KB", 10 * 1024), ("100KB", 100 * 1024)];
+
+fn bench_compress(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compress");
+
+    for (size_name, size_bytes) in SIZES {
+        let payload = generate_payload(size_bytes);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        for (name, compressor) in compressors() {
+            group.bench_with_input(BenchmarkId::new(name, size_name), &payload, |b, payload| {
+                b.iter(|| black_box(compressor.compress(black_box(payload)).unwrap()));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_decompress(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decompress");
+
+    for (size_name, size_bytes) in SIZES {
+        let payload = generate_payload(size_bytes);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        for (name, compressor) in compressors() {
+            let compressed = compressor.compress(&payload).unwrap();
+            group.bench_with_input(
+                BenchmarkId::new(name, size_name),
+                &compressed,
+                |b, compressed| {
+                    b.iter(|| black_box(compressor.decompress(black_box(compressed)).unwrap()));
+                },
+            );
+        }
+    }
+
+    group.finish();
+
+    // Print compressed size table
+    println!("\n=== Compressed Size (bytes) ===\n");
+    print!("{:<12}", "Compressor");
+    for (size_name, _) in SIZES {
+        print!(" {:>10}", size_name);
+    }
+    println!();
+    println!("{:-<45}", "");
+
+    let payloads: Vec<_> = SIZES
+        .iter()
+        .map(|(_, size_bytes)| generate_payload(*size_bytes))
+        .collect();
+    for (name, compressor) in compressors() {
+        print!("{:<12}", name);
+        for payload in &payloads {
+            print!(" {:>10}", compressor.compress(payload).unwrap().len());
+        }
+        println!();
+    }
+    println!();
+}
+
+criterion_group!(benches, bench_compress, bench_decompress);
+criterion_main!(benches);
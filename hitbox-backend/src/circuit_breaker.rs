@@ -0,0 +1,377 @@
+//! Circuit breaker for any backend.
+//!
+//! [`CircuitBreakerBackend`] stops calling an inner backend that keeps
+//! failing, so requests stop paying for the round-trip and the error path:
+//!
+//! - **Closed**: operations go to the inner backend. When
+//!   [`failure_threshold`](CircuitBreakerBackend::failure_threshold) errors
+//!   happen within [`failure_window`](CircuitBreakerBackend::failure_window),
+//!   the circuit opens.
+//! - **Open**: the inner backend is not touched. Reads are misses, writes are
+//!   skipped and removes fail with [`CircuitOpenError`]. After
+//!   [`open_duration`](CircuitBreakerBackend::open_duration) the circuit
+//!   half-opens.
+//! - **Half-open**: a single operation is let through as a probe. Success
+//!   closes the circuit, failure opens it again.
+//!
+//! Clones of a `CircuitBreakerBackend` share the same circuit.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use hitbox_backend::CircuitBreakerBackend;
+//!
+//! let backend = CircuitBreakerBackend::new(redis)
+//!     .failure_threshold(5)
+//!     .failure_window(Duration::from_secs(10))
+//!     .open_duration(Duration::from_secs(30));
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use hitbox_core::{BackendLabel, CacheKey, CacheValue, Raw};
+
+use crate::format::Format;
+use crate::{
+    Backend, BackendError, BackendResult, CacheBackend, CacheKeyFormat, Compressor, DeleteStatus,
+};
+
+/// Default number of failures within the window that opens the circuit.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+/// Default window in which failures are counted.
+pub const DEFAULT_FAILURE_WINDOW: Duration = Duration::from_secs(10);
+/// Default time the circuit stays open before probing the backend.
+pub const DEFAULT_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// State of a circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Operations reach the inner backend.
+    Closed,
+    /// Operations are short-circuited.
+    Open,
+    /// A single probe operation is allowed to decide whether to close.
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// Returns the state name for metrics labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+
+    /// Returns the state as a gauge value: 0 closed, 1 half-open, 2 open.
+    fn as_gauge(&self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+}
+
+/// Snapshot of a circuit breaker, returned by [`CircuitBreakerBackend::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitStatus {
+    /// Current state.
+    pub state: CircuitState,
+    /// Failures counted within the current window.
+    pub recent_failures: u32,
+}
+
+/// Error returned by operations that cannot be skipped while the circuit is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitOpenError;
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("circuit breaker is open")
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+impl Circuit {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            failures: VecDeque::new(),
+            opened_at: None,
+            probing: false,
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(now);
+        self.failures.clear();
+        self.probing = false;
+    }
+
+    fn close(&mut self) {
+        self.state = CircuitState::Closed;
+        self.opened_at = None;
+        self.failures.clear();
+        self.probing = false;
+    }
+}
+
+/// How an admitted operation counts towards the circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Call {
+    Regular,
+    Probe,
+}
+
+/// A backend wrapper that short-circuits an inner backend after repeated failures.
+///
+/// Serialization format, key format, compressor and label are those of the
+/// inner backend, so wrapping is transparent for stored data and metrics.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerBackend<B> {
+    inner: B,
+    failure_threshold: u32,
+    failure_window: Duration,
+    open_duration: Duration,
+    circuit: Arc<Mutex<Circuit>>,
+}
+
+impl<B: Backend> CircuitBreakerBackend<B> {
+    /// Wraps a backend with a closed circuit and default thresholds.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            failure_window: DEFAULT_FAILURE_WINDOW,
+            open_duration: DEFAULT_OPEN_DURATION,
+            circuit: Arc::new(Mutex::new(Circuit::new())),
+        }
+    }
+
+    /// Sets how many failures within the window open the circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is zero.
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        assert!(threshold > 0, "failure threshold must be greater than zero");
+        self.failure_threshold = threshold;
+        self
+    }
+
+    /// Sets the window in which failures are counted.
+    pub fn failure_window(mut self, window: Duration) -> Self {
+        self.failure_window = window;
+        self
+    }
+
+    /// Sets how long the circuit stays open before probing the backend.
+    pub fn open_duration(mut self, duration: Duration) -> Self {
+        self.open_duration = duration;
+        self
+    }
+
+    /// Returns a reference to the wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns the current state of the circuit.
+    pub fn status(&self) -> CircuitStatus {
+        let mut circuit = self.lock();
+        self.expire_failures(&mut circuit, Instant::now());
+        CircuitStatus {
+            state: circuit.state,
+            recent_failures: circuit.failures.len() as u32,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Circuit> {
+        self.circuit.lock().expect("circuit breaker state poisoned")
+    }
+
+    fn expire_failures(&self, circuit: &mut Circuit, now: Instant) {
+        while circuit
+            .failures
+            .front()
+            .is_some_and(|failed_at| now.duration_since(*failed_at) > self.failure_window)
+        {
+            circuit.failures.pop_front();
+        }
+    }
+
+    /// Decides whether an operation may reach the inner backend.
+    fn admit(&self, operation: &'static str) -> Option<Call> {
+        let mut circuit = self.lock();
+        let admitted = match circuit.state {
+            CircuitState::Closed => Some(Call::Regular),
+            CircuitState::Open => {
+                let elapsed = circuit
+                    .opened_at
+                    .is_some_and(|opened_at| opened_at.elapsed() >= self.open_duration);
+                if elapsed {
+                    circuit.state = CircuitState::HalfOpen;
+                    circuit.probing = true;
+                    drop(circuit);
+                    self.transitioned(CircuitState::HalfOpen);
+                    return Some(Call::Probe);
+                }
+                None
+            }
+            CircuitState::HalfOpen if !circuit.probing => {
+                circuit.probing = true;
+                Some(Call::Probe)
+            }
+            CircuitState::HalfOpen => None,
+        };
+        drop(circuit);
+
+        if admitted.is_none() {
+            crate::metrics::record_circuit_rejected(self.inner.label().as_str(), operation);
+        }
+        admitted
+    }
+
+    fn on_success(&self, call: Call) {
+        if call != Call::Probe {
+            return;
+        }
+        self.lock().close();
+        self.transitioned(CircuitState::Closed);
+    }
+
+    fn on_failure(&self, call: Call) {
+        let now = Instant::now();
+        let mut circuit = self.lock();
+        let opens = match call {
+            Call::Probe => true,
+            // A regular call admitted before the circuit opened has nothing to add.
+            Call::Regular if circuit.state != CircuitState::Closed => false,
+            Call::Regular => {
+                self.expire_failures(&mut circuit, now);
+                circuit.failures.push_back(now);
+                circuit.failures.len() as u32 >= self.failure_threshold
+            }
+        };
+        if opens {
+            circuit.open(now);
+            drop(circuit);
+            self.transitioned(CircuitState::Open);
+        }
+    }
+
+    /// Gives up a probe that never completed, e.g. because it was cancelled.
+    fn abandon(&self, call: Call) {
+        if call == Call::Probe {
+            self.lock().probing = false;
+        }
+    }
+
+    fn transitioned(&self, state: CircuitState) {
+        let label = self.inner.label();
+        match state {
+            CircuitState::Open => {
+                tracing::warn!(backend = %label, open_duration = ?self.open_duration, "Circuit breaker opened");
+            }
+            CircuitState::HalfOpen => {
+                tracing::debug!(backend = %label, "Circuit breaker half-open, probing backend");
+            }
+            CircuitState::Closed => {
+                tracing::info!(backend = %label, "Circuit breaker closed");
+            }
+        }
+        crate::metrics::record_circuit_state(label.as_str(), state.as_str(), state.as_gauge());
+    }
+
+    /// Runs an operation through the circuit. Returns `None` if it was short-circuited.
+    async fn call<T, F>(&self, operation: &'static str, future: F) -> Option<BackendResult<T>>
+    where
+        F: Future<Output = BackendResult<T>>,
+    {
+        let call = self.admit(operation)?;
+        let mut attempt = Attempt {
+            breaker: self,
+            call,
+            settled: false,
+        };
+        let result = future.await;
+        attempt.settled = true;
+        match &result {
+            Ok(_) => self.on_success(call),
+            Err(_) => self.on_failure(call),
+        }
+        Some(result)
+    }
+}
+
+/// Releases the probe slot if the operation future is dropped before completion.
+struct Attempt<'a, B: Backend> {
+    breaker: &'a CircuitBreakerBackend<B>,
+    call: Call,
+    settled: bool,
+}
+
+impl<B: Backend> Drop for Attempt<'_, B> {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.breaker.abandon(self.call);
+        }
+    }
+}
+
+#[async_trait]
+impl<B: Backend> Backend for CircuitBreakerBackend<B> {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        self.call("read", self.inner.read(key))
+            .await
+            .unwrap_or(Ok(None))
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        self.call("write", self.inner.write(key, value))
+            .await
+            .unwrap_or(Ok(()))
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.call("remove", self.inner.remove(key))
+            .await
+            .unwrap_or_else(|| Err(BackendError::InternalError(Box::new(CircuitOpenError))))
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.inner.label()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.inner.value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.inner.key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.inner.compressor()
+    }
+}
+
+impl<B: Backend> CacheBackend for CircuitBreakerBackend<B> {}
@@ -0,0 +1,79 @@
+//! In-process invalidation bus.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use super::{InvalidationBus, InvalidationMessage, InvalidationStream};
+use crate::BackendResult;
+
+/// Default number of messages buffered per subscriber.
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Invalidation bus over an in-process broadcast channel.
+///
+/// Clones share the channel. Connect several backends of one process by
+/// giving each a clone, e.g. to test invalidation between simulated
+/// instances.
+///
+/// # Example
+/// ```ignore
+/// use hitbox_backend::composition::invalidation::ChannelInvalidationBus;
+///
+/// let bus = ChannelInvalidationBus::new();
+/// let a = CompositionBackend::new(moka_a, redis.clone(), offload.clone())
+///     .invalidation(bus.clone());
+/// let b = CompositionBackend::new(moka_b, redis, offload)
+///     .invalidation(bus);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChannelInvalidationBus {
+    sender: broadcast::Sender<InvalidationMessage>,
+}
+
+impl ChannelInvalidationBus {
+    /// Create a bus buffering up to 1024 messages per subscriber.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a bus buffering up to `capacity` messages per subscriber.
+    ///
+    /// Subscribers falling further behind skip the oldest messages.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+}
+
+impl Default for ChannelInvalidationBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl InvalidationBus for ChannelInvalidationBus {
+    async fn publish(&self, message: InvalidationMessage) -> BackendResult<()> {
+        // Sending fails only without subscribers, when there is nobody to invalidate.
+        let _ = self.sender.send(message);
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> BackendResult<InvalidationStream> {
+        let receiver = self.sender.subscribe();
+        let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(message) => return Some((message, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Invalidation subscriber lagged behind");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(stream.boxed())
+    }
+}
@@ -0,0 +1,231 @@
+//! Cross-instance invalidation of L1 entries.
+//!
+//! With a local L1 and a shared L2 across many replicas, a remove or overwrite
+//! only reaches the L1 of the instance that issued it. An [`InvalidationBus`]
+//! carries the affected keys to every other instance, which drops them from
+//! its L1 so the next read goes to L2.
+//!
+//! Attach a bus with [`CompositionBackend::invalidation`]. Each backend
+//! publishes an [`InvalidationMessage`] once a remove or write has reached
+//! L2, and listens for the messages of the other instances. With write
+//! policies that return early, such as write-behind, the message follows the
+//! delayed L2 write rather than the call. Refills are not published.
+//!
+//! # Implementations
+//! - [`ChannelInvalidationBus`] - In-process broadcast channel, for tests and
+//!   for several backends within one process
+//! - `RedisInvalidationBus` in `hitbox-redis` - Redis pub/sub
+//!
+//! Other transports implement [`InvalidationBus`]. Byte-oriented transports
+//! can use [`InvalidationMessage::encode`] and [`InvalidationMessage::decode`].
+//!
+//! # Delivery
+//!
+//! Delivery is best effort: messages published while an instance is not
+//! subscribed are lost, and a lagging subscriber may skip messages. When
+//! subscribing fails or the stream ends, e.g. on a lost connection, the
+//! listener subscribes again with exponential backoff. A
+//! [`TtlPolicy`](super::policy::TtlPolicy) bounds how long a missed
+//! invalidation leaves a stale L1 entry.
+//!
+//! [`CompositionBackend::invalidation`]: super::CompositionBackend::invalidation
+
+mod channel;
+
+pub use channel::ChannelInvalidationBus;
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use hitbox_core::CacheKey;
+
+use crate::format::FormatError;
+use crate::{Backend, BackendResult, CacheKeyFormat};
+
+/// Delay before the first resubscribe attempt.
+const RESUBSCRIBE_BACKOFF_MIN: Duration = Duration::from_millis(100);
+
+/// Upper bound of the delay between resubscribe attempts.
+const RESUBSCRIBE_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// Stream of invalidations received from a bus.
+pub type InvalidationStream = BoxStream<'static, InvalidationMessage>;
+
+/// A key to drop from L1, and the instance that changed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidationMessage {
+    /// Identifier of the publishing instance.
+    pub origin: u64,
+    /// The removed or overwritten key.
+    pub key: CacheKey,
+}
+
+impl InvalidationMessage {
+    /// Create a message for `key` published by `origin`.
+    pub fn new(origin: u64, key: CacheKey) -> Self {
+        Self { origin, key }
+    }
+
+    /// Encodes the message as the big-endian origin followed by the key.
+    pub fn encode(&self) -> Result<Vec<u8>, FormatError> {
+        let key = CacheKeyFormat::Bitcode.serialize(&self.key)?;
+        let mut bytes = Vec::with_capacity(8 + key.len());
+        bytes.extend_from_slice(&self.origin.to_be_bytes());
+        bytes.extend_from_slice(&key);
+        Ok(bytes)
+    }
+
+    /// Decodes a message produced by [`encode`](Self::encode).
+    pub fn decode(bytes: &[u8]) -> Result<Self, FormatError> {
+        let (origin, key) = bytes
+            .split_first_chunk::<8>()
+            .ok_or_else(|| FormatError::Deserialize(Box::new(TruncatedMessage)))?;
+        Ok(Self {
+            origin: u64::from_be_bytes(*origin),
+            key: CacheKeyFormat::Bitcode.deserialize(key)?,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalidation message is shorter than its header")]
+struct TruncatedMessage;
+
+/// Transport for invalidation messages between instances.
+///
+/// Every message published by any instance is delivered to every subscriber,
+/// including the publisher itself; backends skip their own messages by
+/// [`origin`](InvalidationMessage::origin).
+#[async_trait]
+pub trait InvalidationBus: Send + Sync + 'static {
+    /// Publish a message to all subscribers.
+    async fn publish(&self, message: InvalidationMessage) -> BackendResult<()>;
+
+    /// Subscribe to messages published after this call returns.
+    async fn subscribe(&self) -> BackendResult<InvalidationStream>;
+}
+
+/// A bus attached to a composition backend.
+#[derive(Clone)]
+pub(crate) struct Invalidation {
+    bus: Arc<dyn InvalidationBus>,
+    origin: u64,
+    /// Shared by the clones of the backend; the listener stops once all are dropped.
+    alive: Arc<()>,
+}
+
+impl Invalidation {
+    /// Attaches `bus` and starts dropping the keys published by other
+    /// instances from `l1`.
+    ///
+    /// The listener runs on its own Tokio task rather than the offload, which
+    /// may drop or cancel tasks, and stops once every clone is dropped.
+    pub(crate) fn attach<L1>(bus: Arc<dyn InvalidationBus>, l1: L1) -> Self
+    where
+        L1: Backend + Send + Sync + 'static,
+    {
+        let invalidation = Self {
+            bus,
+            origin: new_origin(),
+            alive: Arc::new(()),
+        };
+
+        tokio::spawn(listen(
+            invalidation.bus.clone(),
+            l1,
+            invalidation.origin,
+            Arc::downgrade(&invalidation.alive),
+        ));
+
+        invalidation
+    }
+
+    /// Publishes `key` to the other instances in the background.
+    ///
+    /// Like the listener, this runs on a Tokio task, as an offload dropping
+    /// the task would silently lose the invalidation.
+    pub(crate) fn publish(&self, key: &CacheKey) {
+        let bus = self.bus.clone();
+        let message = InvalidationMessage::new(self.origin, key.clone());
+        tokio::spawn(async move {
+            if let Err(error) = bus.publish(message).await {
+                tracing::warn!(?error, "Invalidation publish failed");
+            }
+        });
+    }
+}
+
+/// Drops the keys published by other instances from `l1`, subscribing again
+/// whenever subscribing fails or the stream ends, until `alive` is gone.
+async fn listen<L1>(bus: Arc<dyn InvalidationBus>, l1: L1, origin: u64, alive: Weak<()>)
+where
+    L1: Backend + Send + Sync + 'static,
+{
+    let mut backoff = RESUBSCRIBE_BACKOFF_MIN;
+    while alive.strong_count() > 0 {
+        match bus.subscribe().await {
+            Ok(mut stream) => {
+                while let Some(message) = stream.next().await {
+                    if alive.strong_count() == 0 {
+                        return;
+                    }
+                    backoff = RESUBSCRIBE_BACKOFF_MIN;
+                    if message.origin == origin {
+                        continue;
+                    }
+                    if let Err(error) = l1.remove(&message.key).await {
+                        tracing::warn!(?error, "L1 invalidation failed");
+                    }
+                }
+                if alive.strong_count() == 0 {
+                    return;
+                }
+                tracing::warn!(retry_in = ?backoff, "Invalidation stream ended, resubscribing");
+            }
+            Err(error) => {
+                tracing::warn!(?error, retry_in = ?backoff, "Invalidation subscribe failed");
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(RESUBSCRIBE_BACKOFF_MAX);
+    }
+}
+
+/// Returns an identifier unique to this backend instance.
+fn new_origin() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = DefaultHasher::new();
+    std::process::id().hash(&mut hasher);
+    SystemTime::now().hash(&mut hasher);
+    COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = InvalidationMessage::new(42, CacheKey::from_str("user", "1"));
+        let bytes = message.encode().unwrap();
+        assert_eq!(InvalidationMessage::decode(&bytes).unwrap(), message);
+    }
+
+    #[test]
+    fn test_truncated_message() {
+        assert!(InvalidationMessage::decode(&[0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_origins_are_unique() {
+        assert_ne!(new_origin(), new_origin());
+    }
+}
@@ -22,6 +22,10 @@
 //! - [`policy::RefillPolicy::Always`] - Populate L1 after L2 hit
 //! - [`policy::RefillPolicy::Never`] - Skip L1 population **(default)**
 //!
+//! ## TTL Policy
+//! - [`policy::TtlPolicy`] - Cap or scale the TTL of L1 entries on writes and
+//!   refills, keeping the value's TTL by default
+//!
 //! # Example
 //!
 //! ```ignore
@@ -38,9 +42,22 @@
 //!
 //! let cache = moka.compose(redis, offload).with_policy(policy);
 //! ```
+//!
+//! For three or more layers, [`TieredBackend`] composes any number of tiers
+//! without nesting.
+//!
+//! # Invalidation
+//!
+//! Across replicas sharing an L2, attach an
+//! [`InvalidationBus`] with
+//! [`CompositionBackend::invalidation`] so that removes and overwrites on one
+//! instance drop the key from the L1 of every other. See the [`invalidation`]
+//! module.
 
 pub mod compose;
+pub mod invalidation;
 pub mod policy;
+pub mod tiered;
 
 mod context;
 mod envelope;
@@ -48,6 +65,7 @@ mod format;
 
 pub use compose::Compose;
 pub use policy::CompositionPolicy;
+pub use tiered::TieredBackend;
 
 // Re-exports for submodules (not part of public API)
 pub(crate) use context::{CompositionContext, CompositionLayer};
@@ -61,15 +79,18 @@ use crate::{
 };
 use async_trait::async_trait;
 use envelope::CompositionEnvelope;
+use futures::future::BoxFuture;
 use hitbox_core::{
     BackendLabel, BoxContext, CacheContext, CacheKey, CacheStatus, CacheValue, Cacheable,
     CacheableResponse, Offload, Raw, ResponseSource,
 };
+use invalidation::{Invalidation, InvalidationBus};
 use policy::{
     CompositionReadPolicy, CompositionWritePolicy, OptimisticParallelWritePolicy, ReadResult,
-    RefillPolicy, SequentialReadPolicy,
+    RefillPolicy, SequentialReadPolicy, TtlPolicy,
 };
 use smol_str::SmolStr;
+use std::future::Future;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -87,6 +108,12 @@ pub enum CompositionError {
         /// Error from L2 layer
         l2: BackendError,
     },
+    /// Every tier of a [`TieredBackend`] failed.
+    #[error("All cache tiers failed: {errors:?}")]
+    AllTiersFailed {
+        /// Errors in tier order
+        errors: Vec<BackendError>,
+    },
 }
 
 /// A backend that composes two cache backends into a layered caching system.
@@ -126,12 +153,16 @@ pub struct CompositionBackend<
     write_policy: W,
     /// Refill policy
     refill_policy: RefillPolicy,
+    /// TTL policy for L1 writes and refills
+    ttl_policy: TtlPolicy,
     /// Label of this backend for source path composition
     label: BackendLabel,
     /// Pre-computed metrics label for L1: "{label}.{l1.label()}"
     l1_label: SmolStr,
     /// Pre-computed metrics label for L2: "{label}.{l2.label()}"
     l2_label: SmolStr,
+    /// Bus publishing and receiving L1 invalidations, if attached
+    invalidation: Option<Invalidation>,
 }
 
 /// Helper to compose a metrics label: "{prefix}.{suffix}"
@@ -177,9 +208,11 @@ where
             read_policy: SequentialReadPolicy::new(),
             write_policy: OptimisticParallelWritePolicy::new(),
             refill_policy: RefillPolicy::default(),
+            ttl_policy: TtlPolicy::default(),
             label,
             l1_label,
             l2_label,
+            invalidation: None,
         }
     }
 }
@@ -207,6 +240,11 @@ where
         &self.refill_policy
     }
 
+    /// Returns a reference to the TTL policy.
+    pub fn ttl_policy(&self) -> &TtlPolicy {
+        &self.ttl_policy
+    }
+
     /// Returns a reference to the offload manager.
     pub fn offload(&self) -> &O {
         &self.offload
@@ -260,9 +298,11 @@ where
             read_policy: policy.read,
             write_policy: policy.write,
             refill_policy: policy.refill,
+            ttl_policy: policy.ttl,
             label: self.label,
             l1_label: self.l1_label,
             l2_label: self.l2_label,
+            invalidation: self.invalidation,
         }
     }
 
@@ -290,9 +330,11 @@ where
             read_policy,
             write_policy: self.write_policy,
             refill_policy: self.refill_policy,
+            ttl_policy: self.ttl_policy,
             label: self.label,
             l1_label: self.l1_label,
             l2_label: self.l2_label,
+            invalidation: self.invalidation,
         }
     }
 
@@ -320,9 +362,11 @@ where
             read_policy: self.read_policy,
             write_policy,
             refill_policy: self.refill_policy,
+            ttl_policy: self.ttl_policy,
             label: self.label,
             l1_label: self.l1_label,
             l2_label: self.l2_label,
+            invalidation: self.invalidation,
         }
     }
 
@@ -342,6 +386,79 @@ where
         self.refill_policy = refill_policy;
         self
     }
+
+    /// Set the TTL policy for L1 writes and refills (builder pattern).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use std::time::Duration;
+    /// use hitbox_backend::CompositionBackend;
+    /// use hitbox_backend::composition::policy::TtlPolicy;
+    ///
+    /// let backend = CompositionBackend::new(l1, l2, offload)
+    ///     .ttl(TtlPolicy::new().max(Duration::from_secs(5)));
+    /// ```
+    pub fn ttl(mut self, ttl_policy: TtlPolicy) -> Self {
+        self.ttl_policy = ttl_policy;
+        self
+    }
+
+    /// Attach an invalidation bus shared with other instances (builder pattern).
+    ///
+    /// Removes and writes reaching L2 are published to the bus, and keys
+    /// published by other instances are dropped from this L1. The listener
+    /// runs on its own Tokio task until every clone of this backend is
+    /// dropped, and subscribes again if its subscription fails or ends.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use hitbox_backend::CompositionBackend;
+    /// use hitbox_redis::RedisInvalidationBus;
+    ///
+    /// let bus = RedisInvalidationBus::new("redis://localhost:6379/")?;
+    /// let backend = CompositionBackend::new(moka, redis, offload)
+    ///     .invalidation(bus);
+    /// ```
+    pub fn invalidation(mut self, bus: impl InvalidationBus) -> Self
+    where
+        L1: Clone + 'static,
+    {
+        self.invalidation = Some(Invalidation::attach(Arc::new(bus), self.l1.clone()));
+        self
+    }
+
+    /// Publishes `key` to other instances if a bus is attached.
+    fn publish_invalidation(&self, key: &CacheKey) {
+        if let Some(invalidation) = &self.invalidation {
+            invalidation.publish(key);
+        }
+    }
+
+    /// Wraps an L2 write so its key is published once L2 holds the new value.
+    ///
+    /// Race, optimistic parallel and write-behind policies may return before
+    /// L2 is written. Publishing then would let other instances drop their
+    /// L1, miss, and refill it with the old L2 value.
+    fn publish_after_l2<F, Fut>(
+        &self,
+        write_l2: F,
+    ) -> impl FnOnce(CacheKey) -> BoxFuture<'static, BackendResult<()>> + Send
+    where
+        F: FnOnce(CacheKey) -> Fut + Send,
+        Fut: Future<Output = BackendResult<()>> + Send + 'static,
+    {
+        let invalidation = self.invalidation.clone();
+        move |key: CacheKey| {
+            let write = write_l2(key.clone());
+            Box::pin(async move {
+                write.await?;
+                if let Some(invalidation) = invalidation {
+                    invalidation.publish(&key);
+                }
+                Ok(())
+            })
+        }
+    }
 }
 
 impl<L1, L2, O, R, W> Clone for CompositionBackend<L1, L2, O, R, W>
@@ -360,10 +477,12 @@ where
             offload: self.offload.clone(),
             read_policy: self.read_policy.clone(),
             write_policy: self.write_policy.clone(),
-            refill_policy: self.refill_policy,
+            refill_policy: self.refill_policy.clone(),
+            ttl_policy: self.ttl_policy,
             label: self.label.clone(),
             l1_label: self.l1_label.clone(),
             l2_label: self.l2_label.clone(),
+            invalidation: self.invalidation.clone(),
         }
     }
 }
@@ -386,6 +505,8 @@ where
             .field("read_policy", &self.read_policy)
             .field("write_policy", &self.write_policy)
             .field("refill_policy", &self.refill_policy)
+            .field("ttl_policy", &self.ttl_policy)
+            .field("invalidation", &self.invalidation.is_some())
             .finish()
     }
 }
@@ -484,12 +605,19 @@ where
     async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
         // Unpack CompositionEnvelope using zero-copy format
         let composition = CompositionEnvelope::deserialize(value.data())?;
+        // Layer values are packed by CompositionFormat without metadata,
+        // so they take the expiry of the outer value.
+        let (expire, stale) = (value.expire(), value.stale());
 
         // Write to appropriate layers
         // In normal usage via CacheBackend::set, this is always Both variant
         // The L1/L2 branches are defensive code for edge cases
         match composition {
             CompositionEnvelope::Both { l1, l2 } => {
+                let l1 = self
+                    .ttl_policy
+                    .apply(CacheValue::new(l1.into_parts().1, expire, stale));
+                let l2 = CacheValue::new(l2.into_parts().1, expire, stale);
                 // Clone backends for 'static closures
                 let l1_backend = self.l1.clone();
                 let l2_backend = self.l2.clone();
@@ -520,11 +648,20 @@ where
                     result
                 };
 
+                let write_l2 = self.publish_after_l2(write_l2);
                 self.write_policy
                     .execute_with(key.clone(), write_l1, write_l2, &self.offload)
                     .await
             }
             CompositionEnvelope::L1(l1) => {
+                // L1-only envelopes are produced by refills
+                if !self.refill_policy.admit(key) || !self.refill_policy.admit_size(l1.data().len())
+                {
+                    return Ok(());
+                }
+                let l1 = self
+                    .ttl_policy
+                    .apply(CacheValue::new(l1.into_parts().1, expire, stale));
                 let l1_len = l1.data().len();
                 let timer = Timer::new();
                 let result = self.l1.write(key, l1).await;
@@ -536,12 +673,16 @@ where
                 result
             }
             CompositionEnvelope::L2(l2) => {
+                let l2 = CacheValue::new(l2.into_parts().1, expire, stale);
                 let l2_len = l2.data().len();
                 let timer = Timer::new();
                 let result = self.l2.write(key, l2).await;
                 crate::metrics::record_write(&self.l2_label, timer.elapsed());
                 match &result {
-                    Ok(()) => crate::metrics::record_write_bytes(&self.l2_label, l2_len),
+                    Ok(()) => {
+                        crate::metrics::record_write_bytes(&self.l2_label, l2_len);
+                        self.publish_invalidation(key);
+                    }
                     Err(_) => crate::metrics::record_write_error(&self.l2_label),
                 }
                 result
@@ -553,8 +694,9 @@ where
     async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
         // Delete from both layers in parallel for better performance
         let (l1_result, l2_result) = futures::join!(self.l1.remove(key), self.l2.remove(key));
+        let l2_removed = l2_result.is_ok();
 
-        match (l1_result, l2_result) {
+        let result = match (l1_result, l2_result) {
             (Err(e1), Err(e2)) => {
                 tracing::error!(l1_error = ?e1, l2_error = ?e2, "Both L1 and L2 delete failed");
                 Err(BackendError::InternalError(Box::new(
@@ -577,7 +719,12 @@ where
                 Ok(DeleteStatus::Deleted(n))
             }
             (Ok(DeleteStatus::Missing), Ok(DeleteStatus::Missing)) => Ok(DeleteStatus::Missing),
+        };
+        // Other instances would refill their L1 from an L2 that still has the key
+        if l2_removed {
+            self.publish_invalidation(key);
         }
+        result
     }
 
     fn label(&self) -> BackendLabel {
@@ -597,6 +744,62 @@ where
     }
 }
 
+impl<L1, L2, O, R, W> CompositionBackend<L1, L2, O, R, W>
+where
+    L1: CacheBackend + Clone + Send + Sync + 'static,
+    L2: CacheBackend + Clone + Send + Sync + 'static,
+    O: Offload<'static>,
+    R: CompositionReadPolicy,
+    W: CompositionWritePolicy,
+{
+    /// Writes a value read from L2 to L1 if the refill policy admits it.
+    async fn refill_l1<T>(
+        &self,
+        key: &CacheKey,
+        value: &CacheValue<T::Cached>,
+        ctx: &BoxContext,
+    ) -> BackendResult<()>
+    where
+        T: CacheableResponse,
+        T::Cached: Cacheable,
+    {
+        if !self.refill_policy.admit(key) {
+            return Ok(());
+        }
+
+        let l1_bytes = self
+            .format
+            .serialize_layer(
+                CompositionLayer::L1,
+                &mut |serializer| {
+                    serializer.serialize(value.data())?;
+                    Ok(())
+                },
+                &**ctx,
+            )
+            .map_err(|e| BackendError::InternalError(Box::new(e)))?;
+
+        let l1_len = l1_bytes.len();
+        if !self.refill_policy.admit_size(l1_len) {
+            tracing::trace!(size = l1_len, "Entry too large for L1 refill");
+            return Ok(());
+        }
+        let l1_value =
+            self.ttl_policy
+                .apply(CacheValue::new(l1_bytes, value.expire(), value.stale()));
+
+        // Write to L1 with metrics
+        let timer = Timer::new();
+        let result = self.l1.write(key, l1_value).await;
+        crate::metrics::record_write(&self.l1_label, timer.elapsed());
+        match &result {
+            Ok(()) => crate::metrics::record_write_bytes(&self.l1_label, l1_len),
+            Err(_) => crate::metrics::record_write_error(&self.l1_label),
+        }
+        result
+    }
+}
+
 impl<L1, L2, O, R, W> CacheBackend for CompositionBackend<L1, L2, O, R, W>
 where
     L1: CacheBackend + Clone + Send + Sync + 'static,
@@ -781,9 +984,9 @@ where
         if let Some(ref _cache_value) = value {
             ctx.merge_from(&*inner_ctx, &self.label);
 
-            // If L2 hit and refill policy is Always, set ReadMode::Refill
-            // CacheFuture will handle the actual refill via set()
-            if source == CompositionLayer::L2 && self.refill_policy == RefillPolicy::Always {
+            // If L2 hit and refill is enabled, set ReadMode::Refill
+            // CacheFuture will handle the actual refill via set(), where admission is checked
+            if source == CompositionLayer::L2 && self.refill_policy != RefillPolicy::Never {
                 ctx.set_read_mode(hitbox_core::ReadMode::Refill);
             }
         }
@@ -807,44 +1010,15 @@ where
         // Check if this is a refill operation (triggered by CacheFuture after L2 hit)
         // This happens when CacheBackend::get() sets ReadMode::Refill
         if ctx.read_mode() == ReadMode::Refill {
-            match self.refill_policy {
-                RefillPolicy::Always => {
-                    // Refill L1 only - write serialized data to L1
-                    let l1_bytes = self
-                        .format
-                        .serialize_layer(
-                            CompositionLayer::L1,
-                            &mut |serializer| {
-                                serializer.serialize(value.data())?;
-                                Ok(())
-                            },
-                            &**ctx,
-                        )
-                        .map_err(|e| BackendError::InternalError(Box::new(e)))?;
-
-                    let l1_len = l1_bytes.len();
-                    let l1_value = CacheValue::new(l1_bytes, value.expire(), value.stale());
-
-                    // Write to L1 with metrics
-                    let timer = Timer::new();
-                    let result = self.l1.write(key, l1_value).await;
-                    crate::metrics::record_write(&self.l1_label, timer.elapsed());
-                    match &result {
-                        Ok(()) => crate::metrics::record_write_bytes(&self.l1_label, l1_len),
-                        Err(_) => crate::metrics::record_write_error(&self.l1_label),
-                    }
-                    result?;
-
-                    // Recursively call L2.set() for nested refill
-                    // L2 (if it's a CompositionBackend) will handle its own refill logic
-                    return self.l2.set::<T>(key, value, ctx).await;
-                }
-                RefillPolicy::Never => {
-                    // With Never policy, don't refill at all
-                    // L2 already has the data (it's the source), so skip write
-                    return Ok(());
-                }
+            if self.refill_policy == RefillPolicy::Never {
+                // L2 already has the data (it's the source), so skip write
+                return Ok(());
             }
+            self.refill_l1::<T>(key, value, ctx).await?;
+
+            // Recursively call L2.set() for nested refill
+            // L2 (if it's a CompositionBackend) will handle its own refill logic
+            return self.l2.set::<T>(key, value, ctx).await;
         }
 
         // Check if this is a nested refill operation via CompositionContext
@@ -852,45 +1026,13 @@ where
         if let Some(comp_ctx) = ctx.as_any().downcast_ref::<CompositionContext>()
             && comp_ctx.layer == CompositionLayer::L2
         {
-            match self.refill_policy {
-                RefillPolicy::Always => {
-                    // This level needs refill: write to L1 only
-                    let l1_bytes = self
-                        .format
-                        .serialize_layer(
-                            CompositionLayer::L1,
-                            &mut |serializer| {
-                                serializer.serialize(value.data())?;
-                                Ok(())
-                            },
-                            &**ctx,
-                        )
-                        .map_err(|e| BackendError::InternalError(Box::new(e)))?;
-
-                    let l1_len = l1_bytes.len();
-                    let l1_value = CacheValue::new(l1_bytes, value.expire(), value.stale());
-
-                    // Write to L1 with metrics
-                    let timer = Timer::new();
-                    let result = self.l1.write(key, l1_value).await;
-                    crate::metrics::record_write(&self.l1_label, timer.elapsed());
-                    match &result {
-                        Ok(()) => crate::metrics::record_write_bytes(&self.l1_label, l1_len),
-                        Err(_) => crate::metrics::record_write_error(&self.l1_label),
-                    }
-                    result?;
+            // This level may need refill: write to L1 only
+            self.refill_l1::<T>(key, value, ctx).await?;
 
-                    // Recursively call L2.set() with inner context for nested refill
-                    // Inner context may be another CompositionContext (nested) or CacheContext (leaf)
-                    let mut inner_ctx = comp_ctx.inner().clone_box();
-                    return self.l2.set::<T>(key, value, &mut inner_ctx).await;
-                }
-                RefillPolicy::Never => {
-                    // Skip L1 write (no refill), but recurse to L2 for nested handling
-                    let mut inner_ctx = comp_ctx.inner().clone_box();
-                    return self.l2.set::<T>(key, value, &mut inner_ctx).await;
-                }
-            }
+            // Recursively call L2.set() with inner context for nested refill
+            // Inner context may be another CompositionContext (nested) or CacheContext (leaf)
+            let mut inner_ctx = comp_ctx.inner().clone_box();
+            return self.l2.set::<T>(key, value, &mut inner_ctx).await;
         }
 
         // Normal mode: write to both layers
@@ -911,7 +1053,9 @@ where
         let l2_len = l2_bytes.len();
 
         // Create raw values for Backend::write
-        let l1_value = CacheValue::new(l1_bytes, value.expire(), value.stale());
+        let l1_value =
+            self.ttl_policy
+                .apply(CacheValue::new(l1_bytes, value.expire(), value.stale()));
         let l2_value = CacheValue::new(l2_bytes, value.expire(), value.stale());
 
         // Clone backends for 'static closures
@@ -945,6 +1089,7 @@ where
             result
         };
 
+        let write_l2 = self.publish_after_l2(write_l2);
         self.write_policy
             .execute_with(key.clone(), write_l1, write_l2, &self.offload)
             .await
@@ -959,9 +1104,10 @@ where
             self.l1.delete(key, &mut l1_ctx),
             self.l2.delete(key, &mut l2_ctx)
         );
+        let l2_removed = l2_result.is_ok();
 
         // Aggregate results
-        match (l1_result, l2_result) {
+        let result = match (l1_result, l2_result) {
             (Err(e1), Err(e2)) => {
                 tracing::error!(l1_error = ?e1, l2_error = ?e2, "Both L1 and L2 delete failed");
                 Err(BackendError::InternalError(Box::new(
@@ -989,7 +1135,12 @@ where
                 tracing::trace!("Key missing from both layers");
                 Ok(DeleteStatus::Missing)
             }
+        };
+        // Other instances would refill their L1 from an L2 that still has the key
+        if l2_removed {
+            self.publish_invalidation(key);
         }
+        result
     }
 }
 
@@ -2,12 +2,12 @@
 
 use super::{
     CompositionReadPolicy, CompositionWritePolicy, OptimisticParallelWritePolicy, RefillPolicy,
-    SequentialReadPolicy,
+    SequentialReadPolicy, TtlPolicy,
 };
 
-/// Bundle of read, write, and refill policies for CompositionBackend.
+/// Bundle of read, write, refill, and TTL policies for CompositionBackend.
 ///
-/// This struct provides a builder pattern for configuring all three policy types
+/// This struct provides a builder pattern for configuring all policy types
 /// together, making it easy to create and reuse policy configurations.
 ///
 /// # Example
@@ -36,6 +36,8 @@ where
     pub(crate) write: W,
     /// Refill policy
     pub(crate) refill: RefillPolicy,
+    /// TTL policy for L1 writes and refills
+    pub(crate) ttl: TtlPolicy,
 }
 
 impl CompositionPolicy<SequentialReadPolicy, OptimisticParallelWritePolicy> {
@@ -45,11 +47,13 @@ impl CompositionPolicy<SequentialReadPolicy, OptimisticParallelWritePolicy> {
     /// - Read: `SequentialReadPolicy` (try L1 first, then L2)
     /// - Write: `OptimisticParallelWritePolicy` (write to both, succeed if ≥1 succeeds)
     /// - Refill: `RefillPolicy::Never` (do not populate L1 after L2 hit)
+    /// - TTL: `TtlPolicy::new()` (L1 entries keep the TTL of the value)
     pub fn new() -> Self {
         Self {
             read: SequentialReadPolicy::new(),
             write: OptimisticParallelWritePolicy::new(),
             refill: RefillPolicy::default(),
+            ttl: TtlPolicy::default(),
         }
     }
 }
@@ -80,6 +84,7 @@ where
             read,
             write: self.write,
             refill: self.refill,
+            ttl: self.ttl,
         }
     }
 
@@ -98,6 +103,7 @@ where
             read: self.read,
             write,
             refill: self.refill,
+            ttl: self.ttl,
         }
     }
 
@@ -116,9 +122,26 @@ where
             read: self.read,
             write: self.write,
             refill,
+            ttl: self.ttl,
         }
     }
 
+    /// Set the TTL policy for L1 writes and refills (builder pattern).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use std::time::Duration;
+    /// use hitbox_backend::composition::CompositionPolicy;
+    /// use hitbox_backend::composition::policy::TtlPolicy;
+    ///
+    /// let policy = CompositionPolicy::new()
+    ///     .ttl(TtlPolicy::new().max(Duration::from_secs(5)));
+    /// ```
+    pub fn ttl(mut self, ttl: TtlPolicy) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
     /// Get a reference to the read policy.
     pub fn read_policy(&self) -> &R {
         &self.read
@@ -133,4 +156,9 @@ where
     pub fn refill_policy(&self) -> &RefillPolicy {
         &self.refill
     }
+
+    /// Get a reference to the TTL policy.
+    pub fn ttl_policy(&self) -> &TtlPolicy {
+        &self.ttl
+    }
 }
@@ -15,10 +15,22 @@
 //! - [`SequentialWritePolicy`] - Write to L1, then L2 (write-through)
 //! - [`OptimisticParallelWritePolicy`] - Write to both in parallel (join), succeed if ≥1 succeeds
 //! - [`RaceWritePolicy`] - Race both writes, return on first success, background the other
+//! - [`WriteBehindWritePolicy`] - Write L1, queue L2 and flush it in coalesced batches
 //!
 //! ## Refill Policies
 //! - [`RefillPolicy::Always`] - Always populate L1 after L2 hit
 //! - [`RefillPolicy::Never`] - Never populate L1 after L2 hit (default)
+//! - [`RefillPolicy::Frequency`] - Populate L1 with keys read often enough,
+//!   counted by a [`FrequencyAdmission`] sketch
+//! - [`RefillPolicy::MaxSize`] - Populate L1 only with entries up to a size
+//!
+//! ## TTL Policy
+//! - [`TtlPolicy`] - Cap or scale the TTL of entries written to L1, on
+//!   writes and refills (default: keep the TTL)
+//!
+//! ## Tier Policies
+//! - [`TierPolicy`] - Read, write and refill policies of one tier of a
+//!   [`TieredBackend`](crate::composition::TieredBackend)
 //!
 //! ## Delete Policies (Future)
 //! - `SequentialDeletePolicy` - Delete from L1, then L2
@@ -27,6 +39,8 @@
 pub mod builder;
 pub mod read;
 pub mod refill;
+pub mod tier;
+pub mod ttl;
 pub mod write;
 
 // Re-export policy builder
@@ -39,10 +53,16 @@ pub use read::{
 };
 
 // Re-export refill policy
-pub use refill::RefillPolicy;
+pub use refill::{FrequencyAdmission, RefillPolicy};
+
+// Re-export tier policies
+pub use tier::{TierPolicy, TierReadPolicy, TierWritePolicy};
+
+// Re-export TTL policy
+pub use ttl::TtlPolicy;
 
 // Re-export write policies
 pub use write::{
     CompositionWritePolicy, OptimisticParallelWritePolicy, RaceLoserPolicy as RaceWriteLoserPolicy,
-    RaceWritePolicy, SequentialWritePolicy,
+    RaceWritePolicy, SequentialWritePolicy, WriteBehindWritePolicy,
 };
@@ -0,0 +1,228 @@
+//! Frequency-based admission for L1 refill.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use hitbox_core::CacheKey;
+
+/// Number of counter rows in the count-min sketch.
+const DEPTH: usize = 4;
+
+/// Default number of distinct keys the sketch is sized for.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Admission filter counting how often keys are read from L2.
+///
+/// A key is admitted to L1 once it has been read `min_hits` times within the
+/// current window. The first read of a key only sets its bits in a doorkeeper
+/// bloom filter, so one-hit wonders never reach the count-min sketch behind
+/// it. Both are cleared when the window elapses.
+///
+/// Counts are approximate: hash collisions can only overestimate, so a key
+/// may occasionally be admitted early but never late.
+///
+/// Clones share their counters. Create a separate filter for each layer.
+///
+/// # Example
+/// ```ignore
+/// use std::time::Duration;
+/// use hitbox_backend::composition::policy::{FrequencyAdmission, RefillPolicy};
+///
+/// // Refill L1 on the second L2 hit within a minute.
+/// let refill = RefillPolicy::Frequency(FrequencyAdmission::new(2, Duration::from_secs(60)));
+/// ```
+#[derive(Clone)]
+pub struct FrequencyAdmission {
+    inner: Arc<Sketch>,
+}
+
+struct Sketch {
+    min_hits: u8,
+    window: Duration,
+    mask: usize,
+    counters: Box<[AtomicU8]>,
+    doorkeeper: Box<[AtomicU64]>,
+    created: Instant,
+    /// Start of the current window, in milliseconds since `created`.
+    window_start: AtomicU64,
+}
+
+impl FrequencyAdmission {
+    /// Creates a filter admitting keys read `min_hits` times within `window`.
+    ///
+    /// A `min_hits` of 0 or 1 admits every key on its first read.
+    pub fn new(min_hits: u8, window: Duration) -> Self {
+        Self::with_capacity(min_hits, window, DEFAULT_CAPACITY)
+    }
+
+    /// Creates a filter sized for about `capacity` distinct keys per window.
+    pub fn with_capacity(min_hits: u8, window: Duration, capacity: usize) -> Self {
+        let width = capacity.max(64).next_power_of_two();
+        Self {
+            inner: Arc::new(Sketch {
+                min_hits: min_hits.max(1),
+                window,
+                mask: width - 1,
+                counters: (0..DEPTH * width).map(|_| AtomicU8::new(0)).collect(),
+                // 8 bits per key keeps false positives around 5% with two probes.
+                doorkeeper: (0..width / 8).map(|_| AtomicU64::new(0)).collect(),
+                created: Instant::now(),
+                window_start: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns the number of reads required for admission.
+    pub fn min_hits(&self) -> u8 {
+        self.inner.min_hits
+    }
+
+    /// Returns the counting window.
+    pub fn window(&self) -> Duration {
+        self.inner.window
+    }
+
+    /// Records a read of `key` and returns whether it should be admitted.
+    pub fn admit(&self, key: &CacheKey) -> bool {
+        let sketch = &self.inner;
+        sketch.rotate();
+
+        if sketch.min_hits == 1 {
+            return true;
+        }
+        let hash = hash(key);
+        if !sketch.doorkeeper_insert(hash) {
+            return false;
+        }
+        // The doorkeeper accounts for the first read.
+        sketch.increment(hash).saturating_add(1) >= sketch.min_hits
+    }
+}
+
+impl Sketch {
+    /// Clears all counters once the window has elapsed.
+    fn rotate(&self) {
+        let now = self.created.elapsed().as_millis() as u64;
+        let start = self.window_start.load(Ordering::Relaxed);
+        if now.saturating_sub(start) < self.window.as_millis() as u64 {
+            return;
+        }
+        // Only the thread moving the window clears the counters.
+        if self
+            .window_start
+            .compare_exchange(start, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            self.counters
+                .iter()
+                .for_each(|counter| counter.store(0, Ordering::Relaxed));
+            self.doorkeeper
+                .iter()
+                .for_each(|word| word.store(0, Ordering::Relaxed));
+        }
+    }
+
+    /// Sets the doorkeeper bits of `hash`, returning whether all were set.
+    fn doorkeeper_insert(&self, hash: u64) -> bool {
+        let bits = self.doorkeeper.len() * 64;
+        let mut present = true;
+        for probe in [hash, hash.rotate_left(32)] {
+            let bit = probe as usize % bits;
+            let mask = 1u64 << (bit % 64);
+            let previous = self.doorkeeper[bit / 64].fetch_or(mask, Ordering::Relaxed);
+            present &= previous & mask != 0;
+        }
+        present
+    }
+
+    /// Increments the counters of `hash` and returns the new estimate.
+    fn increment(&self, hash: u64) -> u8 {
+        let width = self.mask + 1;
+        let (h1, h2) = (hash as usize, ((hash >> 32) as usize) | 1);
+        (0..DEPTH)
+            .map(|row| {
+                let index = row * width + (h1.wrapping_add(row.wrapping_mul(h2)) & self.mask);
+                let previous = self.counters[index]
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                        Some(count.saturating_add(1))
+                    })
+                    .unwrap_or(u8::MAX);
+                previous.saturating_add(1)
+            })
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+fn hash(key: &CacheKey) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl PartialEq for FrequencyAdmission {
+    /// Filters are equal when configured alike; their counts are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.min_hits == other.inner.min_hits
+            && self.inner.window == other.inner.window
+            && self.inner.mask == other.inner.mask
+    }
+}
+
+impl Eq for FrequencyAdmission {}
+
+impl std::fmt::Debug for FrequencyAdmission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrequencyAdmission")
+            .field("min_hits", &self.inner.min_hits)
+            .field("window", &self.inner.window)
+            .field("width", &(self.inner.mask + 1))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admits_on_min_hits() {
+        let filter = FrequencyAdmission::new(3, Duration::from_secs(60));
+        let key = CacheKey::from_str("frequency", "hot");
+
+        assert!(!filter.admit(&key));
+        assert!(!filter.admit(&key));
+        assert!(filter.admit(&key));
+        assert!(filter.admit(&key));
+    }
+
+    #[test]
+    fn test_one_hit_wonders_are_rejected() {
+        let filter = FrequencyAdmission::new(2, Duration::from_secs(60));
+        let admitted = (0..1000)
+            .filter(|i| filter.admit(&CacheKey::from_str("frequency", &i.to_string())))
+            .count();
+        // Only doorkeeper false positives slip through.
+        assert!(admitted < 100, "admitted {admitted} of 1000 unique keys");
+    }
+
+    #[test]
+    fn test_window_resets_counts() {
+        let filter = FrequencyAdmission::new(2, Duration::ZERO);
+        let key = CacheKey::from_str("frequency", "cold");
+
+        assert!(!filter.admit(&key));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(!filter.admit(&key));
+    }
+
+    #[test]
+    fn test_single_hit_admits_everything() {
+        let filter = FrequencyAdmission::new(0, Duration::from_secs(60));
+        assert_eq!(filter.min_hits(), 1);
+        assert!(filter.admit(&CacheKey::from_str("frequency", "any")));
+    }
+}
@@ -1,14 +1,48 @@
 //! Refill policies for controlling L1 population after L2 hits.
 
+mod frequency;
+
+pub use frequency::FrequencyAdmission;
+
+use hitbox_core::CacheKey;
+
 /// Policy for controlling L1 refill after L2 hits.
 ///
 /// When a read misses L1 but hits L2, the refill policy determines whether
 /// to populate L1 with the value from L2.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+///
+/// Refilled entries keep the expiry of the L2 entry unless a
+/// [`TtlPolicy`](super::TtlPolicy) limits it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum RefillPolicy {
     /// Always populate L1 after L2 hit (classic cache hierarchy behavior).
     Always,
     /// Never populate L1 from L2 hits (L1 is write-only).
     #[default]
     Never,
+    /// Populate L1 only with keys read from L2 often enough, keeping
+    /// one-hit wonders out of L1.
+    Frequency(FrequencyAdmission),
+    /// Populate L1 only with entries of at most this many bytes, as encoded
+    /// for L1.
+    MaxSize(usize),
+}
+
+impl RefillPolicy {
+    /// Records an L2 hit of `key` and returns whether L1 may be refilled.
+    pub fn admit(&self, key: &CacheKey) -> bool {
+        match self {
+            Self::Always | Self::MaxSize(_) => true,
+            Self::Never => false,
+            Self::Frequency(filter) => filter.admit(key),
+        }
+    }
+
+    /// Returns whether an entry of `size` encoded bytes may be refilled.
+    pub fn admit_size(&self, size: usize) -> bool {
+        match self {
+            Self::MaxSize(max) => size <= *max,
+            _ => true,
+        }
+    }
 }
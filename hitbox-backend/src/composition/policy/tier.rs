@@ -0,0 +1,163 @@
+//! Runtime-selected policies for the tiers of a [`TieredBackend`].
+//!
+//! [`CompositionBackend`](crate::CompositionBackend) picks its policies through
+//! type parameters. The tiers of a [`TieredBackend`] live in one list, so each
+//! tier stores its policies as the enums below instead. Both enums implement
+//! the regular policy traits by delegating to the wrapped policy.
+//!
+//! [`TieredBackend`]: crate::composition::TieredBackend
+
+use async_trait::async_trait;
+use hitbox_core::{BoxContext, CacheKey, CacheValue, Offload};
+use std::future::Future;
+
+use super::{
+    CompositionPolicy, CompositionReadPolicy, CompositionWritePolicy,
+    OptimisticParallelWritePolicy, ParallelReadPolicy, RaceReadPolicy, RaceWritePolicy, ReadResult,
+    SequentialReadPolicy, SequentialWritePolicy,
+};
+use crate::BackendError;
+
+/// Policies of a single tier: how it is read and written relative to the
+/// slower tiers, whether it is refilled on their hits, and how long it keeps
+/// entries.
+pub type TierPolicy = CompositionPolicy<TierReadPolicy, TierWritePolicy>;
+
+/// Any of the built-in read policies.
+#[derive(Debug, Clone, Copy)]
+pub enum TierReadPolicy {
+    /// See [`SequentialReadPolicy`].
+    Sequential(SequentialReadPolicy),
+    /// See [`RaceReadPolicy`].
+    Race(RaceReadPolicy),
+    /// See [`ParallelReadPolicy`].
+    Parallel(ParallelReadPolicy),
+}
+
+impl Default for TierReadPolicy {
+    fn default() -> Self {
+        Self::Sequential(SequentialReadPolicy::new())
+    }
+}
+
+impl From<SequentialReadPolicy> for TierReadPolicy {
+    fn from(policy: SequentialReadPolicy) -> Self {
+        Self::Sequential(policy)
+    }
+}
+
+impl From<RaceReadPolicy> for TierReadPolicy {
+    fn from(policy: RaceReadPolicy) -> Self {
+        Self::Race(policy)
+    }
+}
+
+impl From<ParallelReadPolicy> for TierReadPolicy {
+    fn from(policy: ParallelReadPolicy) -> Self {
+        Self::Parallel(policy)
+    }
+}
+
+#[async_trait]
+impl CompositionReadPolicy for TierReadPolicy {
+    async fn execute_with<T, E, F1, F2, Fut1, Fut2, O>(
+        &self,
+        key: CacheKey,
+        read_l1: F1,
+        read_l2: F2,
+        offload: &O,
+    ) -> Result<ReadResult<T>, E>
+    where
+        T: Send + 'static,
+        E: Send + std::fmt::Debug + 'static,
+        F1: FnOnce(CacheKey) -> Fut1 + Send,
+        F2: FnOnce(CacheKey) -> Fut2 + Send,
+        Fut1: Future<Output = (Result<Option<CacheValue<T>>, E>, BoxContext)> + Send + 'static,
+        Fut2: Future<Output = (Result<Option<CacheValue<T>>, E>, BoxContext)> + Send + 'static,
+        O: Offload<'static>,
+    {
+        match self {
+            Self::Sequential(policy) => policy.execute_with(key, read_l1, read_l2, offload).await,
+            Self::Race(policy) => policy.execute_with(key, read_l1, read_l2, offload).await,
+            Self::Parallel(policy) => policy.execute_with(key, read_l1, read_l2, offload).await,
+        }
+    }
+}
+
+/// Any of the built-in write policies.
+#[derive(Debug, Clone, Copy)]
+pub enum TierWritePolicy {
+    /// See [`SequentialWritePolicy`].
+    Sequential(SequentialWritePolicy),
+    /// See [`OptimisticParallelWritePolicy`].
+    OptimisticParallel(OptimisticParallelWritePolicy),
+    /// See [`RaceWritePolicy`].
+    Race(RaceWritePolicy),
+}
+
+impl Default for TierWritePolicy {
+    fn default() -> Self {
+        Self::OptimisticParallel(OptimisticParallelWritePolicy::new())
+    }
+}
+
+impl From<SequentialWritePolicy> for TierWritePolicy {
+    fn from(policy: SequentialWritePolicy) -> Self {
+        Self::Sequential(policy)
+    }
+}
+
+impl From<OptimisticParallelWritePolicy> for TierWritePolicy {
+    fn from(policy: OptimisticParallelWritePolicy) -> Self {
+        Self::OptimisticParallel(policy)
+    }
+}
+
+impl From<RaceWritePolicy> for TierWritePolicy {
+    fn from(policy: RaceWritePolicy) -> Self {
+        Self::Race(policy)
+    }
+}
+
+#[async_trait]
+impl CompositionWritePolicy for TierWritePolicy {
+    async fn execute_with<F1, F2, Fut1, Fut2, O>(
+        &self,
+        key: CacheKey,
+        write_l1: F1,
+        write_l2: F2,
+        offload: &O,
+    ) -> Result<(), BackendError>
+    where
+        F1: FnOnce(CacheKey) -> Fut1 + Send,
+        F2: FnOnce(CacheKey) -> Fut2 + Send,
+        Fut1: Future<Output = Result<(), BackendError>> + Send + 'static,
+        Fut2: Future<Output = Result<(), BackendError>> + Send + 'static,
+        O: Offload<'static>,
+    {
+        match self {
+            Self::Sequential(policy) => policy.execute_with(key, write_l1, write_l2, offload).await,
+            Self::OptimisticParallel(policy) => {
+                policy.execute_with(key, write_l1, write_l2, offload).await
+            }
+            Self::Race(policy) => policy.execute_with(key, write_l1, write_l2, offload).await,
+        }
+    }
+}
+
+impl<R, W> CompositionPolicy<R, W>
+where
+    R: CompositionReadPolicy + Into<TierReadPolicy>,
+    W: CompositionWritePolicy + Into<TierWritePolicy>,
+{
+    /// Converts the policies into a [`TierPolicy`] for a tier of a
+    /// [`TieredBackend`](crate::composition::TieredBackend).
+    pub fn into_tier_policy(self) -> TierPolicy {
+        CompositionPolicy {
+            read: self.read.into(),
+            write: self.write.into(),
+            refill: self.refill,
+            ttl: self.ttl,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! TTL policies for limiting how long a faster layer keeps an entry.
+
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use hitbox_core::CacheValue;
+
+/// Policy for limiting the TTL of entries stored in L1.
+///
+/// Applied whenever L1 is written: on normal writes and on refills after an
+/// L2 hit. A short L1 TTL bounds how long an invalidation in L2 takes to
+/// reach every L1, while L2 keeps the entry for its full TTL.
+///
+/// Scaling is applied first, then the cap. A stale time past the resulting
+/// expiry is moved to the expiry.
+///
+/// # Example
+/// ```ignore
+/// use std::time::Duration;
+/// use hitbox_backend::composition::policy::TtlPolicy;
+///
+/// // At most 5s, and never more than a tenth of the L2 TTL.
+/// let ttl = TtlPolicy::new()
+///     .ratio(0.1)
+///     .max(Duration::from_secs(5));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TtlPolicy {
+    max: Option<Duration>,
+    ratio: Option<f64>,
+}
+
+impl TtlPolicy {
+    /// Create a policy keeping the TTL of the written value (default).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap the TTL at `max`. Entries without expiry get `max` as their TTL.
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Scale the remaining TTL by `ratio`. Entries without expiry are not
+    /// affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `ratio` is greater than zero and at most one.
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        assert!(
+            ratio > 0.0 && ratio <= 1.0,
+            "TTL ratio must be in (0, 1], got {ratio}"
+        );
+        self.ratio = Some(ratio);
+        self
+    }
+
+    /// Returns the TTL cap, if any.
+    pub fn max_ttl(&self) -> Option<Duration> {
+        self.max
+    }
+
+    /// Returns the TTL ratio, if any.
+    pub fn ttl_ratio(&self) -> Option<f64> {
+        self.ratio
+    }
+
+    /// Returns `true` if values are written with their own TTL.
+    pub fn is_inherit(&self) -> bool {
+        self.max.is_none() && self.ratio.is_none()
+    }
+
+    /// Applies the policy to the expiry and stale times of `value`.
+    pub fn apply<T>(&self, value: CacheValue<T>) -> CacheValue<T> {
+        if self.is_inherit() {
+            return value;
+        }
+        let (meta, data) = value.into_parts();
+        let (expire, stale) = self.limit(meta.expire, meta.stale, Utc::now());
+        CacheValue::new(data, expire, stale)
+    }
+
+    fn limit(
+        &self,
+        mut expire: Option<DateTime<Utc>>,
+        stale: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        if let (Some(ratio), Some(at)) = (self.ratio, expire) {
+            let remaining = (at - now).max(TimeDelta::zero());
+            let scaled = remaining.num_milliseconds() as f64 * ratio;
+            expire = Some(now + TimeDelta::milliseconds(scaled as i64));
+        }
+        if let Some(max) = self.max {
+            let cap = now + TimeDelta::from_std(max).unwrap_or(TimeDelta::MAX);
+            expire = Some(expire.map_or(cap, |at| at.min(cap)));
+        }
+        let stale = match (stale, expire) {
+            (Some(stale), Some(expire)) => Some(stale.min(expire)),
+            (stale, _) => stale,
+        };
+        (expire, stale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(n: i64) -> TimeDelta {
+        TimeDelta::seconds(n)
+    }
+
+    #[test]
+    fn test_inherit_keeps_times() {
+        let now = Utc::now();
+        let times = (Some(now + secs(60)), Some(now + secs(30)));
+        assert_eq!(TtlPolicy::new().limit(times.0, times.1, now), times);
+    }
+
+    #[test]
+    fn test_max_caps_expire_and_stale() {
+        let now = Utc::now();
+        let policy = TtlPolicy::new().max(Duration::from_secs(5));
+
+        let (expire, stale) = policy.limit(Some(now + secs(60)), Some(now + secs(30)), now);
+        assert_eq!(expire, Some(now + secs(5)));
+        assert_eq!(stale, Some(now + secs(5)));
+
+        // Shorter TTLs are kept.
+        let (expire, _) = policy.limit(Some(now + secs(2)), None, now);
+        assert_eq!(expire, Some(now + secs(2)));
+
+        // Entries without expiry get the cap.
+        let (expire, _) = policy.limit(None, None, now);
+        assert_eq!(expire, Some(now + secs(5)));
+    }
+
+    #[test]
+    fn test_ratio_scales_then_max_caps() {
+        let now = Utc::now();
+
+        let (expire, stale) =
+            TtlPolicy::new()
+                .ratio(0.1)
+                .limit(Some(now + secs(600)), Some(now + secs(30)), now);
+        assert_eq!(expire, Some(now + secs(60)));
+        assert_eq!(stale, Some(now + secs(30)));
+
+        let policy = TtlPolicy::new().ratio(0.1).max(Duration::from_secs(5));
+        let (expire, _) = policy.limit(Some(now + secs(600)), None, now);
+        assert_eq!(expire, Some(now + secs(5)));
+
+        // Ratio alone leaves entries without expiry alone.
+        let (expire, _) = TtlPolicy::new().ratio(0.5).limit(None, None, now);
+        assert_eq!(expire, None);
+    }
+}
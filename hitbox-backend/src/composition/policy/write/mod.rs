@@ -13,10 +13,12 @@ use crate::BackendError;
 pub mod optimistic_parallel;
 pub mod race;
 pub mod sequential;
+pub mod write_behind;
 
 pub use optimistic_parallel::OptimisticParallelWritePolicy;
 pub use race::{RaceLoserPolicy, RaceWritePolicy};
 pub use sequential::SequentialWritePolicy;
+pub use write_behind::WriteBehindWritePolicy;
 
 /// Policy trait for controlling write operations across cache layers.
 ///
@@ -0,0 +1,285 @@
+//! Write-behind policy implementation.
+//!
+//! This policy writes L1 on the request path and queues L2 writes, which a
+//! background task flushes in batches.
+
+use async_trait::async_trait;
+use futures::future::{BoxFuture, join_all};
+use hitbox_core::{CacheKey, Offload};
+use smol_str::SmolStr;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::CompositionWritePolicy;
+use crate::BackendError;
+
+/// Default number of distinct keys waiting for L2.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Default number of L2 writes flushed together.
+const DEFAULT_BATCH_SIZE: usize = 100;
+
+/// Default delay between the first queued write and the flush.
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Write-behind policy: Write L1 now, queue L2 for a background flush.
+///
+/// This strategy provides:
+/// - L1 write latency only (L2 is never on the request path)
+/// - Fewer L2 writes for hot keys (repeated writes are coalesced)
+/// - Eventual consistency (L2 lags behind L1 by up to the flush interval)
+///
+/// # Behavior
+/// 1. Write L1 and wait for the result. If it fails, return the error
+///    without queueing L2.
+/// 2. Queue the L2 write. If the key is already queued, the queued write is
+///    replaced by the new one and never executed.
+/// 3. The first queued write starts a flush task on the Tokio runtime. After
+///    `flush_interval` it drains the queue in batches of `batch_size`, running
+///    the writes of a batch concurrently, and stops once the queue is empty.
+///
+/// The flush task is spawned with `tokio::spawn` rather than the offload:
+/// an offload may drop or cancel tasks, which would leave queued writes with
+/// no task to flush them.
+///
+/// A batch issues one L2 write per key. The policy receives each L2 write as
+/// an opaque future rather than a key and value, so it cannot merge a batch
+/// into a single backend call.
+///
+/// When the queue holds `capacity` keys, L2 writes for new keys are dropped
+/// and counted.
+///
+/// # Consistency Guarantee
+/// If this operation returns `Ok(())`, L1 has been updated. L2 is updated
+/// later, unless the write is dropped, fails, or the process exits first.
+/// If it returns an error, L1 failed and L2 is left untouched.
+/// Call [`flush`](Self::flush) on shutdown to write out queued entries.
+///
+/// # Metrics
+/// - `hitbox_backend_write_behind_queue_depth` - keys waiting for L2
+/// - `hitbox_backend_write_behind_dropped_total` - L2 writes dropped on a full queue
+///
+/// Both are labeled with [`label`](Self::label).
+///
+/// # Use Cases
+/// - Write-heavy workloads with a slow or remote L2
+/// - Hot keys rewritten faster than L2 needs to see them
+///
+/// Clones share their queue. Settings apply to the policy they are set on,
+/// so configure the policy before cloning it.
+#[derive(Clone)]
+pub struct WriteBehindWritePolicy {
+    label: SmolStr,
+    capacity: usize,
+    batch_size: usize,
+    flush_interval: Duration,
+    queue: Arc<Queue>,
+}
+
+#[derive(Default)]
+struct Queue {
+    state: Mutex<QueueState>,
+    dropped: AtomicU64,
+}
+
+#[derive(Default)]
+struct QueueState {
+    /// Keys in the order they were first queued.
+    order: VecDeque<CacheKey>,
+    /// Latest pending L2 write of each queued key.
+    writes: HashMap<CacheKey, PendingWrite>,
+    /// Whether a flush task is scheduled.
+    flushing: bool,
+}
+
+type PendingWrite = BoxFuture<'static, Result<(), BackendError>>;
+
+impl WriteBehindWritePolicy {
+    /// Create a new write-behind policy with default settings.
+    pub fn new() -> Self {
+        Self {
+            label: SmolStr::new_static("write_behind"),
+            capacity: DEFAULT_CAPACITY,
+            batch_size: DEFAULT_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            queue: Arc::default(),
+        }
+    }
+
+    /// Set the label used in metrics (default: `write_behind`).
+    pub fn label(mut self, label: impl Into<SmolStr>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the maximum number of distinct keys waiting for L2.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the maximum number of L2 writes flushed together.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the delay between the first queued write and the flush.
+    pub fn flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Returns the number of keys waiting for L2.
+    pub fn queue_depth(&self) -> usize {
+        self.lock().writes.len()
+    }
+
+    /// Returns the number of L2 writes dropped because the queue was full.
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Writes out every queued L2 write now.
+    pub async fn flush(&self) {
+        loop {
+            let batch = self.take_batch();
+            if batch.is_empty() {
+                return;
+            }
+            for result in join_all(batch).await {
+                if let Err(error) = result {
+                    tracing::warn!(label = %self.label, ?error, "Write-behind L2 write failed");
+                }
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.queue
+            .state
+            .lock()
+            .expect("write-behind queue poisoned")
+    }
+
+    /// Queues `write` for `key`, returning whether a flush task must be started.
+    fn push(&self, key: CacheKey, write: PendingWrite) -> bool {
+        let mut state = self.lock();
+        if let Some(pending) = state.writes.get_mut(&key) {
+            *pending = write;
+            tracing::trace!("Coalesced queued L2 write");
+        } else if state.writes.len() >= self.capacity {
+            drop(state);
+            self.queue.dropped.fetch_add(1, Ordering::Relaxed);
+            crate::metrics::record_write_behind_dropped(&self.label);
+            tracing::warn!(label = %self.label, "Write-behind queue full, dropping L2 write");
+            return false;
+        } else {
+            state.order.push_back(key.clone());
+            state.writes.insert(key, write);
+            crate::metrics::record_write_behind_depth(&self.label, state.writes.len());
+        }
+
+        !std::mem::replace(&mut state.flushing, true)
+    }
+
+    /// Takes up to `batch_size` queued writes, oldest first.
+    fn take_batch(&self) -> Vec<PendingWrite> {
+        let mut state = self.lock();
+        let count = state.order.len().min(self.batch_size);
+        let keys: Vec<_> = state.order.drain(..count).collect();
+        let batch = keys
+            .iter()
+            .filter_map(|key| state.writes.remove(key))
+            .collect();
+        crate::metrics::record_write_behind_depth(&self.label, state.writes.len());
+        batch
+    }
+
+    /// Background task: waits for the flush interval, then drains the queue.
+    async fn run(self) {
+        let mut guard = FlushingGuard {
+            queue: &self.queue,
+            armed: true,
+        };
+        loop {
+            tokio::time::sleep(self.flush_interval).await;
+            self.flush().await;
+
+            // Stop only if nothing was queued while flushing; the next push
+            // starts a new task.
+            let mut state = self.lock();
+            if state.writes.is_empty() {
+                state.flushing = false;
+                guard.armed = false;
+                return;
+            }
+        }
+    }
+}
+
+/// Clears `flushing` if the flush task ends early, e.g. on a panicking L2
+/// write, so that the next push starts a new task.
+struct FlushingGuard<'a> {
+    queue: &'a Queue,
+    armed: bool,
+}
+
+impl Drop for FlushingGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed
+            && let Ok(mut state) = self.queue.state.lock()
+        {
+            state.flushing = false;
+        }
+    }
+}
+
+impl Default for WriteBehindWritePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for WriteBehindWritePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteBehindWritePolicy")
+            .field("label", &self.label)
+            .field("capacity", &self.capacity)
+            .field("batch_size", &self.batch_size)
+            .field("flush_interval", &self.flush_interval)
+            .field("queue_depth", &self.queue_depth())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CompositionWritePolicy for WriteBehindWritePolicy {
+    #[tracing::instrument(skip(self, key, write_l1, write_l2, _offload), level = "trace")]
+    async fn execute_with<F1, F2, Fut1, Fut2, O>(
+        &self,
+        key: CacheKey,
+        write_l1: F1,
+        write_l2: F2,
+        _offload: &O,
+    ) -> Result<(), BackendError>
+    where
+        F1: FnOnce(CacheKey) -> Fut1 + Send,
+        F2: FnOnce(CacheKey) -> Fut2 + Send,
+        Fut1: Future<Output = Result<(), BackendError>> + Send + 'static,
+        Fut2: Future<Output = Result<(), BackendError>> + Send + 'static,
+        O: Offload<'static>,
+    {
+        write_l1(key.clone()).await?;
+
+        let write = Box::pin(write_l2(key.clone()));
+        if self.push(key, write) {
+            tokio::spawn(self.clone().run());
+        }
+
+        Ok(())
+    }
+}
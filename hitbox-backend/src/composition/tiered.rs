@@ -0,0 +1,602 @@
+//! Flat multi-tier caching over any number of backends.
+//!
+//! [`TieredBackend`] keeps an ordered list of tiers, fastest first. It does
+//! the same job as nesting [`CompositionBackend`]s, e.g. memory → disk →
+//! Redis, without the per-level cost: no composition envelopes, no nested
+//! contexts, and values are serialized once per distinct format no matter how
+//! many tiers share it.
+//!
+//! # Policies
+//!
+//! Each tier has a [`TierPolicy`], made of the same policies a
+//! [`CompositionBackend`] uses. A tier's read and write policies decide how it
+//! is accessed relative to all slower tiers taken together, just like L1
+//! relative to L2. Those of the last tier are never used.
+//!
+//! A tier's [`RefillPolicy`] decides whether it is populated when a slower
+//! tier serves a hit. On a hit in tier `k`, every faster tier whose policy
+//! admits the entry is refilled before the value is returned. A
+//! [`RefillPolicy::Frequency`] filter counts the hits of its own tier only.
+//!
+//! A tier's [`TtlPolicy`](super::policy::TtlPolicy) limits how long the tier
+//! keeps entries, on writes and refills alike. Unlike the other policies, it
+//! applies to the last tier too.
+//!
+//! # Formats
+//!
+//! Through [`CacheBackend`], every tier stores values in its own format and
+//! compression. Behind a trait object, or as a layer of another composition,
+//! values are stored as encoded by the first tier, so all tiers should share
+//! its format and compressor.
+//!
+//! # Response source
+//!
+//! Hits report the serving tier as `{label}.{tier label}`, e.g.
+//! `tiered.redis`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use hitbox_backend::composition::{CompositionPolicy, TieredBackend};
+//! use hitbox_backend::composition::policy::RefillPolicy;
+//!
+//! let refill = CompositionPolicy::new().refill(RefillPolicy::Always);
+//!
+//! let cache = TieredBackend::new(offload)
+//!     .tier_with_policy(moka, refill.clone())
+//!     .tier_with_policy(feoxdb, refill)
+//!     .tier(redis);
+//! ```
+//!
+//! [`CompositionBackend`]: crate::CompositionBackend
+//! [`RefillPolicy`]: super::policy::RefillPolicy
+//! [`RefillPolicy::Frequency`]: super::policy::RefillPolicy::Frequency
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::future::{BoxFuture, join_all};
+use hitbox_core::{
+    BackendLabel, BoxContext, CacheContext, CacheKey, CacheStatus, CacheValue, Cacheable,
+    CacheableResponse, Context, Offload, Raw, ResponseSource,
+};
+
+use super::CompositionError;
+use super::policy::{
+    CompositionPolicy, CompositionReadPolicy, CompositionWritePolicy, ReadResult, TierPolicy,
+    TierReadPolicy, TierWritePolicy,
+};
+use crate::format::{BincodeFormat, Format, FormatExt, FormatTypeId};
+use crate::metrics::Timer;
+use crate::{
+    Backend, BackendError, BackendResult, CacheBackend, CacheKeyFormat, Compressor, DeleteStatus,
+    PassthroughCompressor, SyncBackend,
+};
+
+/// One tier of a [`TieredBackend`].
+#[derive(Clone)]
+struct Tier {
+    backend: Arc<SyncBackend>,
+    policy: TierPolicy,
+    /// The tier's own label.
+    name: BackendLabel,
+    /// Pre-computed "{label}.{name}", used for metrics and response source.
+    label: BackendLabel,
+}
+
+impl std::fmt::Debug for Tier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tier")
+            .field("name", &self.name)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+/// A value read from the tier at `tier`.
+struct TierHit {
+    tier: usize,
+    data: Raw,
+}
+
+type TierRead = (BackendResult<Option<CacheValue<TierHit>>>, BoxContext);
+
+/// A backend layering any number of tiers, fastest first.
+///
+/// See the [module documentation](self) for how tiers are read, written and
+/// refilled.
+#[derive(Clone, Debug)]
+pub struct TieredBackend<O> {
+    tiers: Arc<[Tier]>,
+    offload: O,
+    label: BackendLabel,
+}
+
+impl<O> TieredBackend<O>
+where
+    O: Offload<'static> + 'static,
+{
+    /// Creates a backend without tiers. Add them with [`tier`](Self::tier),
+    /// fastest first.
+    ///
+    /// The offload runs background work of the tier policies, e.g. losers of
+    /// a race.
+    pub fn new(offload: O) -> Self {
+        Self {
+            tiers: Arc::from(Vec::new()),
+            offload,
+            label: BackendLabel::new_static("tiered"),
+        }
+    }
+
+    /// Adds a tier slower than all current ones, with default policies.
+    pub fn tier<B>(self, backend: B) -> Self
+    where
+        B: Backend + 'static,
+    {
+        self.tier_with_policy(backend, CompositionPolicy::new())
+    }
+
+    /// Adds a tier slower than all current ones.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use hitbox_backend::composition::{CompositionPolicy, TieredBackend};
+    /// use hitbox_backend::composition::policy::{RaceReadPolicy, RefillPolicy};
+    ///
+    /// let backend = TieredBackend::new(offload)
+    ///     .tier_with_policy(
+    ///         moka,
+    ///         CompositionPolicy::new()
+    ///             .read(RaceReadPolicy::new())
+    ///             .refill(RefillPolicy::Always),
+    ///     )
+    ///     .tier(redis);
+    /// ```
+    pub fn tier_with_policy<B, R, W>(self, backend: B, policy: CompositionPolicy<R, W>) -> Self
+    where
+        B: Backend + 'static,
+        R: CompositionReadPolicy + Into<TierReadPolicy>,
+        W: CompositionWritePolicy + Into<TierWritePolicy>,
+    {
+        let name = backend.label();
+        let mut tiers = self.tiers.to_vec();
+        tiers.push(Tier {
+            backend: Arc::new(backend),
+            policy: policy.into_tier_policy(),
+            label: self.label.compose(&name),
+            name,
+        });
+        Self {
+            tiers: tiers.into(),
+            ..self
+        }
+    }
+
+    /// Sets a custom label for this backend.
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        let tiers: Vec<Tier> = self
+            .tiers
+            .iter()
+            .cloned()
+            .map(|mut tier| {
+                tier.label = self.label.compose(&tier.name);
+                tier
+            })
+            .collect();
+        self.tiers = tiers.into();
+        self
+    }
+
+    /// Returns the number of tiers.
+    pub fn tier_count(&self) -> usize {
+        self.tiers.len()
+    }
+
+    /// Returns the policies of the tier at `index`.
+    pub fn tier_policy(&self, index: usize) -> Option<&TierPolicy> {
+        self.tiers.get(index).map(|tier| &tier.policy)
+    }
+
+    /// Returns a reference to the offload manager.
+    pub fn offload(&self) -> &O {
+        &self.offload
+    }
+
+    /// Reads through all tiers according to their read policies.
+    async fn read_tiers(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<TierHit>>> {
+        if self.tiers.is_empty() {
+            return Ok(None);
+        }
+        read_level(self.tiers.clone(), self.offload.clone(), 0, key.clone())
+            .await
+            .0
+    }
+
+    /// Writes one value per tier according to their write policies.
+    async fn write_tiers(&self, key: &CacheKey, values: Vec<CacheValue<Raw>>) -> BackendResult<()> {
+        if self.tiers.is_empty() {
+            return Ok(());
+        }
+        write_level(
+            self.tiers.clone(),
+            self.offload.clone(),
+            0,
+            key.clone(),
+            values.into_iter(),
+        )
+        .await
+    }
+
+    /// Populates the tiers faster than `source` that have refill enabled.
+    async fn refill(
+        &self,
+        key: &CacheKey,
+        source: usize,
+        mut encode: impl FnMut(&Tier) -> BackendResult<Raw>,
+        value: &CacheValue<impl Sized>,
+    ) {
+        let writes = self.tiers[..source]
+            .iter()
+            .filter(|tier| tier.policy.refill_policy().admit(key))
+            .filter_map(|tier| match encode(tier) {
+                Ok(data) if !tier.policy.refill_policy().admit_size(data.len()) => None,
+                Ok(data) => Some(async move {
+                    let value = tier.policy.ttl_policy().apply(CacheValue::new(
+                        data,
+                        value.expire(),
+                        value.stale(),
+                    ));
+                    let result = write_tier(tier, key, value).await;
+                    (tier, result)
+                }),
+                Err(error) => {
+                    tracing::warn!(tier = %tier.label, ?error, "Tier refill encoding failed");
+                    None
+                }
+            });
+
+        for (tier, result) in join_all(writes).await {
+            if let Err(error) = result {
+                tracing::warn!(tier = %tier.label, ?error, "Tier refill failed");
+            }
+        }
+    }
+}
+
+/// Reads tier `index`, then the slower tiers as its read policy dictates.
+async fn read_level<O>(tiers: Arc<[Tier]>, offload: O, index: usize, key: CacheKey) -> TierRead
+where
+    O: Offload<'static> + 'static,
+{
+    if index + 1 == tiers.len() {
+        return read_tier(tiers, index, key).await;
+    }
+
+    let this = tiers.clone();
+    let slower = tiers.clone();
+    let slower_offload = offload.clone();
+    let result = tiers[index]
+        .policy
+        .read_policy()
+        .execute_with(
+            key,
+            move |k| read_tier(this, index, k),
+            move |k| read_from(slower, slower_offload, index + 1, k),
+            &offload,
+        )
+        .await;
+
+    match result {
+        Ok(ReadResult { value, context, .. }) => (Ok(value), context),
+        Err(error) => (Err(error), CacheContext::default().boxed()),
+    }
+}
+
+fn read_from<O>(
+    tiers: Arc<[Tier]>,
+    offload: O,
+    index: usize,
+    key: CacheKey,
+) -> BoxFuture<'static, TierRead>
+where
+    O: Offload<'static> + 'static,
+{
+    Box::pin(read_level(tiers, offload, index, key))
+}
+
+async fn read_tier(tiers: Arc<[Tier]>, index: usize, key: CacheKey) -> TierRead {
+    let tier = &tiers[index];
+    let timer = Timer::new();
+    let result = tier.backend.read(&key).await;
+    crate::metrics::record_read(tier.label.as_str(), timer.elapsed());
+
+    let result = match result {
+        Ok(Some(value)) => {
+            crate::metrics::record_read_bytes(tier.label.as_str(), value.data().len());
+            let (meta, data) = value.into_parts();
+            Ok(Some(CacheValue::new(
+                TierHit { tier: index, data },
+                meta.expire,
+                meta.stale,
+            )))
+        }
+        Ok(None) => Ok(None),
+        Err(error) => {
+            crate::metrics::record_read_error(tier.label.as_str());
+            Err(error)
+        }
+    };
+    (result, CacheContext::default().boxed())
+}
+
+/// Writes tier `index`, then the slower tiers as its write policy dictates.
+async fn write_level<O>(
+    tiers: Arc<[Tier]>,
+    offload: O,
+    index: usize,
+    key: CacheKey,
+    mut values: std::vec::IntoIter<CacheValue<Raw>>,
+) -> BackendResult<()>
+where
+    O: Offload<'static> + 'static,
+{
+    let value = values.next().expect("one value is encoded for every tier");
+    if index + 1 == tiers.len() {
+        return write_tier(&tiers[index], &key, value).await;
+    }
+
+    let this = tiers.clone();
+    let slower = tiers.clone();
+    let slower_offload = offload.clone();
+    tiers[index]
+        .policy
+        .write_policy()
+        .execute_with(
+            key,
+            move |k| async move { write_tier(&this[index], &k, value).await },
+            move |k| write_from(slower, slower_offload, index + 1, k, values),
+            &offload,
+        )
+        .await
+}
+
+fn write_from<O>(
+    tiers: Arc<[Tier]>,
+    offload: O,
+    index: usize,
+    key: CacheKey,
+    values: std::vec::IntoIter<CacheValue<Raw>>,
+) -> BoxFuture<'static, BackendResult<()>>
+where
+    O: Offload<'static> + 'static,
+{
+    Box::pin(write_level(tiers, offload, index, key, values))
+}
+
+async fn write_tier(tier: &Tier, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+    let len = value.data().len();
+    let timer = Timer::new();
+    let result = tier.backend.write(key, value).await;
+    crate::metrics::record_write(tier.label.as_str(), timer.elapsed());
+    match &result {
+        Ok(()) => crate::metrics::record_write_bytes(tier.label.as_str(), len),
+        Err(_) => crate::metrics::record_write_error(tier.label.as_str()),
+    }
+    result
+}
+
+/// Serializes and compresses a value for `tier`.
+///
+/// Serialized bytes are kept in `serialized` and reused by every tier sharing
+/// the format, so only compression runs once per tier.
+fn encode<V: Cacheable>(
+    tier: &Tier,
+    value: &V,
+    ctx: &dyn Context,
+    serialized: &mut Vec<(FormatTypeId, Raw)>,
+) -> BackendResult<Raw> {
+    let format = tier.backend.value_format();
+    let format_id = format.format_type_id();
+    let bytes = match serialized.iter().find(|(id, _)| *id == format_id) {
+        Some((_, bytes)) => bytes.clone(),
+        None => {
+            let timer = Timer::new();
+            let bytes = format.serialize(value, ctx)?;
+            crate::metrics::record_serialize(tier.label.as_str(), timer.elapsed());
+            serialized.push((format_id, bytes.clone()));
+            bytes
+        }
+    };
+
+    let timer = Timer::new();
+    let compressed = tier.backend.compressor().compress(&bytes)?;
+    crate::metrics::record_compress(tier.label.as_str(), timer.elapsed());
+    Ok(Bytes::from(compressed))
+}
+
+#[async_trait]
+impl<O> Backend for TieredBackend<O>
+where
+    O: Offload<'static> + 'static,
+{
+    #[tracing::instrument(skip(self), level = "trace")]
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let Some(hit) = self.read_tiers(key).await? else {
+            return Ok(None);
+        };
+        let (meta, TierHit { tier, data }) = hit.into_parts();
+        let value = CacheValue::new(data, meta.expire, meta.stale);
+
+        // Values are stored as encoded by the first tier, so they are copied as-is.
+        self.refill(key, tier, |_| Ok(value.data().clone()), &value)
+            .await;
+        Ok(Some(value))
+    }
+
+    #[tracing::instrument(skip(self, value), level = "trace")]
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let values = self
+            .tiers
+            .iter()
+            .map(|tier| tier.policy.ttl_policy().apply(value.clone()))
+            .collect();
+        self.write_tiers(key, values).await
+    }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        let results = join_all(self.tiers.iter().map(|tier| tier.backend.remove(key))).await;
+
+        let mut deleted = 0;
+        let mut errors = Vec::new();
+        for (tier, result) in self.tiers.iter().zip(results) {
+            match result {
+                Ok(DeleteStatus::Deleted(n)) => deleted += n,
+                Ok(DeleteStatus::Missing) => {}
+                Err(error) => {
+                    tracing::warn!(tier = %tier.label, ?error, "Tier delete failed");
+                    errors.push(error);
+                }
+            }
+        }
+
+        if !errors.is_empty() && errors.len() == self.tiers.len() {
+            return Err(BackendError::InternalError(Box::new(
+                CompositionError::AllTiersFailed { errors },
+            )));
+        }
+        Ok(if deleted > 0 {
+            DeleteStatus::Deleted(deleted)
+        } else {
+            DeleteStatus::Missing
+        })
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        match self.tiers.first() {
+            Some(tier) => tier.backend.value_format(),
+            None => &BincodeFormat,
+        }
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        match self.tiers.first() {
+            Some(tier) => tier.backend.key_format(),
+            None => &CacheKeyFormat::Bitcode,
+        }
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        match self.tiers.first() {
+            Some(tier) => tier.backend.compressor(),
+            None => &PassthroughCompressor,
+        }
+    }
+}
+
+impl<O> CacheBackend for TieredBackend<O>
+where
+    O: Offload<'static> + 'static,
+{
+    #[tracing::instrument(skip(self, ctx), level = "trace")]
+    async fn get<T>(
+        &self,
+        key: &CacheKey,
+        ctx: &mut BoxContext,
+    ) -> BackendResult<Option<CacheValue<T::Cached>>>
+    where
+        T: CacheableResponse,
+        T::Cached: Cacheable,
+    {
+        let Some(hit) = self.read_tiers(key).await? else {
+            return Ok(None);
+        };
+        let (meta, TierHit { tier: index, data }) = hit.into_parts();
+        let tier = &self.tiers[index];
+        let format = tier.backend.value_format();
+
+        let timer = Timer::new();
+        let decompressed = Bytes::from(tier.backend.compressor().decompress(&data)?);
+        crate::metrics::record_decompress(tier.label.as_str(), timer.elapsed());
+
+        // Deserialize with a scratch context: formats may upgrade it, which
+        // must not leak into the caller's context.
+        let mut tier_ctx = ctx.clone_box();
+        let timer = Timer::new();
+        let mut deserialized: Option<T::Cached> = None;
+        format.with_deserializer(
+            &decompressed,
+            &mut |deserializer| {
+                deserialized = Some(deserializer.deserialize()?);
+                Ok(())
+            },
+            &mut tier_ctx,
+        )?;
+        crate::metrics::record_deserialize(tier.label.as_str(), timer.elapsed());
+
+        let value = CacheValue::new(
+            deserialized.ok_or_else(|| {
+                BackendError::InternalError(Box::new(std::io::Error::other(
+                    "deserialization produced no result",
+                )))
+            })?,
+            meta.expire,
+            meta.stale,
+        );
+
+        let mut serialized = vec![(format.format_type_id(), decompressed)];
+        self.refill(
+            key,
+            index,
+            |target| encode(target, value.data(), &**ctx, &mut serialized),
+            &value,
+        )
+        .await;
+
+        ctx.set_status(CacheStatus::Hit);
+        ctx.set_source(ResponseSource::Backend(tier.label.clone()));
+        Ok(Some(value))
+    }
+
+    #[tracing::instrument(skip(self, value, ctx), level = "trace")]
+    async fn set<T>(
+        &self,
+        key: &CacheKey,
+        value: &CacheValue<T::Cached>,
+        ctx: &mut BoxContext,
+    ) -> BackendResult<()>
+    where
+        T: CacheableResponse,
+        T::Cached: Cacheable,
+    {
+        // Refills are handled in `get`, and as a layer of another composition
+        // this backend is the source of a refill and already has the value.
+        if ctx.read_mode() == hitbox_core::ReadMode::Refill {
+            return Ok(());
+        }
+
+        let mut serialized = Vec::with_capacity(1);
+        let values = self
+            .tiers
+            .iter()
+            .map(|tier| {
+                encode(tier, value.data(), &**ctx, &mut serialized).map(|data| {
+                    tier.policy.ttl_policy().apply(CacheValue::new(
+                        data,
+                        value.expire(),
+                        value.stale(),
+                    ))
+                })
+            })
+            .collect::<BackendResult<Vec<_>>>()?;
+
+        self.write_tiers(key, values).await
+    }
+}
@@ -9,6 +9,10 @@
 //! | [`PassthroughCompressor`] | None | Fastest | - |
 //! | `GzipCompressor` | Good | Medium | `gzip` |
 //! | `ZstdCompressor` | Best | Fast | `zstd` |
+//! | `ZstdDictCompressor` | Best on small values | Fast | `zstd` |
+//! | `Lz4Compressor` | Fair | Fastest | `lz4` |
+//! | `SnappyCompressor` | Fair | Fastest | `snappy` |
+//! | `BrotliCompressor` | Best | Slow | `brotli` |
 
 use thiserror::Error;
 
@@ -199,6 +203,373 @@ impl Compressor for ZstdCompressor {
     }
 }
 
+/// Zstd dictionary for [`ZstdDictCompressor`]
+///
+/// A dictionary carries content common to many small values, so each value
+/// compresses well on its own. The `id` is written in front of every payload
+/// compressed with the dictionary and must be unique among the dictionaries
+/// a cache ever used.
+#[cfg(feature = "zstd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ZstdDictionary {
+    id: u32,
+    data: Vec<u8>,
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdDictionary {
+    /// Create a dictionary from bytes, such as a file produced by `zstd --train`
+    pub fn new(id: u32, data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            id,
+            data: data.into(),
+        }
+    }
+
+    /// Train a dictionary of at most `max_size` bytes from sample values
+    ///
+    /// Samples should be serialized values as they are written to the cache.
+    /// Zstd needs a few hundred samples to train a useful dictionary; about
+    /// 100 times `max_size` bytes of samples is a good start.
+    pub fn train<S: AsRef<[u8]>>(
+        id: u32,
+        samples: &[S],
+        max_size: usize,
+    ) -> Result<Self, CompressionError> {
+        let data = zstd::dict::from_samples(samples, max_size).map_err(|e| {
+            CompressionError::CompressionFailed(format!("dictionary training failed: {e}"))
+        })?;
+        Ok(Self { id, data })
+    }
+
+    /// Identifier written in front of payloads compressed with this dictionary
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Raw dictionary bytes, to be stored and loaded with [`ZstdDictionary::new`]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl std::fmt::Debug for ZstdDictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZstdDictionary")
+            .field("id", &self.id)
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
+
+/// Zstd compression with a shared dictionary
+///
+/// Plain Zstd gains little on small values, since each is compressed
+/// without context. With a dictionary trained on similar values, such as
+/// JSON responses of the same API, small values compress several times
+/// better.
+///
+/// Each payload starts with the 4-byte little-endian ID of the dictionary it
+/// was compressed with. To rotate dictionaries, build a compressor with the
+/// new dictionary and keep the old ones with
+/// [`with_previous`](Self::with_previous) until entries compressed with them
+/// have expired.
+///
+/// # Examples
+///
+/// ```ignore
+/// use hitbox_backend::{ZstdDictCompressor, ZstdDictionary};
+///
+/// let current = ZstdDictionary::train(2, &samples, 16 * 1024)?;
+/// let previous = ZstdDictionary::new(1, std::fs::read("dict-1.zstd")?);
+/// let compressor = ZstdDictCompressor::new(current).with_previous(previous);
+/// ```
+#[cfg(feature = "zstd")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
+#[derive(Clone)]
+pub struct ZstdDictCompressor {
+    id: u32,
+    level: i32,
+    encoder: std::sync::Arc<zstd::dict::EncoderDictionary<'static>>,
+    decoders:
+        std::collections::HashMap<u32, std::sync::Arc<zstd::dict::DecoderDictionary<'static>>>,
+}
+
+#[cfg(feature = "zstd")]
+impl ZstdDictCompressor {
+    /// Length of the dictionary ID prefix
+    const ID_LEN: usize = 4;
+
+    /// Create a new ZstdDictCompressor with default compression level (3)
+    pub fn new(dictionary: ZstdDictionary) -> Self {
+        Self::with_level(dictionary, 3)
+    }
+
+    /// Create a new ZstdDictCompressor with specified compression level (-7 to 22)
+    pub fn with_level(dictionary: ZstdDictionary, level: i32) -> Self {
+        let level = level.clamp(-7, 22);
+        let mut decoders = std::collections::HashMap::new();
+        decoders.insert(
+            dictionary.id,
+            std::sync::Arc::new(zstd::dict::DecoderDictionary::copy(&dictionary.data)),
+        );
+        Self {
+            id: dictionary.id,
+            level,
+            encoder: std::sync::Arc::new(zstd::dict::EncoderDictionary::copy(
+                &dictionary.data,
+                level,
+            )),
+            decoders,
+        }
+    }
+
+    /// Keep an older dictionary to decompress entries written before a rotation
+    ///
+    /// New entries are always compressed with the dictionary passed to
+    /// [`new`](Self::new). A previous dictionary with the same ID as the
+    /// current one is ignored.
+    pub fn with_previous(mut self, dictionary: ZstdDictionary) -> Self {
+        if dictionary.id != self.id {
+            self.decoders.insert(
+                dictionary.id,
+                std::sync::Arc::new(zstd::dict::DecoderDictionary::copy(&dictionary.data)),
+            );
+        }
+        self
+    }
+
+    /// ID of the dictionary new entries are compressed with
+    pub fn dictionary_id(&self) -> u32 {
+        self.id
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl std::fmt::Debug for ZstdDictCompressor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut previous: Vec<_> = self.decoders.keys().filter(|id| **id != self.id).collect();
+        previous.sort();
+        f.debug_struct("ZstdDictCompressor")
+            .field("dictionary_id", &self.id)
+            .field("level", &self.level)
+            .field("previous", &previous)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Compressor for ZstdDictCompressor {
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        let mut compressor = zstd::bulk::Compressor::with_prepared_dictionary(&self.encoder)
+            .map_err(|e| CompressionError::CompressionFailed(e.to_string()))?;
+        let frame = compressor
+            .compress(data)
+            .map_err(|e| CompressionError::CompressionFailed(e.to_string()))?;
+
+        let mut compressed = Vec::with_capacity(Self::ID_LEN + frame.len());
+        compressed.extend_from_slice(&self.id.to_le_bytes());
+        compressed.extend_from_slice(&frame);
+        Ok(compressed)
+    }
+
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        use std::io::Read;
+
+        let (id, frame) = data
+            .split_first_chunk::<{ Self::ID_LEN }>()
+            .ok_or_else(|| {
+                CompressionError::DecompressionFailed("missing dictionary ID".to_string())
+            })?;
+        let id = u32::from_le_bytes(*id);
+        let dictionary = self.decoders.get(&id).ok_or_else(|| {
+            CompressionError::DecompressionFailed(format!("unknown dictionary ID {id}"))
+        })?;
+
+        let mut decoder = zstd::stream::read::Decoder::with_prepared_dictionary(frame, dictionary)
+            .map_err(|e| CompressionError::DecompressionFailed(e.to_string()))?;
+        let mut decompressed = Vec::new();
+        decoder
+            .read_to_end(&mut decompressed)
+            .map_err(|e| CompressionError::DecompressionFailed(e.to_string()))?;
+        Ok(decompressed)
+    }
+
+    fn clone_box(&self) -> Box<dyn Compressor> {
+        Box::new(self.clone())
+    }
+}
+
+/// LZ4 compression with configurable level
+///
+/// Compresses and decompresses several times faster than Zstd at a lower
+/// ratio, which suits in-memory tiers where CPU time matters more than size.
+#[cfg(feature = "lz4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+#[derive(Debug, Clone, Copy)]
+pub struct Lz4Compressor {
+    level: i32,
+}
+
+#[cfg(feature = "lz4")]
+impl Lz4Compressor {
+    /// Create a new Lz4Compressor with default compression level (0)
+    pub fn new() -> Self {
+        Self { level: 0 }
+    }
+
+    /// Create a new Lz4Compressor with specified compression level (-65537 to 12)
+    /// Negative values = fast mode with acceleration, trading ratio for speed
+    /// 1 to 12 = high compression mode, slower to compress only
+    pub fn with_level(level: i32) -> Self {
+        Self {
+            level: level.clamp(-65537, 12),
+        }
+    }
+
+    fn mode(&self) -> lz4::block::CompressionMode {
+        use lz4::block::CompressionMode;
+
+        match self.level {
+            0 => CompressionMode::DEFAULT,
+            level if level < 0 => CompressionMode::FAST(-level),
+            level => CompressionMode::HIGHCOMPRESSION(level),
+        }
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl Default for Lz4Compressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl Compressor for Lz4Compressor {
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        // The decompressed size is prepended so decompression allocates once
+        lz4::block::compress(data, Some(self.mode()), true)
+            .map_err(|e| CompressionError::CompressionFailed(e.to_string()))
+    }
+
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        // The prefix sizes the output buffer, so check it before trusting it:
+        // an LZ4 block expands at most 255 times
+        let (size, block) = data.split_first_chunk::<4>().ok_or_else(|| {
+            CompressionError::DecompressionFailed("missing lz4 size prefix".to_string())
+        })?;
+        let size = u32::from_le_bytes(*size);
+        if u64::from(size) > block.len() as u64 * 255 {
+            return Err(CompressionError::DecompressionFailed(format!(
+                "lz4 size prefix {size} is too large for a {} byte block",
+                block.len()
+            )));
+        }
+        lz4::block::decompress(data, None)
+            .map_err(|e| CompressionError::DecompressionFailed(e.to_string()))
+    }
+
+    fn clone_box(&self) -> Box<dyn Compressor> {
+        Box::new(*self)
+    }
+}
+
+/// Snappy compression
+///
+/// Comparable to [`Lz4Compressor`] in speed and ratio. Snappy has no
+/// compression levels.
+#[cfg(feature = "snappy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snappy")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SnappyCompressor;
+
+#[cfg(feature = "snappy")]
+impl Compressor for SnappyCompressor {
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        snap::raw::Encoder::new()
+            .compress_vec(data)
+            .map_err(|e| CompressionError::CompressionFailed(e.to_string()))
+    }
+
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        snap::raw::Decoder::new()
+            .decompress_vec(data)
+            .map_err(|e| CompressionError::DecompressionFailed(e.to_string()))
+    }
+
+    fn clone_box(&self) -> Box<dyn Compressor> {
+        Box::new(*self)
+    }
+}
+
+/// Brotli compression with configurable level
+///
+/// Reaches better ratios than Zstd on text such as JSON or HTML, but high
+/// levels are slow to compress. Suits large entries that are written once
+/// and read many times.
+#[cfg(feature = "brotli")]
+#[cfg_attr(docsrs, doc(cfg(feature = "brotli")))]
+#[derive(Debug, Clone, Copy)]
+pub struct BrotliCompressor {
+    level: u32,
+}
+
+#[cfg(feature = "brotli")]
+impl BrotliCompressor {
+    /// Window size as a power of two, brotli's default of 4 MiB
+    const LGWIN: i32 = 22;
+
+    /// Create a new BrotliCompressor with default compression level (9)
+    pub fn new() -> Self {
+        Self { level: 9 }
+    }
+
+    /// Create a new BrotliCompressor with specified compression level (0-11)
+    /// Levels 10 and 11 compress an order of magnitude slower than 9
+    pub fn with_level(level: u32) -> Self {
+        Self {
+            level: level.min(11),
+        }
+    }
+}
+
+#[cfg(feature = "brotli")]
+impl Default for BrotliCompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "brotli")]
+impl Compressor for BrotliCompressor {
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        let params = brotli::enc::BrotliEncoderParams {
+            quality: self.level as i32,
+            lgwin: Self::LGWIN,
+            size_hint: data.len(),
+            ..Default::default()
+        };
+        let mut compressed = Vec::new();
+        brotli::BrotliCompress(&mut &data[..], &mut compressed, &params)
+            .map_err(|e| CompressionError::CompressionFailed(e.to_string()))?;
+        Ok(compressed)
+    }
+
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        let mut decompressed = Vec::new();
+        brotli::BrotliDecompress(&mut &data[..], &mut decompressed)
+            .map_err(|e| CompressionError::DecompressionFailed(e.to_string()))?;
+        Ok(decompressed)
+    }
+
+    fn clone_box(&self) -> Box<dyn Compressor> {
+        Box::new(*self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,4 +662,227 @@ mod tests {
         assert_eq!(balanced.decompress(&balanced_compressed).unwrap(), data);
         assert_eq!(max.decompress(&max_compressed).unwrap(), data);
     }
+
+    #[cfg(feature = "zstd")]
+    fn json_sample(i: usize) -> Vec<u8> {
+        format!(
+            r#"{{"id":{i},"name":"User {i}","email":"user{i}@example.com","active":{},"roles":["reader","writer"],"created_at":"2026-01-{:02}T12:00:00Z"}}"#,
+            i.is_multiple_of(2),
+            i % 28 + 1
+        )
+        .into_bytes()
+    }
+
+    #[cfg(feature = "zstd")]
+    fn trained_dictionary(id: u32) -> ZstdDictionary {
+        let samples: Vec<_> = (0..1000).map(json_sample).collect();
+        ZstdDictionary::train(id, &samples, 4096).unwrap()
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_dict_compressor() {
+        let compressor = ZstdDictCompressor::new(trained_dictionary(7));
+        let data = json_sample(5000);
+
+        let compressed = compressor.compress(&data).unwrap();
+        assert_eq!(compressed[..4], 7u32.to_le_bytes());
+
+        let decompressed = compressor.decompress(&compressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_dict_beats_plain_zstd_on_small_values() {
+        let compressor = ZstdDictCompressor::new(trained_dictionary(1));
+        let data = json_sample(5000);
+
+        let with_dict = compressor.compress(&data).unwrap();
+        let plain = ZstdCompressor::new().compress(&data).unwrap();
+        assert!(
+            with_dict.len() * 2 < plain.len(),
+            "dictionary: {} bytes, plain: {} bytes",
+            with_dict.len(),
+            plain.len()
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_dict_rotation() {
+        let old = trained_dictionary(1);
+        // Any bytes make a valid raw content dictionary
+        let new = ZstdDictionary::new(2, json_sample(0));
+        let data = json_sample(5000);
+        let written_before = ZstdDictCompressor::new(old.clone())
+            .compress(&data)
+            .unwrap();
+
+        let rotated = ZstdDictCompressor::new(new).with_previous(old);
+        assert_eq!(rotated.dictionary_id(), 2);
+        assert_eq!(rotated.decompress(&written_before).unwrap(), data);
+        let written_after = rotated.compress(&data).unwrap();
+        assert_eq!(written_after[..4], 2u32.to_le_bytes());
+        assert_eq!(rotated.decompress(&written_after).unwrap(), data);
+
+        // Dropping the old dictionary makes its entries unreadable
+        let result = ZstdDictCompressor::new(trained_dictionary(3)).decompress(&written_before);
+        assert!(matches!(
+            result,
+            Err(CompressionError::DecompressionFailed(message)) if message.contains("unknown dictionary ID 1")
+        ));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_dict_rejects_missing_prefix() {
+        let compressor = ZstdDictCompressor::new(trained_dictionary(1));
+        assert!(matches!(
+            compressor.decompress(&[1, 0]),
+            Err(CompressionError::DecompressionFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_dict_training_needs_samples() {
+        let samples = [json_sample(1)];
+        assert!(matches!(
+            ZstdDictionary::train(1, &samples, 4096),
+            Err(CompressionError::CompressionFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_lz4_compressor() {
+        let compressor = Lz4Compressor::new();
+        let data = b"Hello, World! This is a test of lz4 compression.".repeat(10);
+
+        let compressed = compressor.compress(&data).unwrap();
+        assert!(
+            compressed.len() < data.len(),
+            "Compressed data should be smaller"
+        );
+
+        let decompressed = compressor.decompress(&compressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_lz4_compression_levels() {
+        let data = b"Hello, World! This is a test of lz4 compression.".repeat(100);
+
+        let fast = Lz4Compressor::with_level(-10);
+        let balanced = Lz4Compressor::with_level(0);
+        let max = Lz4Compressor::with_level(12);
+
+        let fast_compressed = fast.compress(&data).unwrap();
+        let balanced_compressed = balanced.compress(&data).unwrap();
+        let max_compressed = max.compress(&data).unwrap();
+
+        // Higher compression level should produce smaller output
+        assert!(max_compressed.len() <= balanced_compressed.len());
+        assert!(balanced_compressed.len() <= fast_compressed.len());
+
+        // All should decompress to original
+        assert_eq!(fast.decompress(&fast_compressed).unwrap(), data);
+        assert_eq!(balanced.decompress(&balanced_compressed).unwrap(), data);
+        assert_eq!(max.decompress(&max_compressed).unwrap(), data);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_lz4_rejects_truncated_data() {
+        let compressor = Lz4Compressor::new();
+        let compressed = compressor.compress(&b"truncated".repeat(10)).unwrap();
+
+        let result = compressor.decompress(&compressed[..compressed.len() / 2]);
+        assert!(matches!(
+            result,
+            Err(CompressionError::DecompressionFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_lz4_rejects_oversized_prefix() {
+        let compressor = Lz4Compressor::new();
+        let data = vec![0u8; 1024 * 1024];
+        let mut compressed = compressor.compress(&data).unwrap();
+        assert_eq!(compressor.decompress(&compressed).unwrap(), data);
+
+        compressed[..4].copy_from_slice(&i32::MAX.to_le_bytes());
+        assert!(matches!(
+            compressor.decompress(&compressed),
+            Err(CompressionError::DecompressionFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "snappy")]
+    #[test]
+    fn test_snappy_compressor() {
+        let compressor = SnappyCompressor;
+        let data = b"Hello, World! This is a test of snappy compression.".repeat(10);
+
+        let compressed = compressor.compress(&data).unwrap();
+        assert!(
+            compressed.len() < data.len(),
+            "Compressed data should be smaller"
+        );
+
+        let decompressed = compressor.decompress(&compressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[cfg(feature = "snappy")]
+    #[test]
+    fn test_snappy_rejects_garbage() {
+        let result = SnappyCompressor.decompress(b"\xff\xff\xff\xff\x0f");
+        assert!(matches!(
+            result,
+            Err(CompressionError::DecompressionFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_brotli_compressor() {
+        let compressor = BrotliCompressor::new();
+        let data = b"Hello, World! This is a test of brotli compression.".repeat(10);
+
+        let compressed = compressor.compress(&data).unwrap();
+        assert!(
+            compressed.len() < data.len(),
+            "Compressed data should be smaller"
+        );
+
+        let decompressed = compressor.decompress(&compressed).unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_brotli_compression_levels() {
+        let data = b"Hello, World! This is a test of brotli compression.".repeat(100);
+
+        let fast = BrotliCompressor::with_level(0);
+        let balanced = BrotliCompressor::with_level(9);
+        let max = BrotliCompressor::with_level(11);
+
+        let fast_compressed = fast.compress(&data).unwrap();
+        let balanced_compressed = balanced.compress(&data).unwrap();
+        let max_compressed = max.compress(&data).unwrap();
+
+        // Higher compression level should produce smaller output
+        assert!(max_compressed.len() <= balanced_compressed.len());
+        assert!(balanced_compressed.len() <= fast_compressed.len());
+
+        // All should decompress to original
+        assert_eq!(fast.decompress(&fast_compressed).unwrap(), data);
+        assert_eq!(balanced.decompress(&balanced_compressed).unwrap(), data);
+        assert_eq!(max.decompress(&max_compressed).unwrap(), data);
+    }
 }
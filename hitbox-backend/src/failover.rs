@@ -0,0 +1,316 @@
+//! Primary/secondary failover for high availability.
+//!
+//! [`FailoverBackend`] sends all traffic to a primary backend. When the
+//! primary returns a [`BackendError`], the failed operation is retried on the
+//! secondary and traffic stays there. Every
+//! [`probe_interval`](FailoverBackend::probe_interval) one operation is sent
+//! to the primary as a health probe; if it succeeds, traffic switches back.
+//!
+//! Unlike [`CompositionBackend`](crate::CompositionBackend), which layers
+//! caches of different speed, both backends here play the same role and only
+//! one of them serves traffic at a time. Removes are the exception: they go
+//! to both backends, so entries written during a failover do not outlive an
+//! invalidation.
+//!
+//! Values are encoded once with the primary's format and compressor and
+//! stored as-is in whichever backend is active, so the secondary's own value
+//! format and compressor are not used.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use hitbox_backend::{Compose, FailoverBackend};
+//!
+//! // Redis cluster A, falling back to cluster B.
+//! let redis = FailoverBackend::new(redis_a, redis_b)
+//!     .probe_interval(Duration::from_secs(5));
+//!
+//! // Failover pairs compose like any other backend.
+//! let cache = moka.compose(redis, offload);
+//! ```
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use hitbox_core::{BackendLabel, CacheKey, CacheValue, Raw};
+
+use crate::format::Format;
+use crate::{
+    Backend, BackendError, BackendResult, CacheBackend, CacheKeyFormat, Compressor, DeleteStatus,
+};
+
+/// Default interval between health probes of a failed primary.
+pub const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Backend currently serving traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveBackend {
+    /// The primary backend.
+    Primary,
+    /// The secondary backend, while the primary is failing.
+    Secondary,
+}
+
+impl ActiveBackend {
+    /// Returns the backend role for metrics labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+        }
+    }
+
+    fn as_gauge(&self) -> f64 {
+        match self {
+            Self::Primary => 0.0,
+            Self::Secondary => 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct FailoverState {
+    active: ActiveBackend,
+    last_probe: Option<Instant>,
+    probing: bool,
+}
+
+/// Where a single operation is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Route {
+    Primary,
+    Probe,
+    Secondary,
+}
+
+/// A backend that switches from a failing primary to a secondary and back.
+#[derive(Clone, Debug)]
+pub struct FailoverBackend<P, S> {
+    primary: P,
+    secondary: S,
+    label: BackendLabel,
+    probe_interval: Duration,
+    state: Arc<Mutex<FailoverState>>,
+}
+
+impl<P: Backend, S: Backend> FailoverBackend<P, S> {
+    /// Creates a failover pair serving traffic from `primary`.
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            primary,
+            secondary,
+            label: BackendLabel::new_static("failover"),
+            probe_interval: DEFAULT_PROBE_INTERVAL,
+            state: Arc::new(Mutex::new(FailoverState {
+                active: ActiveBackend::Primary,
+                last_probe: None,
+                probing: false,
+            })),
+        }
+    }
+
+    /// Sets a custom label for this backend.
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets how often a failed primary is probed.
+    pub fn probe_interval(mut self, interval: Duration) -> Self {
+        self.probe_interval = interval;
+        self
+    }
+
+    /// Returns a reference to the primary backend.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns a reference to the secondary backend.
+    pub fn secondary(&self) -> &S {
+        &self.secondary
+    }
+
+    /// Returns the backend currently serving traffic.
+    pub fn active(&self) -> ActiveBackend {
+        self.lock().active
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FailoverState> {
+        self.state.lock().expect("failover state poisoned")
+    }
+
+    fn route(&self) -> Route {
+        let mut state = self.lock();
+        match state.active {
+            ActiveBackend::Primary => Route::Primary,
+            ActiveBackend::Secondary if state.probing => Route::Secondary,
+            ActiveBackend::Secondary => {
+                let due = state
+                    .last_probe
+                    .is_none_or(|last_probe| last_probe.elapsed() >= self.probe_interval);
+                if due {
+                    state.probing = true;
+                    state.last_probe = Some(Instant::now());
+                    Route::Probe
+                } else {
+                    Route::Secondary
+                }
+            }
+        }
+    }
+
+    fn on_primary_error(&self, route: Route, error: &BackendError) {
+        let mut state = self.lock();
+        if route == Route::Probe {
+            state.probing = false;
+            tracing::debug!(backend = %self.label, ?error, "Failover primary probe failed");
+            return;
+        }
+        if state.active == ActiveBackend::Secondary {
+            return;
+        }
+        state.active = ActiveBackend::Secondary;
+        state.last_probe = Some(Instant::now());
+        drop(state);
+        tracing::warn!(backend = %self.label, ?error, "Primary backend failed, switching to secondary");
+        self.switched(ActiveBackend::Secondary);
+    }
+
+    fn on_primary_success(&self, route: Route) {
+        if route != Route::Probe {
+            return;
+        }
+        let mut state = self.lock();
+        state.active = ActiveBackend::Primary;
+        state.probing = false;
+        drop(state);
+        tracing::info!(backend = %self.label, "Primary backend recovered, switching back");
+        self.switched(ActiveBackend::Primary);
+    }
+
+    fn switched(&self, to: ActiveBackend) {
+        crate::metrics::record_failover_switch(self.label.as_str(), to.as_str(), to.as_gauge());
+    }
+
+    /// Runs an operation on the active backend, failing over on primary errors.
+    async fn call<T, PF, SF>(
+        &self,
+        on_primary: impl FnOnce() -> PF,
+        on_secondary: impl FnOnce() -> SF,
+    ) -> BackendResult<T>
+    where
+        PF: Future<Output = BackendResult<T>>,
+        SF: Future<Output = BackendResult<T>>,
+    {
+        let route = self.route();
+        if route == Route::Secondary {
+            return on_secondary().await;
+        }
+
+        let mut probe = ProbeGuard {
+            backend: self,
+            active: route == Route::Probe,
+        };
+        let result = on_primary().await;
+        probe.active = false;
+        match result {
+            Ok(value) => {
+                self.on_primary_success(route);
+                Ok(value)
+            }
+            Err(error) => {
+                self.on_primary_error(route, &error);
+                on_secondary().await
+            }
+        }
+    }
+}
+
+/// Releases the probe slot if a probing operation is dropped before completion.
+struct ProbeGuard<'a, P: Backend, S: Backend> {
+    backend: &'a FailoverBackend<P, S>,
+    active: bool,
+}
+
+impl<P: Backend, S: Backend> Drop for ProbeGuard<'_, P, S> {
+    fn drop(&mut self) {
+        if self.active {
+            self.backend.lock().probing = false;
+        }
+    }
+}
+
+#[async_trait]
+impl<P: Backend, S: Backend> Backend for FailoverBackend<P, S> {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        self.call(|| self.primary.read(key), || self.secondary.read(key))
+            .await
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let retry = value.clone();
+        self.call(
+            || self.primary.write(key, value),
+            || self.secondary.write(key, retry),
+        )
+        .await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        let (primary, secondary) =
+            futures::join!(self.primary.remove(key), self.secondary.remove(key));
+
+        match (primary, secondary) {
+            (Ok(primary), Ok(secondary)) => Ok(merge_delete_status(primary, secondary)),
+            (Ok(status), Err(error)) => {
+                tracing::warn!(backend = %self.label, ?error, "Failover secondary delete failed");
+                Ok(status)
+            }
+            (Err(error), Ok(status)) => {
+                self.on_primary_error(Route::Primary, &error);
+                Ok(status)
+            }
+            (Err(error), Err(secondary_error)) => {
+                tracing::error!(
+                    backend = %self.label,
+                    primary_error = ?error,
+                    secondary_error = ?secondary_error,
+                    "Failover primary and secondary delete failed"
+                );
+                self.on_primary_error(Route::Primary, &error);
+                Err(error)
+            }
+        }
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.primary.value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.primary.key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.primary.compressor()
+    }
+}
+
+impl<P: Backend, S: Backend> CacheBackend for FailoverBackend<P, S> {}
+
+fn merge_delete_status(primary: DeleteStatus, secondary: DeleteStatus) -> DeleteStatus {
+    match (primary, secondary) {
+        (DeleteStatus::Deleted(a), DeleteStatus::Deleted(b)) => DeleteStatus::Deleted(a + b),
+        (DeleteStatus::Deleted(n), DeleteStatus::Missing)
+        | (DeleteStatus::Missing, DeleteStatus::Deleted(n)) => DeleteStatus::Deleted(n),
+        (DeleteStatus::Missing, DeleteStatus::Missing) => DeleteStatus::Missing,
+    }
+}
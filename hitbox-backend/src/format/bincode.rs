@@ -34,7 +34,7 @@ impl ::bincode::enc::write::Writer for BincodeVecWriter {
 
 /// Fast, compact binary serialization.
 ///
-/// Produces the smallest output among serde-based formats with good performance.
+/// Produces compact output with good performance.
 /// Not human-readable. Uses bincode's standard configuration.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct BincodeFormat;
@@ -0,0 +1,48 @@
+use bytes::Bytes;
+use hitbox_core::{BoxContext, Raw};
+
+use super::{Format, FormatDeserializer, FormatError, FormatSerializer, FormatTypeId};
+use crate::context::Context;
+
+/// CBOR (RFC 8949) serialization format.
+///
+/// Self-describing binary format with libraries in most languages, for
+/// entries shared with non-Rust services. Structs are written as maps keyed
+/// by field name and binary data as CBOR byte strings.
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor_format")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborFormat;
+
+impl Format for CborFormat {
+    fn with_serializer(
+        &self,
+        f: &mut dyn FnMut(&mut FormatSerializer) -> Result<(), FormatError>,
+        _context: &dyn Context,
+    ) -> Result<Raw, FormatError> {
+        // ciborium does not expose its serializer, so values are written
+        // directly into the buffer by FormatSerializer::Cbor
+        let mut buf = Vec::new();
+        let mut format_ser = FormatSerializer::Cbor(&mut buf);
+        f(&mut format_ser)?;
+        Ok(Bytes::from(buf))
+    }
+
+    fn with_deserializer(
+        &self,
+        data: &[u8],
+        f: &mut dyn FnMut(&mut FormatDeserializer) -> Result<(), FormatError>,
+        _ctx: &mut BoxContext,
+    ) -> Result<(), FormatError> {
+        let mut format_deserializer = FormatDeserializer::Cbor(data);
+        f(&mut format_deserializer)?;
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Format> {
+        Box::new(*self)
+    }
+
+    fn format_type_id(&self) -> FormatTypeId {
+        FormatTypeId::Cbor
+    }
+}
@@ -61,13 +61,28 @@ pub struct BincodeEncoder<'a>(pub(crate) &'a mut EncoderImpl<BincodeVecWriter, C
 pub struct BincodeDecoder<'a>(pub(crate) &'a mut DecoderImpl<SliceReader<'a>, Configuration, ()>);
 
 mod bincode;
+#[cfg(feature = "cbor_format")]
+mod cbor;
 mod json;
+#[cfg(feature = "msgpack_format")]
+mod msgpack;
+#[cfg(feature = "postcard_format")]
+mod postcard;
 #[cfg(feature = "rkyv_format")]
 mod rkyv;
 mod ron;
 
 pub use bincode::BincodeFormat;
+#[cfg(feature = "cbor_format")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor_format")))]
+pub use cbor::CborFormat;
 pub use json::JsonFormat;
+#[cfg(feature = "msgpack_format")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack_format")))]
+pub use msgpack::MsgPackFormat;
+#[cfg(feature = "postcard_format")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard_format")))]
+pub use postcard::PostcardFormat;
 #[cfg(feature = "rkyv_format")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rkyv_format")))]
 pub use rkyv::RkyvFormat;
@@ -136,6 +151,12 @@ pub enum FormatTypeId {
     Ron,
     /// Rkyv format.
     Rkyv,
+    /// MessagePack format.
+    MsgPack,
+    /// CBOR format.
+    Cbor,
+    /// Postcard format.
+    Postcard,
     /// User-defined format. The string must be globally unique.
     Custom(&'static str),
 }
@@ -145,11 +166,14 @@ pub enum FormatTypeId {
 /// Wraps different serialization backends (serde, rkyv, bincode) in a unified interface.
 /// Call [`serialize`](Self::serialize) with any [`Cacheable`] value.
 pub enum FormatSerializer<'a> {
-    /// Serde-based serializer (used by JSON, RON).
+    /// Serde-based serializer (used by JSON, RON, MessagePack, Postcard).
     Serde(&'a mut dyn erased_serde::Serializer),
     /// Rkyv serializer (zero-copy).
     #[cfg(feature = "rkyv_format")]
     Rkyv(&'a mut AlignedVec),
+    /// CBOR serializer writing into the buffer.
+    #[cfg(feature = "cbor_format")]
+    Cbor(&'a mut Vec<u8>),
     /// Bincode serializer (opaque, cannot be constructed externally).
     Bincode(BincodeEncoder<'a>),
 }
@@ -180,6 +204,12 @@ impl<'a> FormatSerializer<'a> {
                 **buffer = result_buffer;
                 Ok(())
             }
+            #[cfg(feature = "cbor_format")]
+            FormatSerializer::Cbor(buffer) => {
+                let erased_value = value as &dyn erased_serde::Serialize;
+                ciborium::into_writer(erased_value, &mut **buffer)
+                    .map_err(|e| FormatError::Serialize(Box::new(e)))
+            }
             FormatSerializer::Bincode(enc) => {
                 // Use Compat wrapper to bridge serde and bincode
                 let compat = Compat(value);
@@ -194,11 +224,14 @@ impl<'a> FormatSerializer<'a> {
 /// Wraps different deserialization backends in a unified interface.
 /// Call [`deserialize`](Self::deserialize) to reconstruct the original value.
 pub enum FormatDeserializer<'a> {
-    /// Serde-based deserializer (used by JSON, RON).
+    /// Serde-based deserializer (used by JSON, RON, MessagePack, Postcard).
     Serde(&'a mut dyn erased_serde::Deserializer<'a>),
     /// Rkyv deserializer (validates and deserializes archived bytes).
     #[cfg(feature = "rkyv_format")]
     Rkyv(&'a [u8]),
+    /// CBOR deserializer reading the encoded bytes.
+    #[cfg(feature = "cbor_format")]
+    Cbor(&'a [u8]),
     /// Bincode deserializer (opaque, cannot be constructed externally).
     Bincode(BincodeDecoder<'a>),
 }
@@ -222,6 +255,10 @@ impl<'a> FormatDeserializer<'a> {
                     .map_err(|e| FormatError::Deserialize(Box::new(RkyvValidationError::new(e))))?;
                 Ok(value)
             }
+            #[cfg(feature = "cbor_format")]
+            FormatDeserializer::Cbor(data) => {
+                ciborium::from_reader(*data).map_err(|e| FormatError::Deserialize(Box::new(e)))
+            }
             FormatDeserializer::Bincode(dec) => {
                 // Use Compat wrapper to decode from bincode
                 let compat: Compat<T> =
@@ -377,3 +414,125 @@ impl Format for std::sync::Arc<dyn Format> {
         (**self).format_type_id()
     }
 }
+
+#[cfg(all(
+    test,
+    any(
+        feature = "msgpack_format",
+        feature = "cbor_format",
+        feature = "postcard_format"
+    )
+))]
+mod tests {
+    use super::*;
+    #[cfg(feature = "rkyv_format")]
+    use ::rkyv::{Archive, Serialize as RkyvSerialize};
+    use hitbox_core::CacheContext;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    #[cfg_attr(
+        feature = "rkyv_format",
+        derive(Archive, RkyvSerialize, ::rkyv::Deserialize)
+    )]
+    struct TestData {
+        id: u32,
+        name: String,
+        body: Vec<u8>,
+    }
+
+    fn test_data() -> TestData {
+        TestData {
+            id: 42,
+            name: "hitbox".to_string(),
+            body: b"payload".to_vec(),
+        }
+    }
+
+    fn roundtrip(format: &dyn Format) {
+        let data = test_data();
+        let serialized = format.serialize(&data, &CacheContext::default()).unwrap();
+        let mut ctx = CacheContext::default().boxed();
+        let deserialized: TestData = format.deserialize(&serialized, &mut ctx).unwrap();
+        assert_eq!(deserialized, data);
+    }
+
+    #[cfg(feature = "msgpack_format")]
+    #[test]
+    fn test_msgpack_roundtrip() {
+        roundtrip(&MsgPackFormat);
+    }
+
+    #[cfg(feature = "msgpack_format")]
+    #[test]
+    fn test_msgpack_writes_named_fields() {
+        let serialized = MsgPackFormat
+            .serialize(&test_data(), &CacheContext::default())
+            .unwrap();
+        let value: serde_json::Value = rmp_serde::from_slice(&serialized).unwrap();
+        assert_eq!(value["id"], 42);
+        assert_eq!(value["name"], "hitbox");
+    }
+
+    #[cfg(feature = "cbor_format")]
+    #[test]
+    fn test_cbor_roundtrip() {
+        roundtrip(&CborFormat);
+    }
+
+    #[cfg(feature = "cbor_format")]
+    #[test]
+    fn test_cbor_writes_named_fields() {
+        let serialized = CborFormat
+            .serialize(&test_data(), &CacheContext::default())
+            .unwrap();
+        let value: serde_json::Value = ciborium::from_reader(serialized.as_ref()).unwrap();
+        assert_eq!(value["id"], 42);
+        assert_eq!(value["name"], "hitbox");
+    }
+
+    #[cfg(feature = "cbor_format")]
+    #[test]
+    fn test_cbor_rejects_garbage() {
+        let mut ctx = CacheContext::default().boxed();
+        let result: Result<TestData, _> =
+            CborFormat.deserialize(&Raw::from_static(&[0xff, 0x00]), &mut ctx);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "postcard_format")]
+    #[test]
+    fn test_postcard_roundtrip() {
+        roundtrip(&PostcardFormat);
+    }
+
+    #[cfg(feature = "postcard_format")]
+    #[test]
+    fn test_postcard_smaller_than_bincode() {
+        let data = TestData {
+            id: 1000,
+            name: "x".repeat(300),
+            body: vec![0; 5000],
+        };
+        let ctx = CacheContext::default();
+        let postcard_size = PostcardFormat.serialize(&data, &ctx).unwrap().len();
+        let bincode_size = BincodeFormat.serialize(&data, &ctx).unwrap().len();
+
+        // Postcard's varints encode the id and the length prefixes here in
+        // fewer bytes than bincode's. The exact saving depends on both
+        // encodings, so only the ordering is checked.
+        assert!(postcard_size < bincode_size);
+    }
+
+    #[cfg(feature = "postcard_format")]
+    #[test]
+    fn test_postcard_rejects_truncated_data() {
+        let serialized = PostcardFormat
+            .serialize(&test_data(), &CacheContext::default())
+            .unwrap();
+        let mut ctx = CacheContext::default().boxed();
+        let result: Result<TestData, _> =
+            PostcardFormat.deserialize(&serialized.slice(..serialized.len() - 1), &mut ctx);
+        assert!(result.is_err());
+    }
+}
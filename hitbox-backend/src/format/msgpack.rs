@@ -0,0 +1,53 @@
+use bytes::Bytes;
+use hitbox_core::{BoxContext, Raw};
+
+use super::{Format, FormatDeserializer, FormatError, FormatSerializer, FormatTypeId};
+use crate::context::Context;
+
+/// MessagePack serialization format.
+///
+/// Compact binary format readable by MessagePack libraries in most languages,
+/// for entries shared with non-Rust services. Structs are written as maps
+/// keyed by field name, so other readers see named fields; output is larger
+/// than [`BincodeFormat`](super::BincodeFormat) as a result.
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack_format")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgPackFormat;
+
+impl Format for MsgPackFormat {
+    fn with_serializer(
+        &self,
+        f: &mut dyn FnMut(&mut FormatSerializer) -> Result<(), FormatError>,
+        _context: &dyn Context,
+    ) -> Result<Raw, FormatError> {
+        let mut buf = Vec::new();
+        {
+            let mut ser = rmp_serde::Serializer::new(&mut buf).with_struct_map();
+            let mut erased = <dyn erased_serde::Serializer>::erase(&mut ser);
+            let mut format_ser = FormatSerializer::Serde(&mut erased);
+            f(&mut format_ser)?;
+        }
+        Ok(Bytes::from(buf))
+    }
+
+    fn with_deserializer(
+        &self,
+        data: &[u8],
+        f: &mut dyn FnMut(&mut FormatDeserializer) -> Result<(), FormatError>,
+        _ctx: &mut BoxContext,
+    ) -> Result<(), FormatError> {
+        let mut deserializer = rmp_serde::Deserializer::from_read_ref(data);
+        let mut erased = <dyn erased_serde::Deserializer>::erase(&mut deserializer);
+        let mut format_deserializer = FormatDeserializer::Serde(&mut erased);
+        f(&mut format_deserializer)?;
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Format> {
+        Box::new(*self)
+    }
+
+    fn format_type_id(&self) -> FormatTypeId {
+        FormatTypeId::MsgPack
+    }
+}
@@ -0,0 +1,60 @@
+use ::postcard::ser_flavors::{AllocVec, Flavor};
+use bytes::Bytes;
+use hitbox_core::{BoxContext, Raw};
+
+use super::{Format, FormatDeserializer, FormatError, FormatSerializer, FormatTypeId};
+use crate::context::Context;
+
+/// Postcard serialization format.
+///
+/// Varint-encoded binary format designed for embedded targets. Integers and
+/// lengths below 16384 take at most two bytes, so output is usually a little
+/// smaller than [`BincodeFormat`](super::BincodeFormat); useful when bytes
+/// stored or sent to a remote cache matter most. Not self-describing: entries
+/// can only be read back into the type that wrote them.
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard_format")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostcardFormat;
+
+impl Format for PostcardFormat {
+    fn with_serializer(
+        &self,
+        f: &mut dyn FnMut(&mut FormatSerializer) -> Result<(), FormatError>,
+        _context: &dyn Context,
+    ) -> Result<Raw, FormatError> {
+        let mut ser = ::postcard::Serializer {
+            output: AllocVec::new(),
+        };
+        {
+            let mut erased = <dyn erased_serde::Serializer>::erase(&mut ser);
+            let mut format_ser = FormatSerializer::Serde(&mut erased);
+            f(&mut format_ser)?;
+        }
+        let buf = ser
+            .output
+            .finalize()
+            .map_err(|e| FormatError::Serialize(Box::new(e)))?;
+        Ok(Bytes::from(buf))
+    }
+
+    fn with_deserializer(
+        &self,
+        data: &[u8],
+        f: &mut dyn FnMut(&mut FormatDeserializer) -> Result<(), FormatError>,
+        _ctx: &mut BoxContext,
+    ) -> Result<(), FormatError> {
+        let mut deserializer = ::postcard::Deserializer::from_bytes(data);
+        let mut erased = <dyn erased_serde::Deserializer>::erase(&mut deserializer);
+        let mut format_deserializer = FormatDeserializer::Serde(&mut erased);
+        f(&mut format_deserializer)?;
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Format> {
+        Box::new(*self)
+    }
+
+    fn format_type_id(&self) -> FormatTypeId {
+        FormatTypeId::Postcard
+    }
+}
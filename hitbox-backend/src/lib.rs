@@ -3,25 +3,51 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod backend;
+pub mod circuit_breaker;
 pub mod composition;
 pub mod compressor;
 pub mod context;
 pub mod error;
+pub mod failover;
 pub mod format;
 pub mod key;
 pub(crate) mod metrics;
+pub mod sharded;
+pub mod timeout;
 
 pub use backend::{Backend, BackendResult, CacheBackend, DeleteStatus, SyncBackend, UnsyncBackend};
-pub use composition::{Compose, CompositionBackend};
+pub use circuit_breaker::{CircuitBreakerBackend, CircuitOpenError, CircuitState, CircuitStatus};
+pub use composition::{Compose, CompositionBackend, TieredBackend};
+#[cfg(feature = "brotli")]
+#[cfg_attr(docsrs, doc(cfg(feature = "brotli")))]
+pub use compressor::BrotliCompressor;
 #[cfg(feature = "gzip")]
 #[cfg_attr(docsrs, doc(cfg(feature = "gzip")))]
 pub use compressor::GzipCompressor;
+#[cfg(feature = "lz4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+pub use compressor::Lz4Compressor;
+#[cfg(feature = "snappy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snappy")))]
+pub use compressor::SnappyCompressor;
+pub use compressor::{CompressionError, Compressor, PassthroughCompressor};
 #[cfg(feature = "zstd")]
 #[cfg_attr(docsrs, doc(cfg(feature = "zstd")))]
-pub use compressor::ZstdCompressor;
-pub use compressor::{CompressionError, Compressor, PassthroughCompressor};
+pub use compressor::{ZstdCompressor, ZstdDictCompressor, ZstdDictionary};
 pub use error::BackendError;
+pub use failover::{ActiveBackend, FailoverBackend};
+#[cfg(feature = "cbor_format")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor_format")))]
+pub use format::CborFormat;
+#[cfg(feature = "msgpack_format")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack_format")))]
+pub use format::MsgPackFormat;
+#[cfg(feature = "postcard_format")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard_format")))]
+pub use format::PostcardFormat;
 #[cfg(feature = "rkyv_format")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rkyv_format")))]
 pub use format::RkyvFormat;
 pub use key::CacheKeyFormat;
+pub use sharded::ShardedBackend;
+pub use timeout::{TimeoutBackend, WriteTimeoutAction};
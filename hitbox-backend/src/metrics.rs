@@ -9,6 +9,10 @@
 //!
 //! - `hitbox_backend_read_*` - read operation metrics
 //! - `hitbox_backend_write_*` - write operation metrics
+//! - `hitbox_backend_{read,write}_timeouts_total` - deadline metrics of [`TimeoutBackend`](crate::TimeoutBackend)
+//! - `hitbox_backend_circuit_*` - state of [`CircuitBreakerBackend`](crate::CircuitBreakerBackend)
+//! - `hitbox_backend_failover_*` - state of [`FailoverBackend`](crate::FailoverBackend)
+//! - `hitbox_backend_write_behind_*` - queue of [`WriteBehindWritePolicy`](crate::composition::policy::WriteBehindWritePolicy)
 //! - `hitbox_backend_{compress,decompress,serialize,deserialize}_duration_seconds` - processing metrics
 
 use std::time::Duration;
@@ -140,6 +144,95 @@ lazy_static! {
         "hitbox_backend_write_errors_total"
     };
 
+    // Timeout metrics
+
+    /// Metric name for read timeouts counter.
+    pub static ref BACKEND_READ_TIMEOUTS: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_backend_read_timeouts_total",
+            "Total number of cache reads that exceeded their deadline and were treated as misses."
+        );
+        "hitbox_backend_read_timeouts_total"
+    };
+
+    /// Metric name for write timeouts counter.
+    pub static ref BACKEND_WRITE_TIMEOUTS: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_backend_write_timeouts_total",
+            "Total number of cache writes that exceeded their deadline, by action taken."
+        );
+        "hitbox_backend_write_timeouts_total"
+    };
+
+    // Circuit breaker metrics
+
+    /// Metric name for circuit breaker state gauge.
+    pub static ref BACKEND_CIRCUIT_STATE: &'static str = {
+        metrics::describe_gauge!(
+            "hitbox_backend_circuit_state",
+            "Circuit breaker state per backend: 0 closed, 1 half-open, 2 open."
+        );
+        "hitbox_backend_circuit_state"
+    };
+
+    /// Metric name for circuit breaker transitions counter.
+    pub static ref BACKEND_CIRCUIT_TRANSITIONS: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_backend_circuit_transitions_total",
+            "Total number of circuit breaker state transitions, by new state."
+        );
+        "hitbox_backend_circuit_transitions_total"
+    };
+
+    /// Metric name for short-circuited operations counter.
+    pub static ref BACKEND_CIRCUIT_REJECTED: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_backend_circuit_rejected_total",
+            "Total number of operations short-circuited by an open circuit breaker."
+        );
+        "hitbox_backend_circuit_rejected_total"
+    };
+
+    // Failover metrics
+
+    /// Metric name for failover active backend gauge.
+    pub static ref BACKEND_FAILOVER_ACTIVE: &'static str = {
+        metrics::describe_gauge!(
+            "hitbox_backend_failover_active",
+            "Backend serving traffic per failover pair: 0 primary, 1 secondary."
+        );
+        "hitbox_backend_failover_active"
+    };
+
+    /// Metric name for failover switches counter.
+    pub static ref BACKEND_FAILOVER_SWITCHES: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_backend_failover_switches_total",
+            "Total number of switches between primary and secondary, by target."
+        );
+        "hitbox_backend_failover_switches_total"
+    };
+
+    // Write-behind metrics
+
+    /// Metric name for write-behind queue depth gauge.
+    pub static ref BACKEND_WRITE_BEHIND_QUEUE_DEPTH: &'static str = {
+        metrics::describe_gauge!(
+            "hitbox_backend_write_behind_queue_depth",
+            "Number of keys waiting for a write-behind L2 write."
+        );
+        "hitbox_backend_write_behind_queue_depth"
+    };
+
+    /// Metric name for dropped write-behind writes counter.
+    pub static ref BACKEND_WRITE_BEHIND_DROPPED: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_backend_write_behind_dropped_total",
+            "Total number of write-behind L2 writes dropped on a full queue."
+        );
+        "hitbox_backend_write_behind_dropped_total"
+    };
+
     // Processing duration metrics
 
     /// Metric name for decompression duration histogram.
@@ -265,6 +358,121 @@ pub fn record_write_error(backend: &str) {
 #[inline]
 pub fn record_write_error(_backend: &str) {}
 
+// Timeout metrics
+
+/// Record a read that exceeded its deadline.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_read_timeout(backend: &str) {
+    metrics::counter!(*BACKEND_READ_TIMEOUTS, "backend" => backend.to_string()).increment(1);
+}
+
+/// Record a read timeout (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_read_timeout(_backend: &str) {}
+
+/// Record a write that exceeded its deadline, labeled with the action taken.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_write_timeout(backend: &str, action: &'static str) {
+    metrics::counter!(
+        *BACKEND_WRITE_TIMEOUTS,
+        "backend" => backend.to_string(),
+        "action" => action
+    )
+    .increment(1);
+}
+
+/// Record a write timeout (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_write_timeout(_backend: &str, _action: &'static str) {}
+
+// Circuit breaker metrics
+
+/// Record a circuit breaker transition to a new state.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_circuit_state(backend: &str, state: &'static str, gauge: f64) {
+    metrics::gauge!(*BACKEND_CIRCUIT_STATE, "backend" => backend.to_string()).set(gauge);
+    metrics::counter!(
+        *BACKEND_CIRCUIT_TRANSITIONS,
+        "backend" => backend.to_string(),
+        "state" => state
+    )
+    .increment(1);
+}
+
+/// Record a circuit breaker transition (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_circuit_state(_backend: &str, _state: &'static str, _gauge: f64) {}
+
+/// Record an operation short-circuited by an open circuit breaker.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_circuit_rejected(backend: &str, operation: &'static str) {
+    metrics::counter!(
+        *BACKEND_CIRCUIT_REJECTED,
+        "backend" => backend.to_string(),
+        "operation" => operation
+    )
+    .increment(1);
+}
+
+/// Record a short-circuited operation (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_circuit_rejected(_backend: &str, _operation: &'static str) {}
+
+// Failover metrics
+
+/// Record a failover switch to the given backend role.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_failover_switch(backend: &str, to: &'static str, gauge: f64) {
+    metrics::gauge!(*BACKEND_FAILOVER_ACTIVE, "backend" => backend.to_string()).set(gauge);
+    metrics::counter!(
+        *BACKEND_FAILOVER_SWITCHES,
+        "backend" => backend.to_string(),
+        "to" => to
+    )
+    .increment(1);
+}
+
+/// Record a failover switch (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_failover_switch(_backend: &str, _to: &'static str, _gauge: f64) {}
+
+// Write-behind metrics
+
+/// Record the number of keys waiting in a write-behind queue.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_write_behind_depth(backend: &str, depth: usize) {
+    metrics::gauge!(*BACKEND_WRITE_BEHIND_QUEUE_DEPTH, "backend" => backend.to_string())
+        .set(depth as f64);
+}
+
+/// Record write-behind queue depth (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_write_behind_depth(_backend: &str, _depth: usize) {}
+
+/// Record a write-behind L2 write dropped on a full queue.
+#[cfg(feature = "metrics")]
+#[inline]
+pub fn record_write_behind_dropped(backend: &str) {
+    metrics::counter!(*BACKEND_WRITE_BEHIND_DROPPED, "backend" => backend.to_string()).increment(1);
+}
+
+/// Record a dropped write-behind write (no-op when `metrics` feature disabled).
+#[cfg(not(feature = "metrics"))]
+#[inline]
+pub fn record_write_behind_dropped(_backend: &str) {}
+
 // Processing metrics
 
 /// Record decompression duration.
@@ -0,0 +1,224 @@
+//! Consistent-hash sharding across independent backends.
+//!
+//! [`ShardedBackend`] spreads keys over several backends of the same kind,
+//! e.g. independent Redis instances that are not a Redis Cluster. Each
+//! [`CacheKey`] is routed to exactly one shard with rendezvous (highest random
+//! weight) hashing: every shard scores the key and the highest score wins.
+//!
+//! Shards are identified by their [`label`](Backend::label), not by their
+//! position, so adding or removing a shard only moves the keys that belong
+//! to it — about `1/N` of all keys. Give every shard a distinct label; shards
+//! sharing a label are told apart by their order among themselves.
+//!
+//! All shards should use the same value format and compressor: behind a
+//! trait object, values are encoded with those of the first shard.
+//!
+//! # Response source
+//!
+//! When used through [`CacheBackend`] directly, a hit reports the serving
+//! shard in the context as `{label}.{shard label}` (e.g. `sharded.redis-2`).
+//! As with [`CompositionBackend`](crate::CompositionBackend), a sharded
+//! backend behind a trait object reports its own label.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use hitbox_backend::ShardedBackend;
+//!
+//! let backend = ShardedBackend::new(vec![redis_1, redis_2, redis_3]).label("redis");
+//! ```
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use hitbox_core::{
+    BackendLabel, BoxContext, CacheKey, CacheValue, Cacheable, CacheableResponse, Raw,
+};
+
+use crate::format::Format;
+use crate::{Backend, BackendResult, CacheBackend, CacheKeyFormat, Compressor, DeleteStatus};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+const SEPARATOR: u8 = 0xff;
+
+/// FNV-1a, stable across processes and platforms unlike `std::hash`.
+#[derive(Debug, Clone, Copy)]
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> &mut Self {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+        self
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// SplitMix64 finalizer, spreading the combined key and shard hashes.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+fn key_hash(key: &CacheKey) -> u64 {
+    let mut hasher = Fnv::new();
+    hasher
+        .write(key.prefix().as_bytes())
+        .write(&[SEPARATOR])
+        .write(&key.version().to_le_bytes());
+    for part in key.parts() {
+        hasher.write(&[SEPARATOR]).write(part.key().as_bytes());
+        match part.value() {
+            Some(value) => hasher.write(&[1]).write(value.as_bytes()),
+            None => hasher.write(&[0]),
+        };
+    }
+    hasher.finish()
+}
+
+/// A backend routing each key to one of several shards by consistent hashing.
+#[derive(Clone, Debug)]
+pub struct ShardedBackend<B> {
+    shards: Vec<B>,
+    seeds: Vec<u64>,
+    label: BackendLabel,
+}
+
+impl<B: Backend> ShardedBackend<B> {
+    /// Creates a sharded backend over `shards`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is empty.
+    pub fn new(shards: Vec<B>) -> Self {
+        assert!(
+            !shards.is_empty(),
+            "sharded backend needs at least one shard"
+        );
+
+        let mut occurrences: HashMap<BackendLabel, u32> = HashMap::new();
+        let seeds = shards
+            .iter()
+            .map(|shard| {
+                let label = shard.label();
+                let occurrence = occurrences.entry(label.clone()).or_default();
+                let seed = Fnv::new()
+                    .write(label.as_str().as_bytes())
+                    .write(&[SEPARATOR])
+                    .write(&occurrence.to_le_bytes())
+                    .finish();
+                *occurrence += 1;
+                seed
+            })
+            .collect();
+
+        Self {
+            shards,
+            seeds,
+            label: BackendLabel::new_static("sharded"),
+        }
+    }
+
+    /// Sets a custom label for this backend.
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Returns the shards in declaration order.
+    pub fn shards(&self) -> &[B] {
+        &self.shards
+    }
+
+    /// Returns the index of the shard owning `key`.
+    pub fn shard_index(&self, key: &CacheKey) -> usize {
+        let hash = key_hash(key);
+        self.seeds
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, seed)| mix(hash ^ **seed))
+            .map(|(index, _)| index)
+            .expect("sharded backend has at least one shard")
+    }
+
+    /// Returns the shard owning `key`.
+    pub fn shard_for(&self, key: &CacheKey) -> &B {
+        &self.shards[self.shard_index(key)]
+    }
+}
+
+#[async_trait]
+impl<B: Backend> Backend for ShardedBackend<B> {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        self.shard_for(key).read(key).await
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        self.shard_for(key).write(key, value).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.shard_for(key).remove(key).await
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.shards[0].value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.shards[0].key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.shards[0].compressor()
+    }
+}
+
+impl<B: CacheBackend> CacheBackend for ShardedBackend<B> {
+    async fn get<T>(
+        &self,
+        key: &CacheKey,
+        ctx: &mut BoxContext,
+    ) -> BackendResult<Option<CacheValue<T::Cached>>>
+    where
+        T: CacheableResponse,
+        T::Cached: Cacheable,
+    {
+        let mut shard_ctx = ctx.clone_box();
+        let result = self.shard_for(key).get::<T>(key, &mut shard_ctx).await;
+        ctx.merge_from(&*shard_ctx, &self.label);
+        result
+    }
+
+    async fn set<T>(
+        &self,
+        key: &CacheKey,
+        value: &CacheValue<T::Cached>,
+        ctx: &mut BoxContext,
+    ) -> BackendResult<()>
+    where
+        T: CacheableResponse,
+        T::Cached: Cacheable,
+    {
+        self.shard_for(key).set::<T>(key, value, ctx).await
+    }
+
+    async fn delete(&self, key: &CacheKey, ctx: &mut BoxContext) -> BackendResult<DeleteStatus> {
+        self.shard_for(key).delete(key, ctx).await
+    }
+}
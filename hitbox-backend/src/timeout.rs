@@ -0,0 +1,216 @@
+//! Read and write deadlines for any backend.
+//!
+//! [`TimeoutBackend`] wraps a [`Backend`] and bounds how long a cache
+//! operation may take, so a slow cache degrades into cache misses instead of
+//! slow responses:
+//!
+//! - A **read** that exceeds its deadline is treated as a miss, and the
+//!   request falls through to upstream.
+//! - A **write** that exceeds its deadline is either dropped or handed over
+//!   to an [`Offload`] to finish in the background, see [`WriteTimeoutAction`].
+//!
+//! Removes are not bounded: silently skipping an invalidation would leave
+//! stale data in the cache.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use hitbox_backend::TimeoutBackend;
+//!
+//! let backend = TimeoutBackend::new(redis)
+//!     .read_timeout(Duration::from_millis(20))
+//!     .write_timeout(Duration::from_millis(50))
+//!     .offload_timed_out_writes(offload);
+//! ```
+
+use std::pin::pin;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::future::{Either, select};
+use hitbox_core::{BackendLabel, CacheKey, CacheValue, DisabledOffload, Offload, Raw};
+
+use crate::format::Format;
+use crate::{Backend, BackendResult, CacheBackend, CacheKeyFormat, Compressor, DeleteStatus};
+
+/// What to do with a write that exceeded its deadline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WriteTimeoutAction {
+    /// Cancel the write. The entry is not cached.
+    #[default]
+    Drop,
+    /// Let the write finish in the background via the configured [`Offload`].
+    Offload,
+}
+
+impl WriteTimeoutAction {
+    /// Returns the action name for metrics labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Drop => "dropped",
+            Self::Offload => "offloaded",
+        }
+    }
+}
+
+/// A backend wrapper applying read and write deadlines to an inner backend.
+///
+/// Serialization format, key format, compressor and label are those of the
+/// inner backend, so wrapping is transparent for stored data and metrics.
+#[derive(Clone, Debug)]
+pub struct TimeoutBackend<B, O = DisabledOffload> {
+    inner: B,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    on_write_timeout: WriteTimeoutAction,
+    offload: O,
+}
+
+impl<B: Backend> TimeoutBackend<B, DisabledOffload> {
+    /// Wraps a backend without any deadlines.
+    ///
+    /// Use [`read_timeout`](Self::read_timeout) and
+    /// [`write_timeout`](Self::write_timeout) to set them.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            read_timeout: None,
+            write_timeout: None,
+            on_write_timeout: WriteTimeoutAction::Drop,
+            offload: DisabledOffload,
+        }
+    }
+}
+
+impl<B: Backend, O> TimeoutBackend<B, O> {
+    /// Sets the read deadline. Slower reads are reported as misses.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the write deadline. Slower writes are dropped or offloaded.
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = Some(timeout);
+        self
+    }
+
+    /// Finishes timed out writes in the background instead of dropping them.
+    pub fn offload_timed_out_writes<NewO>(self, offload: NewO) -> TimeoutBackend<B, NewO>
+    where
+        NewO: Offload<'static>,
+    {
+        TimeoutBackend {
+            inner: self.inner,
+            read_timeout: self.read_timeout,
+            write_timeout: self.write_timeout,
+            on_write_timeout: WriteTimeoutAction::Offload,
+            offload,
+        }
+    }
+
+    /// Returns a reference to the wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Returns the read deadline, if any.
+    pub fn read_deadline(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Returns the write deadline, if any.
+    pub fn write_deadline(&self) -> Option<Duration> {
+        self.write_timeout
+    }
+
+    /// Returns what happens to writes exceeding the deadline.
+    pub fn on_write_timeout(&self) -> WriteTimeoutAction {
+        self.on_write_timeout
+    }
+}
+
+#[async_trait]
+impl<B, O> Backend for TimeoutBackend<B, O>
+where
+    B: Backend + Clone + 'static,
+    O: Offload<'static>,
+{
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let Some(timeout) = self.read_timeout else {
+            return self.inner.read(key).await;
+        };
+        match tokio::time::timeout(timeout, self.inner.read(key)).await {
+            Ok(result) => result,
+            Err(_) => {
+                let label = self.inner.label();
+                tracing::warn!(backend = %label, ?timeout, "Backend read timed out, treating as miss");
+                crate::metrics::record_read_timeout(label.as_str());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let Some(timeout) = self.write_timeout else {
+            return self.inner.write(key, value).await;
+        };
+
+        // Own everything the write needs, so it can outlive this call when offloaded.
+        let inner = self.inner.clone();
+        let write_key = key.clone();
+        let write = Box::pin(async move { inner.write(&write_key, value).await });
+
+        match select(write, pin!(tokio::time::sleep(timeout))).await {
+            Either::Left((result, _)) => result,
+            Either::Right(((), write)) => {
+                let label = self.inner.label();
+                let action = self.on_write_timeout;
+                tracing::warn!(
+                    backend = %label,
+                    ?timeout,
+                    action = action.as_str(),
+                    "Backend write timed out"
+                );
+                crate::metrics::record_write_timeout(label.as_str(), action.as_str());
+                if action == WriteTimeoutAction::Offload {
+                    self.offload
+                        .spawn_for_key("timed_out_write", key, async move {
+                            if let Err(error) = write.await {
+                                tracing::warn!(?error, "Offloaded backend write failed");
+                            }
+                        });
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.inner.remove(key).await
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.inner.label()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.inner.value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.inner.key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.inner.compressor()
+    }
+}
+
+impl<B, O> CacheBackend for TimeoutBackend<B, O>
+where
+    B: Backend + Clone + 'static,
+    O: Offload<'static>,
+{
+}
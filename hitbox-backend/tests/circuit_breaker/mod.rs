@@ -0,0 +1,120 @@
+//! Tests for CircuitBreakerBackend state transitions.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use hitbox_backend::{Backend, CircuitBreakerBackend, CircuitState};
+use hitbox_core::{CacheKey, CacheValue, Raw};
+
+use crate::common::FlakyBackend;
+
+const OPEN_DURATION: Duration = Duration::from_millis(50);
+
+fn value() -> CacheValue<Raw> {
+    CacheValue::new(Bytes::from_static(b"value"), None, None)
+}
+
+fn breaker(inner: FlakyBackend) -> CircuitBreakerBackend<FlakyBackend> {
+    CircuitBreakerBackend::new(inner)
+        .failure_threshold(3)
+        .failure_window(Duration::from_secs(10))
+        .open_duration(OPEN_DURATION)
+}
+
+/// Fails `count` reads through the breaker.
+async fn fail_reads(backend: &CircuitBreakerBackend<FlakyBackend>, count: usize) {
+    let key = CacheKey::from_str("circuit", "failing");
+    for _ in 0..count {
+        assert!(backend.read(&key).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_opens_after_threshold() {
+    let inner = FlakyBackend::default();
+    inner.set_failing(true);
+    let backend = breaker(inner.clone());
+
+    fail_reads(&backend, 2).await;
+    let status = backend.status();
+    assert_eq!(status.state, CircuitState::Closed);
+    assert_eq!(status.recent_failures, 2);
+
+    fail_reads(&backend, 1).await;
+    assert_eq!(backend.status().state, CircuitState::Open);
+    assert_eq!(inner.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_open_circuit_skips_inner_backend() {
+    let inner = FlakyBackend::default();
+    inner.set_failing(true);
+    let backend = breaker(inner.clone());
+    fail_reads(&backend, 3).await;
+
+    let key = CacheKey::from_str("circuit", "open");
+    assert!(backend.read(&key).await.unwrap().is_none());
+    backend.write(&key, value()).await.unwrap();
+    assert!(backend.remove(&key).await.is_err());
+    assert_eq!(inner.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_failures_outside_window_are_forgotten() {
+    let inner = FlakyBackend::default();
+    inner.set_failing(true);
+    let backend = breaker(inner).failure_window(Duration::from_millis(30));
+
+    fail_reads(&backend, 2).await;
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    fail_reads(&backend, 2).await;
+
+    let status = backend.status();
+    assert_eq!(status.state, CircuitState::Closed);
+    assert_eq!(status.recent_failures, 2);
+}
+
+#[tokio::test]
+async fn test_successful_probe_closes_circuit() {
+    let inner = FlakyBackend::default();
+    inner.set_failing(true);
+    let backend = breaker(inner.clone());
+    fail_reads(&backend, 3).await;
+
+    inner.set_failing(false);
+    tokio::time::sleep(OPEN_DURATION * 2).await;
+
+    let key = CacheKey::from_str("circuit", "probe");
+    backend.write(&key, value()).await.unwrap();
+    assert_eq!(backend.status().state, CircuitState::Closed);
+    assert!(backend.read(&key).await.unwrap().is_some());
+    assert_eq!(inner.calls(), 5);
+}
+
+#[tokio::test]
+async fn test_failed_probe_reopens_circuit() {
+    let inner = FlakyBackend::default();
+    inner.set_failing(true);
+    let backend = breaker(inner.clone());
+    fail_reads(&backend, 3).await;
+
+    tokio::time::sleep(OPEN_DURATION * 2).await;
+    fail_reads(&backend, 1).await;
+    assert_eq!(backend.status().state, CircuitState::Open);
+
+    // Open again: the next read does not reach the backend.
+    let key = CacheKey::from_str("circuit", "reopened");
+    assert!(backend.read(&key).await.unwrap().is_none());
+    assert_eq!(inner.calls(), 4);
+}
+
+#[tokio::test]
+async fn test_clones_share_circuit() {
+    let inner = FlakyBackend::default();
+    inner.set_failing(true);
+    let backend = breaker(inner);
+    let clone = backend.clone();
+
+    fail_reads(&backend, 3).await;
+    assert_eq!(clone.status().state, CircuitState::Open);
+}
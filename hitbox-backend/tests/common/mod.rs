@@ -2,4 +2,4 @@
 
 pub mod test_backend;
 
-pub use test_backend::{ErrorBackend, TestBackend};
+pub use test_backend::{ErrorBackend, FlakyBackend, SlowBackend, TestBackend};
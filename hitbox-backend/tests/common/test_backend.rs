@@ -9,6 +9,8 @@ use hitbox_backend::{
 };
 use hitbox_core::{BackendLabel, CacheKey, CacheValue, Raw};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
 
 /// Simple in-memory backend for testing using DashMap.
 ///
@@ -16,6 +18,7 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct TestBackend {
     store: Arc<DashMap<CacheKey, CacheValue<Raw>>>,
+    label: BackendLabel,
 }
 
 impl TestBackend {
@@ -23,9 +26,21 @@ impl TestBackend {
     pub fn new() -> Self {
         Self {
             store: Arc::new(DashMap::new()),
+            label: BackendLabel::new_static("test"),
         }
     }
 
+    /// Set a custom label.
+    pub fn with_label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Number of entries in the backend.
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
     /// Clear all entries from the backend.
     pub fn clear(&self) {
         self.store.clear();
@@ -81,7 +96,7 @@ impl Backend for TestBackend {
     }
 
     fn label(&self) -> BackendLabel {
-        BackendLabel::new_static("test")
+        self.label.clone()
     }
 }
 
@@ -129,3 +144,125 @@ impl Backend for ErrorBackend {
 }
 
 impl CacheBackend for ErrorBackend {}
+
+/// Backend that delays every read and write before delegating to a [`TestBackend`]
+/// (for timeout testing).
+#[derive(Clone)]
+pub struct SlowBackend {
+    inner: TestBackend,
+    delay: Duration,
+}
+
+impl SlowBackend {
+    /// Create a slow backend around `inner`.
+    pub fn new(inner: TestBackend, delay: Duration) -> Self {
+        Self { inner, delay }
+    }
+}
+
+#[async_trait]
+impl Backend for SlowBackend {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.read(key).await
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.write(key, value).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.inner.remove(key).await
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.inner.value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.inner.key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.inner.compressor()
+    }
+
+    fn label(&self) -> BackendLabel {
+        BackendLabel::new_static("slow")
+    }
+}
+
+impl CacheBackend for SlowBackend {}
+
+/// Backend that fails on demand and counts the calls reaching it.
+#[derive(Clone, Default)]
+pub struct FlakyBackend {
+    inner: TestBackend,
+    failing: Arc<AtomicBool>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl FlakyBackend {
+    /// Make every following call fail (or succeed again).
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    /// Number of calls that reached this backend.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Check if a key exists in the backend.
+    pub fn has(&self, key: &CacheKey) -> bool {
+        self.inner.has(key)
+    }
+
+    fn check(&self) -> BackendResult<()> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.failing.load(Ordering::SeqCst) {
+            Err(BackendError::ConnectionError(Box::new(
+                std::io::Error::other("simulated error"),
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for FlakyBackend {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        self.check()?;
+        self.inner.read(key).await
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        self.check()?;
+        self.inner.write(key, value).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.check()?;
+        self.inner.remove(key).await
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.inner.value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.inner.key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.inner.compressor()
+    }
+
+    fn label(&self) -> BackendLabel {
+        BackendLabel::new_static("flaky")
+    }
+}
+
+impl CacheBackend for FlakyBackend {}
@@ -0,0 +1,231 @@
+//! Tests for cross-instance L1 invalidation.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::StreamExt;
+use hitbox_backend::composition::invalidation::{
+    ChannelInvalidationBus, InvalidationBus, InvalidationMessage, InvalidationStream,
+};
+use hitbox_backend::composition::policy::WriteBehindWritePolicy;
+use hitbox_backend::composition::{CompositionBackend, CompositionPolicy};
+use hitbox_backend::{Backend, BackendError, BackendResult, CacheBackend};
+use hitbox_core::{BoxContext, CacheContext, CacheKey, CacheValue, Offload};
+use smol_str::SmolStr;
+
+use crate::common::TestBackend;
+use crate::composition::nested::TestValue;
+
+#[derive(Clone, Debug)]
+struct TestOffload;
+
+impl Offload<'static> for TestOffload {
+    fn spawn<F>(&self, _kind: impl Into<SmolStr>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+fn value(data: &str) -> CacheValue<TestValue> {
+    CacheValue::new(
+        TestValue {
+            data: data.to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        None,
+    )
+}
+
+/// Lets spawned listeners subscribe and process published messages.
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(20)).await;
+}
+
+/// Two instances with their own L1 sharing an L2 and a bus.
+async fn replicas() -> (
+    CompositionBackend<TestBackend, TestBackend, TestOffload>,
+    TestBackend,
+    CompositionBackend<TestBackend, TestBackend, TestOffload>,
+    TestBackend,
+) {
+    let bus = ChannelInvalidationBus::new();
+    let l2 = TestBackend::new();
+    let (l1_a, l1_b) = (TestBackend::new(), TestBackend::new());
+    let a =
+        CompositionBackend::new(l1_a.clone(), l2.clone(), TestOffload).invalidation(bus.clone());
+    let b = CompositionBackend::new(l1_b.clone(), l2, TestOffload).invalidation(bus);
+    settle().await;
+    (a, l1_a, b, l1_b)
+}
+
+#[tokio::test]
+async fn test_overwrite_drops_other_l1() {
+    let (a, l1_a, b, l1_b) = replicas().await;
+    let key = CacheKey::from_str("invalidation", "overwrite");
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    b.set::<TestValue>(&key, &value("old"), &mut ctx)
+        .await
+        .unwrap();
+    settle().await;
+    assert!(l1_b.has(&key));
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    a.set::<TestValue>(&key, &value("new"), &mut ctx)
+        .await
+        .unwrap();
+    settle().await;
+
+    assert!(l1_a.has(&key), "publisher keeps its own entry");
+    assert!(!l1_b.has(&key), "other instance drops its entry");
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let result = b.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(result.unwrap().data().data, "new");
+}
+
+#[tokio::test]
+async fn test_remove_drops_other_l1() {
+    let (a, _, b, l1_b) = replicas().await;
+    let key = CacheKey::from_str("invalidation", "remove");
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    b.set::<TestValue>(&key, &value("gone"), &mut ctx)
+        .await
+        .unwrap();
+    settle().await;
+
+    a.remove(&key).await.unwrap();
+    settle().await;
+
+    assert!(!l1_b.has(&key));
+}
+
+#[tokio::test]
+async fn test_write_behind_publishes_after_l2_write() {
+    let bus = ChannelInvalidationBus::new();
+    let mut messages = bus.subscribe().await.unwrap();
+    let l2 = TestBackend::new();
+    let backend = CompositionBackend::new(TestBackend::new(), l2.clone(), TestOffload)
+        .invalidation(bus)
+        .with_policy(
+            CompositionPolicy::new()
+                .write(WriteBehindWritePolicy::new().flush_interval(Duration::from_secs(3600))),
+        );
+    settle().await;
+
+    let key = CacheKey::from_str("invalidation", "write-behind");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    backend
+        .set::<TestValue>(&key, &value("queued"), &mut ctx)
+        .await
+        .unwrap();
+    settle().await;
+
+    assert!(!l2.has(&key));
+    assert!(
+        tokio::time::timeout(Duration::from_millis(20), messages.next())
+            .await
+            .is_err(),
+        "nothing is published while L2 is stale"
+    );
+
+    backend.write_policy().flush().await;
+    let message = tokio::time::timeout(Duration::from_secs(1), messages.next())
+        .await
+        .expect("published after flush")
+        .unwrap();
+    assert!(l2.has(&key));
+    assert_eq!(message.key, key);
+}
+
+#[tokio::test]
+async fn test_listener_stops_when_backend_is_dropped() {
+    let bus = ChannelInvalidationBus::new();
+    let l1 = TestBackend::new();
+    let backend = CompositionBackend::new(l1.clone(), TestBackend::new(), TestOffload)
+        .invalidation(bus.clone());
+    settle().await;
+
+    let key = CacheKey::from_str("invalidation", "dropped");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    backend
+        .set::<TestValue>(&key, &value("kept"), &mut ctx)
+        .await
+        .unwrap();
+    drop(backend);
+
+    bus.publish(InvalidationMessage::new(0, key.clone()))
+        .await
+        .unwrap();
+    settle().await;
+
+    assert!(l1.has(&key), "a dropped backend no longer listens");
+}
+
+/// Fails the first subscribe and closes the second stream immediately, then
+/// delegates to a channel bus.
+#[derive(Clone)]
+struct FlakyBus {
+    inner: ChannelInvalidationBus,
+    subscribes: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl InvalidationBus for FlakyBus {
+    async fn publish(&self, message: InvalidationMessage) -> BackendResult<()> {
+        self.inner.publish(message).await
+    }
+
+    async fn subscribe(&self) -> BackendResult<InvalidationStream> {
+        match self.subscribes.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(BackendError::ConnectionError(Box::new(
+                std::io::Error::from(std::io::ErrorKind::ConnectionRefused),
+            ))),
+            1 => Ok(futures::stream::empty().boxed()),
+            _ => self.inner.subscribe().await,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_listener_resubscribes_after_failure_and_closed_stream() {
+    let bus = FlakyBus {
+        inner: ChannelInvalidationBus::new(),
+        subscribes: Arc::new(AtomicUsize::new(0)),
+    };
+    let l1 = TestBackend::new();
+    let backend = CompositionBackend::new(l1.clone(), TestBackend::new(), TestOffload)
+        .invalidation(bus.clone());
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while bus.subscribes.load(Ordering::SeqCst) < 3 {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "listener did not resubscribe"
+        );
+        settle().await;
+    }
+    settle().await;
+
+    let key = CacheKey::from_str("invalidation", "resubscribed");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    backend
+        .set::<TestValue>(&key, &value("stale"), &mut ctx)
+        .await
+        .unwrap();
+    assert!(l1.has(&key));
+
+    bus.publish(InvalidationMessage::new(0, key.clone()))
+        .await
+        .unwrap();
+    settle().await;
+
+    assert!(!l1.has(&key), "the resubscribed listener drops the key");
+}
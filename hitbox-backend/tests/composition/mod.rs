@@ -4,6 +4,8 @@ mod builder;
 mod compose_api;
 mod context_refill;
 mod error_handling;
-mod nested;
+mod invalidation;
+pub(crate) mod nested;
 mod policy;
+mod tiered;
 mod trait_objects;
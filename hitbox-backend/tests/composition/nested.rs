@@ -38,7 +38,7 @@ impl Offload<'static> for TestOffload {
     feature = "rkyv_format",
     derive(Archive, RkyvSerialize, rkyv::Deserialize)
 )]
-pub(super) struct TestValue {
+pub(crate) struct TestValue {
     pub data: String,
 }
 
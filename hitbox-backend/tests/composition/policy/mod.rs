@@ -1,5 +1,6 @@
-//! Tests for composition policies (read, write, refill).
+//! Tests for composition policies (read, write, refill, ttl).
 
 mod read;
 mod refill;
+mod ttl;
 mod write;
@@ -1,6 +1,55 @@
 //! Tests for composition RefillPolicy enum.
 
-use hitbox_backend::composition::policy::RefillPolicy;
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::Utc;
+use hitbox_backend::CacheBackend;
+use hitbox_backend::composition::policy::{FrequencyAdmission, RefillPolicy};
+use hitbox_backend::composition::{CompositionBackend, CompositionPolicy};
+use hitbox_core::{BoxContext, CacheContext, CacheKey, CacheValue, Offload};
+use smol_str::SmolStr;
+
+use crate::common::TestBackend;
+use crate::composition::nested::TestValue;
+
+#[derive(Clone, Debug)]
+struct TestOffload;
+
+impl Offload<'static> for TestOffload {
+    fn spawn<F>(&self, _kind: impl Into<SmolStr>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+fn value(data: &str) -> CacheValue<TestValue> {
+    CacheValue::new(
+        TestValue {
+            data: data.to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        None,
+    )
+}
+
+/// Writes `data` to L2 only, then reads it and refills the way CacheFuture does.
+async fn read_through<B: CacheBackend>(cache: &B, l2: &TestBackend, key: &CacheKey, data: &str) {
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    l2.set::<TestValue>(key, &value(data), &mut ctx)
+        .await
+        .unwrap();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let value = cache
+        .get::<TestValue>(key, &mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    cache.set::<TestValue>(key, &value, &mut ctx).await.unwrap();
+}
 
 #[test]
 fn test_refill_policy_default_is_never() {
@@ -19,3 +68,59 @@ fn test_refill_policy_never_variant() {
     let policy = RefillPolicy::Never;
     assert_eq!(policy, RefillPolicy::Never);
 }
+
+#[test]
+fn test_frequency_policies_compare_by_configuration() {
+    let key = CacheKey::from_str("refill", "counted");
+    let filter = FrequencyAdmission::new(2, Duration::from_secs(60));
+    filter.admit(&key);
+
+    assert_eq!(
+        RefillPolicy::Frequency(filter),
+        RefillPolicy::Frequency(FrequencyAdmission::new(2, Duration::from_secs(60)))
+    );
+    assert_ne!(
+        RefillPolicy::Frequency(FrequencyAdmission::new(2, Duration::from_secs(60))),
+        RefillPolicy::Frequency(FrequencyAdmission::new(3, Duration::from_secs(60)))
+    );
+    assert_ne!(
+        RefillPolicy::Frequency(FrequencyAdmission::new(2, Duration::from_secs(60))),
+        RefillPolicy::Frequency(FrequencyAdmission::with_capacity(
+            2,
+            Duration::from_secs(60),
+            100_000
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_frequency_refills_after_min_hits() {
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let refill = RefillPolicy::Frequency(FrequencyAdmission::new(3, Duration::from_secs(60)));
+    let cache = CompositionBackend::new(l1.clone(), l2.clone(), TestOffload)
+        .with_policy(CompositionPolicy::new().refill(refill));
+
+    let key = CacheKey::from_str("refill", "frequency");
+    read_through(&cache, &l2, &key, "hot").await;
+    assert!(!l1.has(&key), "first hit is only remembered");
+    read_through(&cache, &l2, &key, "hot").await;
+    assert!(!l1.has(&key), "second hit is below the threshold");
+    read_through(&cache, &l2, &key, "hot").await;
+    assert!(l1.has(&key), "third hit is admitted");
+}
+
+#[tokio::test]
+async fn test_max_size_refuses_large_entries() {
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let cache = CompositionBackend::new(l1.clone(), l2.clone(), TestOffload)
+        .with_policy(CompositionPolicy::new().refill(RefillPolicy::MaxSize(64)));
+
+    let small = CacheKey::from_str("refill", "small");
+    read_through(&cache, &l2, &small, "tiny").await;
+    assert!(l1.has(&small));
+
+    let large = CacheKey::from_str("refill", "large");
+    read_through(&cache, &l2, &large, &"x".repeat(1024)).await;
+    assert!(!l1.has(&large));
+    assert!(l2.has(&large));
+}
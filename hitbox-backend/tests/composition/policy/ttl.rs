@@ -0,0 +1,128 @@
+//! Tests for composition TtlPolicy.
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hitbox_backend::composition::policy::{RefillPolicy, TtlPolicy};
+use hitbox_backend::composition::{CompositionBackend, CompositionPolicy};
+use hitbox_backend::{Backend, CacheBackend};
+use hitbox_core::{BoxContext, CacheContext, CacheKey, CacheValue, Offload};
+use smol_str::SmolStr;
+
+use crate::common::TestBackend;
+use crate::composition::nested::TestValue;
+
+#[derive(Clone, Debug)]
+struct TestOffload;
+
+impl Offload<'static> for TestOffload {
+    fn spawn<F>(&self, _kind: impl Into<SmolStr>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+fn hour_value() -> CacheValue<TestValue> {
+    CacheValue::new(
+        TestValue {
+            data: "value".to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::hours(1)),
+        Some(Utc::now() + chrono::Duration::minutes(30)),
+    )
+}
+
+fn expire_of(backend: &TestBackend, key: &CacheKey) -> DateTime<Utc> {
+    backend.get_raw(key).unwrap().expire().unwrap()
+}
+
+fn within(at: DateTime<Utc>, secs: i64) -> bool {
+    at <= Utc::now() + chrono::Duration::seconds(secs)
+}
+
+#[test]
+fn test_ttl_policy_default_keeps_ttl() {
+    let policy = CompositionPolicy::new();
+    assert!(policy.ttl_policy().is_inherit());
+}
+
+#[test]
+#[should_panic(expected = "TTL ratio")]
+fn test_ttl_ratio_must_be_at_most_one() {
+    let _ = TtlPolicy::new().ratio(1.5);
+}
+
+#[tokio::test]
+async fn test_max_ttl_applies_to_l1_writes_only() {
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let cache = CompositionBackend::new(l1.clone(), l2.clone(), TestOffload)
+        .ttl(TtlPolicy::new().max(Duration::from_secs(5)));
+
+    let key = CacheKey::from_str("ttl", "write");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &hour_value(), &mut ctx)
+        .await
+        .unwrap();
+
+    assert!(within(expire_of(&l1, &key), 5));
+    let l1_stale = l1.get_raw(&key).unwrap().stale().unwrap();
+    assert!(l1_stale <= expire_of(&l1, &key));
+    assert!(!within(expire_of(&l2, &key), 3000));
+}
+
+#[tokio::test]
+async fn test_ttl_ratio_applies_to_refill() {
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let policy = CompositionPolicy::new()
+        .refill(RefillPolicy::Always)
+        .ttl(TtlPolicy::new().ratio(0.01));
+    let cache = CompositionBackend::new(l1.clone(), l2.clone(), TestOffload).with_policy(policy);
+
+    let key = CacheKey::from_str("ttl", "refill");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &hour_value(), &mut ctx)
+        .await
+        .unwrap();
+    l1.clear();
+
+    // Read from L2, then refill L1 the way CacheFuture does.
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let value = cache
+        .get::<TestValue>(&key, &mut ctx)
+        .await
+        .unwrap()
+        .unwrap();
+    cache
+        .set::<TestValue>(&key, &value, &mut ctx)
+        .await
+        .unwrap();
+
+    // 1% of an hour is 36s.
+    let expire = expire_of(&l1, &key);
+    assert!(within(expire, 36));
+    assert!(!within(expire, 30));
+}
+
+#[tokio::test]
+async fn test_max_ttl_applies_behind_trait_object() {
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let cache: Box<dyn Backend> = Box::new(
+        CompositionBackend::new(l1.clone(), l2.clone(), TestOffload)
+            .ttl(TtlPolicy::new().max(Duration::from_secs(5))),
+    );
+
+    let key = CacheKey::from_str("ttl", "dyn");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &hour_value(), &mut ctx)
+        .await
+        .unwrap();
+
+    assert!(within(expire_of(&l1, &key), 5));
+    assert!(!within(expire_of(&l2, &key), 3000));
+}
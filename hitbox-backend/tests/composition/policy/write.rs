@@ -1,13 +1,15 @@
-//! Tests for composition write policies (Sequential, OptimisticParallel).
+//! Tests for composition write policies (Sequential, OptimisticParallel, WriteBehind).
 
 use bytes::Bytes;
 use hitbox_backend::Backend;
 use hitbox_backend::composition::policy::{
     CompositionWritePolicy, OptimisticParallelWritePolicy, SequentialWritePolicy,
+    WriteBehindWritePolicy,
 };
-use hitbox_core::{CacheKey, CacheValue, Offload};
+use hitbox_core::{CacheKey, CacheValue, DisabledOffload, Offload};
 use smol_str::SmolStr;
 use std::future::Future;
+use std::time::Duration;
 
 use crate::common::{ErrorBackend, TestBackend};
 
@@ -242,3 +244,154 @@ async fn test_optimistic_parallel_both_fail() {
     // Should FAIL - both failed
     assert!(result.is_err());
 }
+
+// =============================================================================
+// WriteBehindWritePolicy Tests
+// =============================================================================
+
+async fn write_behind(
+    policy: &WriteBehindWritePolicy,
+    l1: &TestBackend,
+    l2: &TestBackend,
+    key: &CacheKey,
+    data: &'static str,
+) {
+    let (l1, l2) = (l1.clone(), l2.clone());
+    let value = CacheValue::new(Bytes::from(data), None, None);
+    let l2_value = value.clone();
+    policy
+        .execute_with(
+            key.clone(),
+            |k: CacheKey| async move { l1.write(&k, value).await },
+            |k: CacheKey| async move { l2.write(&k, l2_value).await },
+            &TestOffload,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_write_behind_flushes_l2_in_background() {
+    let policy = WriteBehindWritePolicy::new().flush_interval(Duration::from_millis(10));
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let key = CacheKey::from_str("test", "behind");
+
+    write_behind(&policy, &l1, &l2, &key, "value").await;
+    assert!(l1.has(&key));
+    assert!(!l2.has(&key));
+    assert_eq!(policy.queue_depth(), 1);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(l2.has(&key));
+    assert_eq!(policy.queue_depth(), 0);
+}
+
+#[tokio::test]
+async fn test_write_behind_coalesces_writes_to_same_key() {
+    let policy = WriteBehindWritePolicy::new().flush_interval(Duration::from_secs(3600));
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let key = CacheKey::from_str("test", "hot");
+
+    write_behind(&policy, &l1, &l2, &key, "first").await;
+    write_behind(&policy, &l1, &l2, &key, "second").await;
+    assert_eq!(policy.queue_depth(), 1);
+
+    policy.flush().await;
+    assert_eq!(policy.queue_depth(), 0);
+    assert_eq!(l2.get_raw(&key).unwrap().data(), &Bytes::from("second"));
+}
+
+#[tokio::test]
+async fn test_write_behind_drops_when_full() {
+    let policy = WriteBehindWritePolicy::new()
+        .capacity(1)
+        .flush_interval(Duration::from_secs(3600));
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+    let queued = CacheKey::from_str("test", "queued");
+    let dropped = CacheKey::from_str("test", "dropped");
+
+    write_behind(&policy, &l1, &l2, &queued, "a").await;
+    write_behind(&policy, &l1, &l2, &dropped, "b").await;
+    assert_eq!(policy.dropped(), 1);
+    assert!(l1.has(&dropped), "L1 is written even when L2 is dropped");
+
+    policy.flush().await;
+    assert!(l2.has(&queued));
+    assert!(!l2.has(&dropped));
+}
+
+#[tokio::test]
+async fn test_write_behind_flushes_with_disabled_offload() {
+    let policy = WriteBehindWritePolicy::new().flush_interval(Duration::from_millis(10));
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+
+    for i in 0..2 {
+        let key = CacheKey::from_str("test", &format!("disabled-{i}"));
+        let (l1_write, l2_write) = (l1.clone(), l2.clone());
+        let value = CacheValue::new(Bytes::from("value"), None, None);
+        let l2_value = value.clone();
+        policy
+            .execute_with(
+                key.clone(),
+                |k: CacheKey| async move { l1_write.write(&k, value).await },
+                |k: CacheKey| async move { l2_write.write(&k, l2_value).await },
+                &DisabledOffload,
+            )
+            .await
+            .unwrap();
+
+        // The flush task does not depend on the offload running it
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(l2.has(&key));
+        assert_eq!(policy.queue_depth(), 0);
+    }
+}
+
+#[tokio::test]
+async fn test_write_behind_recovers_from_panicking_flush() {
+    let policy = WriteBehindWritePolicy::new().flush_interval(Duration::from_millis(10));
+    let (l1, l2) = (TestBackend::new(), TestBackend::new());
+
+    let l1_write = l1.clone();
+    let value = CacheValue::new(Bytes::from("value"), None, None);
+    policy
+        .execute_with(
+            CacheKey::from_str("test", "panics"),
+            |k: CacheKey| async move { l1_write.write(&k, value).await },
+            |_: CacheKey| async move { panic!("L2 write panicked") },
+            &TestOffload,
+        )
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The panicked task must not keep later writes from being flushed
+    let key = CacheKey::from_str("test", "after-panic");
+    write_behind(&policy, &l1, &l2, &key, "value").await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(l2.has(&key));
+}
+
+#[tokio::test]
+async fn test_write_behind_l1_fails_skips_l2() {
+    let policy = WriteBehindWritePolicy::new().flush_interval(Duration::from_secs(3600));
+    let l2 = TestBackend::new();
+    let key = CacheKey::from_str("test", "l1-fails");
+
+    let l2_write = l2.clone();
+    let value = CacheValue::new(Bytes::from("value"), None, None);
+    let l2_value = value.clone();
+    let result = policy
+        .execute_with(
+            key.clone(),
+            |k: CacheKey| async move { ErrorBackend.write(&k, value).await },
+            |k: CacheKey| async move { l2_write.write(&k, l2_value).await },
+            &TestOffload,
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(policy.queue_depth(), 0);
+    policy.flush().await;
+    assert!(!l2.has(&key));
+}
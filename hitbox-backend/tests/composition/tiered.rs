@@ -0,0 +1,290 @@
+//! Tests for TieredBackend, the flat N-tier composition.
+
+use chrono::Utc;
+use smol_str::SmolStr;
+use std::future::Future;
+use std::time::Duration;
+
+use hitbox_backend::composition::CompositionPolicy;
+use hitbox_backend::composition::policy::{FrequencyAdmission, RefillPolicy, TtlPolicy};
+use hitbox_backend::{Backend, CacheBackend, DeleteStatus, TieredBackend};
+use hitbox_core::{BoxContext, CacheContext, CacheKey, CacheValue, Offload, ResponseSource};
+
+use super::nested::TestValue;
+use crate::common::{ErrorBackend, TestBackend};
+
+#[derive(Clone, Debug)]
+struct TestOffload;
+
+impl Offload<'static> for TestOffload {
+    fn spawn<F>(&self, _kind: impl Into<SmolStr>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+fn tiers() -> (TestBackend, TestBackend, TestBackend) {
+    (
+        TestBackend::new().with_label("memory"),
+        TestBackend::new().with_label("disk"),
+        TestBackend::new().with_label("redis"),
+    )
+}
+
+fn refill(policy: RefillPolicy) -> CompositionPolicy {
+    CompositionPolicy::new().refill(policy)
+}
+
+fn value(data: &str) -> CacheValue<TestValue> {
+    CacheValue::new(
+        TestValue {
+            data: data.to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_write_reaches_every_tier() {
+    let (l1, l2, l3) = tiers();
+    let cache = TieredBackend::new(TestOffload)
+        .tier(l1.clone())
+        .tier(l2.clone())
+        .tier(l3.clone());
+
+    let key = CacheKey::from_str("tiered", "write");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("all"), &mut ctx)
+        .await
+        .unwrap();
+
+    assert!(l1.has(&key));
+    assert!(l2.has(&key));
+    assert!(l3.has(&key));
+}
+
+#[tokio::test]
+async fn test_hit_in_last_tier_refills_all_faster_tiers() {
+    let (l1, l2, l3) = tiers();
+    let cache = TieredBackend::new(TestOffload)
+        .tier_with_policy(l1.clone(), refill(RefillPolicy::Always))
+        .tier_with_policy(l2.clone(), refill(RefillPolicy::Always))
+        .tier(l3.clone());
+
+    let key = CacheKey::from_str("tiered", "refill");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("from_redis"), &mut ctx)
+        .await
+        .unwrap();
+    l1.clear();
+    l2.clear();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let result = cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(result.unwrap().data().data, "from_redis");
+    assert_eq!(
+        ctx.source(),
+        &ResponseSource::Backend("tiered.redis".into())
+    );
+
+    assert!(l1.has(&key), "first tier should be refilled");
+    assert!(l2.has(&key), "second tier should be refilled");
+
+    // The next read is served by the fastest tier.
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(
+        ctx.source(),
+        &ResponseSource::Backend("tiered.memory".into())
+    );
+}
+
+#[tokio::test]
+async fn test_refill_skips_tiers_with_refill_never() {
+    let (l1, l2, l3) = tiers();
+    let cache = TieredBackend::new(TestOffload)
+        .tier_with_policy(l1.clone(), refill(RefillPolicy::Never))
+        .tier_with_policy(l2.clone(), refill(RefillPolicy::Always))
+        .tier(l3.clone());
+
+    let key = CacheKey::from_str("tiered", "refill_never");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("from_redis"), &mut ctx)
+        .await
+        .unwrap();
+    l1.clear();
+    l2.clear();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+
+    assert!(!l1.has(&key), "first tier has refill disabled");
+    assert!(l2.has(&key), "second tier should be refilled");
+}
+
+#[tokio::test]
+async fn test_hit_in_middle_tier_leaves_slower_tiers_alone() {
+    let (l1, l2, l3) = tiers();
+    let cache = TieredBackend::new(TestOffload)
+        .tier_with_policy(l1.clone(), refill(RefillPolicy::Always))
+        .tier_with_policy(l2.clone(), refill(RefillPolicy::Always))
+        .tier(l3.clone())
+        .label("cache");
+
+    let key = CacheKey::from_str("tiered", "middle");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("from_disk"), &mut ctx)
+        .await
+        .unwrap();
+    l1.clear();
+    l3.clear();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(ctx.source(), &ResponseSource::Backend("cache.disk".into()));
+
+    assert!(l1.has(&key));
+    assert!(!l3.has(&key));
+}
+
+#[tokio::test]
+async fn test_failing_tier_falls_through() {
+    let (_, l2, _) = tiers();
+    let cache = TieredBackend::new(TestOffload)
+        .tier(ErrorBackend)
+        .tier(l2.clone());
+
+    let key = CacheKey::from_str("tiered", "error");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("survivor"), &mut ctx)
+        .await
+        .unwrap();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let result = cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(result.unwrap().data().data, "survivor");
+}
+
+#[tokio::test]
+async fn test_delete_removes_from_every_tier() {
+    let (l1, l2, l3) = tiers();
+    let cache = TieredBackend::new(TestOffload)
+        .tier(l1.clone())
+        .tier(l2.clone())
+        .tier(l3.clone());
+
+    let key = CacheKey::from_str("tiered", "delete");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("gone"), &mut ctx)
+        .await
+        .unwrap();
+
+    let status = cache.delete(&key, &mut ctx).await.unwrap();
+    assert_eq!(status, DeleteStatus::Deleted(3));
+    assert!(!l1.has(&key) && !l2.has(&key) && !l3.has(&key));
+}
+
+#[tokio::test]
+async fn test_raw_read_refills_faster_tiers() {
+    let (l1, l2, l3) = tiers();
+    let cache: Box<dyn Backend> = Box::new(
+        TieredBackend::new(TestOffload)
+            .tier_with_policy(l1.clone(), refill(RefillPolicy::Always))
+            .tier_with_policy(l2.clone(), refill(RefillPolicy::Always))
+            .tier(l3.clone()),
+    );
+
+    let key = CacheKey::from_str("tiered", "raw");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("dyn"), &mut ctx)
+        .await
+        .unwrap();
+    l1.clear();
+    l2.clear();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let result = cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(result.unwrap().data().data, "dyn");
+    assert!(l1.has(&key));
+    assert!(l2.has(&key));
+}
+
+#[tokio::test]
+async fn test_tier_ttl_is_applied_on_writes_and_refills() {
+    let (l1, l2, l3) = tiers();
+    let short = CompositionPolicy::new()
+        .refill(RefillPolicy::Always)
+        .ttl(TtlPolicy::new().max(Duration::from_secs(5)));
+    let cache = TieredBackend::new(TestOffload)
+        .tier_with_policy(l1.clone(), short)
+        .tier(l2.clone())
+        .tier_with_policy(
+            l3.clone(),
+            CompositionPolicy::new().ttl(TtlPolicy::new().max(Duration::from_secs(600))),
+        );
+
+    let key = CacheKey::from_str("tiered", "ttl");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let value = CacheValue::new(
+        TestValue {
+            data: "ttl".to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::hours(1)),
+        None,
+    );
+    cache
+        .set::<TestValue>(&key, &value, &mut ctx)
+        .await
+        .unwrap();
+
+    let expire = |backend: &TestBackend| backend.get_raw(&key).unwrap().expire().unwrap();
+    let in_secs = |secs| Utc::now() + chrono::Duration::seconds(secs);
+    assert!(expire(&l1) <= in_secs(5));
+    assert!(expire(&l2) > in_secs(3000));
+    assert!(expire(&l3) <= in_secs(600));
+
+    // A refill from the last tier is capped again.
+    l1.clear();
+    l2.clear();
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert!(expire(&l1) <= in_secs(5));
+}
+
+#[tokio::test]
+async fn test_tier_refill_admission() {
+    let (l1, l2, l3) = tiers();
+    let frequency = RefillPolicy::Frequency(FrequencyAdmission::new(2, Duration::from_secs(60)));
+    let cache = TieredBackend::new(TestOffload)
+        .tier_with_policy(l1.clone(), refill(frequency))
+        .tier_with_policy(l2.clone(), refill(RefillPolicy::MaxSize(16)))
+        .tier(l3.clone());
+
+    let key = CacheKey::from_str("tiered", "admission");
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value("larger than sixteen bytes"), &mut ctx)
+        .await
+        .unwrap();
+    l1.clear();
+    l2.clear();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert!(!l1.has(&key), "first hit is only remembered");
+    assert!(!l2.has(&key), "entry exceeds the size limit");
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert!(l1.has(&key), "second hit is admitted");
+}
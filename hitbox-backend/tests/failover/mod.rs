@@ -0,0 +1,162 @@
+//! Tests for FailoverBackend switching between primary and secondary.
+
+use std::future::Future;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use hitbox_backend::{
+    ActiveBackend, Backend, CacheBackend, Compose, DeleteStatus, FailoverBackend,
+};
+use hitbox_core::{BoxContext, CacheContext, CacheKey, CacheValue, Offload, Raw};
+use smol_str::SmolStr;
+
+use crate::common::{FlakyBackend, TestBackend};
+use crate::composition::nested::TestValue;
+
+/// Test offload that spawns tasks with tokio::spawn
+#[derive(Clone, Debug)]
+struct TestOffload;
+
+impl Offload<'static> for TestOffload {
+    fn spawn<F>(&self, _kind: impl Into<SmolStr>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+const PROBE_INTERVAL: Duration = Duration::from_millis(50);
+
+fn value() -> CacheValue<Raw> {
+    CacheValue::new(Bytes::from_static(b"value"), None, None)
+}
+
+fn failover(
+    primary: FlakyBackend,
+    secondary: FlakyBackend,
+) -> FailoverBackend<FlakyBackend, FlakyBackend> {
+    FailoverBackend::new(primary, secondary).probe_interval(PROBE_INTERVAL)
+}
+
+#[tokio::test]
+async fn test_healthy_primary_serves_traffic() {
+    let primary = FlakyBackend::default();
+    let secondary = FlakyBackend::default();
+    let backend = failover(primary.clone(), secondary.clone());
+
+    let key = CacheKey::from_str("failover", "healthy");
+    backend.write(&key, value()).await.unwrap();
+    assert!(backend.read(&key).await.unwrap().is_some());
+
+    assert_eq!(backend.active(), ActiveBackend::Primary);
+    assert!(primary.has(&key));
+    assert_eq!(secondary.calls(), 0);
+}
+
+#[tokio::test]
+async fn test_primary_error_fails_over_to_secondary() {
+    let primary = FlakyBackend::default();
+    let secondary = FlakyBackend::default();
+    let backend = failover(primary.clone(), secondary.clone());
+    primary.set_failing(true);
+
+    let key = CacheKey::from_str("failover", "switch");
+    backend.write(&key, value()).await.unwrap();
+    assert_eq!(backend.active(), ActiveBackend::Secondary);
+    assert!(secondary.has(&key));
+
+    // Traffic stays on the secondary without touching the primary.
+    assert!(backend.read(&key).await.unwrap().is_some());
+    assert_eq!(primary.calls(), 1);
+}
+
+#[tokio::test]
+async fn test_successful_probe_switches_back() {
+    let primary = FlakyBackend::default();
+    let secondary = FlakyBackend::default();
+    let backend = failover(primary.clone(), secondary.clone());
+    primary.set_failing(true);
+
+    let key = CacheKey::from_str("failover", "recover");
+    backend.write(&key, value()).await.unwrap();
+    assert_eq!(backend.active(), ActiveBackend::Secondary);
+
+    primary.set_failing(false);
+    tokio::time::sleep(PROBE_INTERVAL * 2).await;
+
+    backend.write(&key, value()).await.unwrap();
+    assert_eq!(backend.active(), ActiveBackend::Primary);
+    assert!(primary.has(&key));
+}
+
+#[tokio::test]
+async fn test_failed_probe_stays_on_secondary() {
+    let primary = FlakyBackend::default();
+    let secondary = FlakyBackend::default();
+    let backend = failover(primary.clone(), secondary.clone());
+    primary.set_failing(true);
+
+    let key = CacheKey::from_str("failover", "still-down");
+    backend.write(&key, value()).await.unwrap();
+
+    tokio::time::sleep(PROBE_INTERVAL * 2).await;
+    assert!(backend.read(&key).await.unwrap().is_some());
+    assert_eq!(backend.active(), ActiveBackend::Secondary);
+    assert_eq!(primary.calls(), 2);
+
+    // The next probe waits for another interval.
+    assert!(backend.read(&key).await.unwrap().is_some());
+    assert_eq!(primary.calls(), 2);
+}
+
+#[tokio::test]
+async fn test_remove_reaches_both_backends() {
+    let primary = FlakyBackend::default();
+    let secondary = FlakyBackend::default();
+    let backend = failover(primary.clone(), secondary.clone());
+
+    let key = CacheKey::from_str("failover", "remove");
+    primary.write(&key, value()).await.unwrap();
+    secondary.write(&key, value()).await.unwrap();
+
+    assert_eq!(
+        backend.remove(&key).await.unwrap(),
+        DeleteStatus::Deleted(2)
+    );
+    assert!(!primary.has(&key));
+    assert!(!secondary.has(&key));
+}
+
+#[tokio::test]
+async fn test_failover_as_composition_layer() {
+    let l1 = TestBackend::new();
+    let primary = FlakyBackend::default();
+    let secondary = FlakyBackend::default();
+    let l2 = failover(primary.clone(), secondary.clone());
+    primary.set_failing(true);
+
+    let cache = l1.clone().compose(l2, TestOffload);
+
+    let key = CacheKey::from_str("failover", "composed");
+    let value = CacheValue::new(
+        TestValue {
+            data: "failover".to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        None,
+    );
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    cache
+        .set::<TestValue>(&key, &value, &mut ctx)
+        .await
+        .unwrap();
+    assert!(secondary.has(&key));
+
+    l1.clear();
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let result = cache.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert_eq!(result.unwrap().data().data, "failover");
+}
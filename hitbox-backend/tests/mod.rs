@@ -1,5 +1,9 @@
 //! Integration tests entry point.
 
+mod circuit_breaker;
 mod common;
 mod composition;
+mod failover;
 mod key_format;
+mod sharded;
+mod timeout;
@@ -0,0 +1,130 @@
+//! Tests for ShardedBackend key routing.
+
+use bytes::Bytes;
+use chrono::Utc;
+use hitbox_backend::{Backend, CacheBackend, ShardedBackend};
+use hitbox_core::{
+    BackendLabel, BoxContext, CacheContext, CacheKey, CacheValue, KeyPart, Raw, ResponseSource,
+};
+
+use crate::common::TestBackend;
+use crate::composition::nested::TestValue;
+
+const KEYS: usize = 10_000;
+
+fn shards(count: usize) -> Vec<TestBackend> {
+    (0..count)
+        .map(|i| TestBackend::new().with_label(BackendLabel::new(format!("shard-{i}"))))
+        .collect()
+}
+
+fn key(i: usize) -> CacheKey {
+    CacheKey::new("sharded", 1, vec![KeyPart::new("id", Some(i.to_string()))])
+}
+
+fn value() -> CacheValue<Raw> {
+    CacheValue::new(Bytes::from_static(b"value"), None, None)
+}
+
+/// Label of the shard owning `key`.
+fn owner(backend: &ShardedBackend<TestBackend>, key: &CacheKey) -> BackendLabel {
+    backend.shard_for(key).label()
+}
+
+#[tokio::test]
+async fn test_keys_are_spread_across_shards() {
+    let backend = ShardedBackend::new(shards(4));
+
+    for i in 0..KEYS {
+        backend.write(&key(i), value()).await.unwrap();
+    }
+
+    for shard in backend.shards() {
+        // Expect 2500 per shard; allow generous slack.
+        assert!(
+            (2000..3000).contains(&shard.len()),
+            "{} holds {} keys",
+            shard.label(),
+            shard.len()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_reads_hit_the_owning_shard() {
+    let backend = ShardedBackend::new(shards(3));
+
+    for i in 0..100 {
+        backend.write(&key(i), value()).await.unwrap();
+    }
+    for i in 0..100 {
+        let key = key(i);
+        assert!(backend.shard_for(&key).has(&key));
+        assert!(backend.read(&key).await.unwrap().is_some());
+    }
+}
+
+#[test]
+fn test_adding_a_shard_moves_about_one_nth_of_keys() {
+    let before = ShardedBackend::new(shards(4));
+    let after = ShardedBackend::new(shards(5));
+
+    let mut moved = 0;
+    for i in 0..KEYS {
+        let key = key(i);
+        let (old, new) = (owner(&before, &key), owner(&after, &key));
+        if old != new {
+            // Keys only ever move to the new shard.
+            assert_eq!(new.as_str(), "shard-4");
+            moved += 1;
+        }
+    }
+
+    // Expect 1/5 of the keys to move.
+    assert!((1600..2400).contains(&moved), "{moved} keys moved");
+}
+
+#[test]
+fn test_removing_a_shard_moves_only_its_keys() {
+    let all = shards(4);
+    let before = ShardedBackend::new(all.clone());
+    let remaining = all
+        .into_iter()
+        .filter(|shard| shard.label().as_str() != "shard-1")
+        .collect();
+    let after = ShardedBackend::new(remaining);
+
+    for i in 0..KEYS {
+        let key = key(i);
+        let old = owner(&before, &key);
+        if old.as_str() != "shard-1" {
+            assert_eq!(owner(&after, &key), old);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_hit_reports_serving_shard() {
+    let backend = ShardedBackend::new(shards(3)).label("cache");
+    let key = key(42);
+    let value = CacheValue::new(
+        TestValue {
+            data: "sharded".to_string(),
+        },
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        None,
+    );
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    backend
+        .set::<TestValue>(&key, &value, &mut ctx)
+        .await
+        .unwrap();
+
+    let mut ctx: BoxContext = CacheContext::default().boxed();
+    let result = backend.get::<TestValue>(&key, &mut ctx).await.unwrap();
+    assert!(result.is_some());
+
+    let expected = format!("cache.{}", owner(&backend, &key));
+    assert_eq!(ctx.source(), &ResponseSource::Backend(expected.into()));
+}
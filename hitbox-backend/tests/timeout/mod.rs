@@ -0,0 +1,91 @@
+//! Tests for TimeoutBackend read and write deadlines.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use hitbox_backend::{Backend, TimeoutBackend, WriteTimeoutAction};
+use hitbox_core::{CacheKey, CacheValue, Offload, Raw};
+use smol_str::SmolStr;
+
+use crate::common::{SlowBackend, TestBackend};
+
+/// Test offload that spawns tasks with tokio::spawn
+#[derive(Clone, Debug)]
+struct TestOffload;
+
+impl Offload<'static> for TestOffload {
+    fn spawn<F>(&self, _kind: impl Into<SmolStr>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        tokio::spawn(future);
+    }
+}
+
+const DELAY: Duration = Duration::from_millis(100);
+
+fn value() -> CacheValue<Raw> {
+    CacheValue::new(Bytes::from_static(b"value"), None, None)
+}
+
+#[tokio::test]
+async fn test_fast_read_passes_through() {
+    let store = TestBackend::new();
+    let key = CacheKey::from_str("timeout", "fast-read");
+    store.write(&key, value()).await.unwrap();
+
+    let backend = TimeoutBackend::new(store).read_timeout(DELAY);
+
+    let result = backend.read(&key).await.unwrap();
+    assert_eq!(result.unwrap().data(), &Bytes::from_static(b"value"));
+}
+
+#[tokio::test]
+async fn test_slow_read_is_a_miss() {
+    let store = TestBackend::new();
+    let key = CacheKey::from_str("timeout", "slow-read");
+    store.write(&key, value()).await.unwrap();
+
+    let backend =
+        TimeoutBackend::new(SlowBackend::new(store, DELAY)).read_timeout(Duration::from_millis(10));
+
+    let started = Instant::now();
+    let result = backend.read(&key).await.unwrap();
+    assert!(result.is_none());
+    assert!(started.elapsed() < DELAY);
+}
+
+#[tokio::test]
+async fn test_slow_write_is_dropped() {
+    let store = TestBackend::new();
+    let key = CacheKey::from_str("timeout", "dropped-write");
+
+    let backend = TimeoutBackend::new(SlowBackend::new(store.clone(), DELAY))
+        .write_timeout(Duration::from_millis(10));
+    assert_eq!(backend.on_write_timeout(), WriteTimeoutAction::Drop);
+
+    backend.write(&key, value()).await.unwrap();
+
+    tokio::time::sleep(DELAY * 2).await;
+    assert!(!store.has(&key));
+}
+
+#[tokio::test]
+async fn test_slow_write_is_offloaded() {
+    let store = TestBackend::new();
+    let key = CacheKey::from_str("timeout", "offloaded-write");
+
+    let backend = TimeoutBackend::new(SlowBackend::new(store.clone(), DELAY))
+        .write_timeout(Duration::from_millis(10))
+        .offload_timed_out_writes(TestOffload);
+    assert_eq!(backend.on_write_timeout(), WriteTimeoutAction::Offload);
+
+    let started = Instant::now();
+    backend.write(&key, value()).await.unwrap();
+    assert!(started.elapsed() < DELAY);
+    assert!(!store.has(&key));
+
+    tokio::time::sleep(DELAY * 2).await;
+    assert!(store.has(&key));
+}
@@ -0,0 +1,45 @@
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::ConfigError;
+
+use super::core::Backend;
+
+/// Configuration for wrapping a backend with a circuit breaker.
+///
+/// Unset thresholds fall back to the `CircuitBreakerBackend` defaults.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Wrapped backend
+    pub backend: Box<Backend>,
+    /// Number of failures within `failure_window` that opens the circuit
+    #[serde(default)]
+    pub failure_threshold: Option<NonZeroU32>,
+    /// Window in which failures are counted (e.g. `10s`)
+    #[serde(default, with = "humantime_serde")]
+    pub failure_window: Option<Duration>,
+    /// Time the circuit stays open before probing the backend (e.g. `30s`)
+    #[serde(default, with = "humantime_serde")]
+    pub open_duration: Option<Duration>,
+}
+
+impl CircuitBreakerConfig {
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_backend::CircuitBreakerBackend;
+
+        let mut backend = CircuitBreakerBackend::new(self.backend.into_backend()?);
+        if let Some(threshold) = self.failure_threshold {
+            backend = backend.failure_threshold(threshold.get());
+        }
+        if let Some(window) = self.failure_window {
+            backend = backend.failure_window(window);
+        }
+        if let Some(duration) = self.open_duration {
+            backend = backend.open_duration(duration);
+        }
+        Ok(Arc::new(backend))
+    }
+}
@@ -1,6 +1,8 @@
 use hitbox_backend::Backend as BackendTrait;
+use hitbox_backend::composition::policy::{FrequencyAdmission, RefillPolicy, TtlPolicy};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::error::ConfigError;
 
@@ -38,6 +40,78 @@ pub enum RefillPolicyConfig {
     /// Never populate L1 after L2 hit (default)
     #[default]
     Never,
+    /// Populate L1 once a key was read from L2 `hits` times within `window`
+    Frequency {
+        hits: u8,
+        #[serde(with = "humantime_serde")]
+        window: Duration,
+        /// Expected number of distinct keys per window
+        #[serde(default)]
+        capacity: Option<usize>,
+    },
+    /// Populate L1 only with entries of at most `max_bytes`
+    MaxSize { max_bytes: usize },
+}
+
+impl RefillPolicyConfig {
+    pub(crate) fn into_policy(self) -> RefillPolicy {
+        match self {
+            RefillPolicyConfig::Always => RefillPolicy::Always,
+            RefillPolicyConfig::Never => RefillPolicy::Never,
+            RefillPolicyConfig::Frequency {
+                hits,
+                window,
+                capacity: Some(capacity),
+            } => RefillPolicy::Frequency(FrequencyAdmission::with_capacity(hits, window, capacity)),
+            RefillPolicyConfig::Frequency { hits, window, .. } => {
+                RefillPolicy::Frequency(FrequencyAdmission::new(hits, window))
+            }
+            RefillPolicyConfig::MaxSize { max_bytes } => RefillPolicy::MaxSize(max_bytes),
+        }
+    }
+}
+
+/// TTL limits for entries written to the faster layer.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Default)]
+pub struct TtlConfig {
+    /// Maximum TTL, e.g. "5s"
+    #[serde(default, with = "humantime_serde")]
+    pub max: Option<Duration>,
+    /// Fraction of the remaining TTL to keep, in (0, 1]
+    #[serde(default, deserialize_with = "deserialize_finite")]
+    pub ratio: Option<f64>,
+}
+
+// Deserialization rejects a NaN `ratio`, so equality is reflexive.
+impl Eq for TtlConfig {}
+
+fn deserialize_finite<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<f64>::deserialize(deserializer)?;
+    match value {
+        Some(ratio) if !ratio.is_finite() => Err(serde::de::Error::custom(format!(
+            "TTL ratio must be a finite number, got {ratio}"
+        ))),
+        _ => Ok(value),
+    }
+}
+
+impl TtlConfig {
+    pub(crate) fn into_policy(self) -> Result<TtlPolicy, ConfigError> {
+        let mut policy = TtlPolicy::new();
+        if let Some(max) = self.max {
+            policy = policy.max(max);
+        }
+        if let Some(ratio) = self.ratio {
+            if !(ratio > 0.0 && ratio <= 1.0) {
+                return Err(ConfigError::InvalidTtlRatio(ratio));
+            }
+            policy = policy.ratio(ratio);
+        }
+        Ok(policy)
+    }
 }
 
 /// Policy configuration for composition backends.
@@ -49,6 +123,9 @@ pub struct CompositionPolicyConfig {
     pub write: WritePolicy,
     #[serde(default)]
     pub refill: RefillPolicyConfig,
+    /// TTL limits for the faster layer (default: keep the value's TTL)
+    #[serde(default)]
+    pub ttl: TtlConfig,
 }
 
 /// Configuration for composing two backends into a layered cache.
@@ -71,26 +148,25 @@ impl CompositionConfig {
         use hitbox::offload::OffloadManager;
         use hitbox_backend::composition::policy::{
             OptimisticParallelWritePolicy, ParallelReadPolicy, RaceReadPolicy, RaceWritePolicy,
-            RefillPolicy, SequentialReadPolicy, SequentialWritePolicy,
+            SequentialReadPolicy, SequentialWritePolicy,
         };
         use hitbox_backend::composition::{Compose, CompositionPolicy};
 
+        let ttl = self.policy.ttl.into_policy()?;
         let l1 = self.l1.into_backend()?;
         let l2 = self.l2.into_backend()?;
         let offload = OffloadManager::default();
         let label = self.label;
 
-        let refill = match self.policy.refill {
-            RefillPolicyConfig::Always => RefillPolicy::Always,
-            RefillPolicyConfig::Never => RefillPolicy::Never,
-        };
+        let refill = self.policy.refill.into_policy();
 
         match (self.policy.read, self.policy.write) {
             (ReadPolicy::Sequential, WritePolicy::Sequential) => {
                 let policy = CompositionPolicy::new()
                     .read(SequentialReadPolicy::new())
                     .write(SequentialWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -103,7 +179,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(SequentialReadPolicy::new())
                     .write(OptimisticParallelWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -116,7 +193,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(SequentialReadPolicy::new())
                     .write(RaceWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -129,7 +207,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(RaceReadPolicy::new())
                     .write(SequentialWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -142,7 +221,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(RaceReadPolicy::new())
                     .write(OptimisticParallelWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -155,7 +235,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(RaceReadPolicy::new())
                     .write(RaceWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -168,7 +249,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(ParallelReadPolicy::new())
                     .write(SequentialWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -181,7 +263,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(ParallelReadPolicy::new())
                     .write(OptimisticParallelWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -194,7 +277,8 @@ impl CompositionConfig {
                 let policy = CompositionPolicy::new()
                     .read(ParallelReadPolicy::new())
                     .write(RaceWritePolicy::new())
-                    .refill(refill);
+                    .refill(refill)
+                    .ttl(ttl);
                 let backend = l1.compose_with(l2, offload, policy);
                 let backend = if let Some(l) = label {
                     backend.label(l)
@@ -15,6 +15,15 @@ pub enum Compression {
         #[serde(default = "default_zstd_level")]
         level: i32,
     },
+    Lz4 {
+        #[serde(default)]
+        level: i32,
+    },
+    Snappy,
+    Brotli {
+        #[serde(default = "default_brotli_level")]
+        level: u32,
+    },
 }
 
 fn default_gzip_level() -> u32 {
@@ -25,6 +34,10 @@ fn default_zstd_level() -> i32 {
     3
 }
 
+fn default_brotli_level() -> u32 {
+    9
+}
+
 impl Compression {
     /// Convert configuration compression format to backend compressor
     pub fn to_compressor(
@@ -53,6 +66,33 @@ impl Compression {
             Compression::Zstd { .. } => Err(ConfigError::BackendNotAvailable(
                 "Zstd compression requested but 'zstd' feature is not enabled".to_string(),
             )),
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 { level } => {
+                use hitbox_backend::Lz4Compressor;
+                Ok(Arc::new(Lz4Compressor::with_level(*level)))
+            }
+            #[cfg(not(feature = "lz4"))]
+            Compression::Lz4 { .. } => Err(ConfigError::BackendNotAvailable(
+                "Lz4 compression requested but 'lz4' feature is not enabled".to_string(),
+            )),
+            #[cfg(feature = "snappy")]
+            Compression::Snappy => {
+                use hitbox_backend::SnappyCompressor;
+                Ok(Arc::new(SnappyCompressor))
+            }
+            #[cfg(not(feature = "snappy"))]
+            Compression::Snappy => Err(ConfigError::BackendNotAvailable(
+                "Snappy compression requested but 'snappy' feature is not enabled".to_string(),
+            )),
+            #[cfg(feature = "brotli")]
+            Compression::Brotli { level } => {
+                use hitbox_backend::BrotliCompressor;
+                Ok(Arc::new(BrotliCompressor::with_level(*level)))
+            }
+            #[cfg(not(feature = "brotli"))]
+            Compression::Brotli { .. } => Err(ConfigError::BackendNotAvailable(
+                "Brotli compression requested but 'brotli' feature is not enabled".to_string(),
+            )),
         }
     }
 }
@@ -4,11 +4,20 @@ use std::sync::Arc;
 
 use crate::error::ConfigError;
 
+use super::circuit_breaker::CircuitBreakerConfig;
 use super::composition::CompositionConfig;
+use super::failover::FailoverConfig;
 use super::feoxdb::FeOxDb;
+use super::filesystem::Filesystem;
+use super::memcached::Memcached;
 use super::moka::Moka;
+use super::postgres::Postgres;
 use super::redis::Redis;
 use super::serialization::BackendConfig;
+use super::sharded::ShardedConfig;
+use super::sqlite::Sqlite;
+use super::tarantool::Tarantool;
+use super::tiered::TieredConfig;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(tag = "type")]
@@ -16,7 +25,16 @@ pub enum Backend {
     Moka(BackendConfig<Moka>),
     FeOxDb(BackendConfig<FeOxDb>),
     Redis(BackendConfig<Redis>),
+    Tarantool(BackendConfig<Tarantool>),
+    Memcached(BackendConfig<Memcached>),
+    Sqlite(BackendConfig<Sqlite>),
+    Filesystem(BackendConfig<Filesystem>),
+    Postgres(BackendConfig<Postgres>),
     Composition(CompositionConfig),
+    CircuitBreaker(CircuitBreakerConfig),
+    Failover(FailoverConfig),
+    Sharded(ShardedConfig),
+    Tiered(TieredConfig),
 }
 
 impl Backend {
@@ -25,7 +43,16 @@ impl Backend {
             Backend::Moka(config) => config.into_backend(),
             Backend::FeOxDb(config) => config.into_backend(),
             Backend::Redis(config) => config.into_backend(),
+            Backend::Tarantool(config) => config.into_backend(),
+            Backend::Memcached(config) => config.into_backend(),
+            Backend::Sqlite(config) => config.into_backend(),
+            Backend::Filesystem(config) => config.into_backend(),
+            Backend::Postgres(config) => config.into_backend(),
             Backend::Composition(config) => config.into_backend(),
+            Backend::CircuitBreaker(config) => config.into_backend(),
+            Backend::Failover(config) => config.into_backend(),
+            Backend::Sharded(config) => config.into_backend(),
+            Backend::Tiered(config) => config.into_backend(),
         }
     }
 }
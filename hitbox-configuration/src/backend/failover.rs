@@ -0,0 +1,41 @@
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::ConfigError;
+
+use super::core::Backend;
+
+/// Configuration for a primary backend with a secondary used while it fails.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FailoverConfig {
+    /// Backend serving traffic while healthy
+    pub primary: Box<Backend>,
+    /// Backend serving traffic while the primary returns errors
+    pub secondary: Box<Backend>,
+    /// How often a failed primary is probed (e.g. `5s`)
+    #[serde(default, with = "humantime_serde")]
+    pub probe_interval: Option<Duration>,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl FailoverConfig {
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_backend::FailoverBackend;
+
+        let primary = self.primary.into_backend()?;
+        let secondary = self.secondary.into_backend()?;
+
+        let mut backend = FailoverBackend::new(primary, secondary);
+        if let Some(interval) = self.probe_interval {
+            backend = backend.probe_interval(interval);
+        }
+        if let Some(label) = self.label {
+            backend = backend.label(label);
+        }
+        Ok(Arc::new(backend))
+    }
+}
@@ -10,6 +10,9 @@ use super::serialization::BackendConfig;
 pub struct FeOxDb {
     #[serde(default)]
     pub path: Option<String>,
+    /// Prefix of every stored key, for services sharing one database file.
+    #[serde(default)]
+    pub namespace: Option<String>,
     /// Optional label for this backend (used in metrics/tracing).
     #[serde(default)]
     pub label: Option<String>,
@@ -33,6 +36,10 @@ impl BackendConfig<FeOxDb> {
             builder = builder.path(path);
         }
 
+        if let Some(namespace) = self.backend.namespace {
+            builder = builder.namespace(namespace);
+        }
+
         if let Some(label) = self.backend.label {
             builder = builder.label(label);
         }
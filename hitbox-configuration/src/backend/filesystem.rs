@@ -0,0 +1,68 @@
+use bytesize::ByteSize;
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::ConfigError;
+
+use super::serialization::BackendConfig;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Filesystem {
+    /// Directory holding the cache files.
+    pub path: String,
+    /// Total file size above which least recently accessed entries are evicted.
+    #[serde(default)]
+    pub max_size: Option<ByteSize>,
+    /// Pause between walks of the directory; `0s` fails to parse.
+    #[serde(
+        default,
+        serialize_with = "humantime_serde::serialize",
+        deserialize_with = "super::deserialize_sweep_interval"
+    )]
+    pub sweep_interval: Option<Duration>,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl BackendConfig<Filesystem> {
+    #[cfg(feature = "fs")]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_fs::FsBackend;
+
+        let key_format = self.key.format.to_cache_key_format();
+        let serializer = self.value.format.to_serializer();
+        let compressor = self.value.compression.to_compressor()?;
+
+        let mut builder = FsBackend::builder()
+            .path(self.backend.path)
+            .key_format(key_format)
+            .value_format(serializer)
+            .compressor(compressor);
+
+        if let Some(max_size) = self.backend.max_size {
+            builder = builder.max_size(max_size.as_u64());
+        }
+
+        if let Some(interval) = self.backend.sweep_interval {
+            builder = builder.sweep_interval(interval);
+        }
+
+        if let Some(label) = self.backend.label {
+            builder = builder.label(label);
+        }
+
+        let backend = builder
+            .build()
+            .map_err(|e| ConfigError::BackendNotAvailable(format!("Filesystem: {}", e)))?;
+
+        Ok(Arc::new(backend))
+    }
+
+    #[cfg(not(feature = "fs"))]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        Err(ConfigError::BackendNotAvailable("Filesystem".to_string()))
+    }
+}
@@ -0,0 +1,62 @@
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ConfigError;
+
+use super::serialization::BackendConfig;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Memcached {
+    /// Memcached `host:port` addresses, distributed with consistent hashing.
+    pub servers: Vec<String>,
+    /// Connections kept to each server.
+    #[serde(default)]
+    pub pool_size: Option<usize>,
+    /// Prefix of every memcached key (`{namespace}:`), for services sharing one fleet.
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl BackendConfig<Memcached> {
+    #[cfg(feature = "memcached")]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_memcached::MemcachedBackend;
+
+        let key_format = self.key.format.to_cache_key_format();
+        let serializer = self.value.format.to_serializer();
+        let compressor = self.value.compression.to_compressor()?;
+
+        let mut builder = MemcachedBackend::builder()
+            .servers(self.backend.servers)
+            .key_format(key_format)
+            .value_format(serializer)
+            .compressor(compressor);
+
+        if let Some(pool_size) = self.backend.pool_size {
+            builder = builder.pool_size(pool_size);
+        }
+
+        if let Some(namespace) = self.backend.namespace {
+            builder = builder.namespace(namespace);
+        }
+
+        if let Some(label) = self.backend.label {
+            builder = builder.label(label);
+        }
+
+        let backend = builder
+            .build()
+            .map_err(|e| ConfigError::BackendNotAvailable(format!("Memcached: {}", e)))?;
+
+        Ok(Arc::new(backend))
+    }
+
+    #[cfg(not(feature = "memcached"))]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        Err(ConfigError::BackendNotAvailable("Memcached".to_string()))
+    }
+}
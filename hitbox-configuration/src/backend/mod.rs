@@ -1,19 +1,54 @@
+mod circuit_breaker;
 mod composition;
 mod compression;
 mod core;
+mod failover;
 mod feoxdb;
+mod filesystem;
+mod memcached;
 mod moka;
+mod postgres;
 mod redis;
 mod serialization;
+mod sharded;
+mod sqlite;
+mod tarantool;
+mod tiered;
 
+pub use circuit_breaker::CircuitBreakerConfig;
 pub use composition::{
-    CompositionConfig, CompositionPolicyConfig, ReadPolicy, RefillPolicyConfig, WritePolicy,
+    CompositionConfig, CompositionPolicyConfig, ReadPolicy, RefillPolicyConfig, TtlConfig,
+    WritePolicy,
 };
 pub use compression::Compression;
 pub use core::Backend;
+pub use failover::FailoverConfig;
 pub use feoxdb::FeOxDb;
+pub use filesystem::Filesystem;
+pub use memcached::Memcached;
 pub use moka::Moka;
-pub use redis::Redis;
+pub use postgres::Postgres;
+pub use redis::{Redis, RedisSentinel, RedisTls};
 pub use serialization::{
     BackendConfig, KeyFormat, KeySerialization, ValueFormat, ValueSerialization,
 };
+pub use sharded::ShardedConfig;
+pub use sqlite::Sqlite;
+pub use tarantool::Tarantool;
+pub use tiered::{TierConfig, TieredConfig};
+
+/// Deserializes an optional humantime sweep interval, rejecting zero.
+fn deserialize_sweep_interval<'de, D>(
+    deserializer: D,
+) -> Result<Option<std::time::Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let interval = humantime_serde::deserialize::<Option<std::time::Duration>, _>(deserializer)?;
+    match interval {
+        Some(interval) if interval.is_zero() => {
+            Err(serde::de::Error::custom("sweep interval must be non-zero"))
+        }
+        _ => Ok(interval),
+    }
+}
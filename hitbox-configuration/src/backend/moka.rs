@@ -9,6 +9,9 @@ use super::serialization::BackendConfig;
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Moka {
     pub max_capacity: u64,
+    /// Namespace prepended to the prefix of every stored key.
+    #[serde(default)]
+    pub namespace: Option<String>,
     /// Optional label for this backend (used in metrics/tracing).
     #[serde(default)]
     pub label: Option<String>,
@@ -29,6 +32,10 @@ impl BackendConfig<Moka> {
             .compressor(compressor)
             .max_entries(self.backend.max_capacity);
 
+        if let Some(namespace) = self.backend.namespace {
+            builder = builder.namespace(namespace);
+        }
+
         if let Some(label) = self.backend.label {
             builder = builder.label(label);
         }
@@ -1,5 +1,6 @@
 use hitbox_backend::Backend as BackendTrait;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::error::ConfigError;
@@ -8,29 +9,98 @@ use super::serialization::BackendConfig;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Redis {
+    /// Redis URL. Not used when `sentinel` is set.
+    #[serde(default)]
     pub connection_string: String,
+    /// Connect to the master of a Sentinel deployment instead.
+    #[serde(default)]
+    pub sentinel: Option<RedisSentinel>,
+    /// TLS settings applied to every connection.
+    #[serde(default)]
+    pub tls: Option<RedisTls>,
+    /// Prefix of every Redis key (`{namespace}:`), for services sharing one Redis.
+    #[serde(default)]
+    pub namespace: Option<String>,
     /// Optional label for this backend (used in metrics/tracing).
     #[serde(default)]
     pub label: Option<String>,
 }
 
+/// Redis Sentinel deployment.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RedisSentinel {
+    /// Name of the monitored master, as configured in Sentinel.
+    pub master_name: String,
+    /// Sentinel node URLs.
+    pub sentinels: Vec<String>,
+}
+
+/// TLS settings for Redis connections. Certificate files are PEM encoded.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RedisTls {
+    /// CA certificate to trust instead of the platform roots.
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    /// Client certificate chain for mutual TLS.
+    #[serde(default)]
+    pub client_cert: Option<PathBuf>,
+    /// Private key of the client certificate.
+    #[serde(default)]
+    pub client_key: Option<PathBuf>,
+}
+
+impl Redis {
+    /// Checks that a connection target is set and TLS client settings are complete.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match &self.sentinel {
+            Some(sentinel) if sentinel.sentinels.is_empty() => {
+                return Err(ConfigError::InvalidRedisConnection(
+                    "sentinel requires at least one sentinel URL".to_string(),
+                ));
+            }
+            None if self.connection_string.is_empty() => {
+                return Err(ConfigError::InvalidRedisConnection(
+                    "either connection_string or sentinel is required".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(tls) = &self.tls
+            && tls.client_cert.is_some() != tls.client_key.is_some()
+        {
+            return Err(ConfigError::InvalidRedisConnection(
+                "tls client_cert and client_key must be set together".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 impl BackendConfig<Redis> {
     #[cfg(feature = "redis")]
     pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
         use hitbox_redis::RedisBackend;
 
+        self.backend.validate()?;
+
         let key_format = self.key.format.to_cache_key_format();
         let serializer = self.value.format.to_serializer();
         let compressor = self.value.compression.to_compressor()?;
 
         let mut builder = RedisBackend::builder()
-            .connection(hitbox_redis::ConnectionMode::single(
-                self.backend.connection_string,
-            ))
+            .connection(self.backend.connection_mode()?)
             .key_format(key_format)
             .value_format(serializer)
             .compressor(compressor);
 
+        if let Some(tls) = self.backend.tls {
+            builder = tls.apply(builder)?;
+        }
+
+        if let Some(namespace) = self.backend.namespace {
+            builder = builder.namespace(namespace);
+        }
+
         if let Some(label) = self.backend.label {
             builder = builder.label(label);
         }
@@ -47,3 +117,61 @@ impl BackendConfig<Redis> {
         Err(ConfigError::BackendNotAvailable("Redis".to_string()))
     }
 }
+
+#[cfg(feature = "redis")]
+impl Redis {
+    fn connection_mode(&self) -> Result<hitbox_redis::ConnectionMode, ConfigError> {
+        match &self.sentinel {
+            #[cfg(feature = "redis-sentinel")]
+            Some(sentinel) => Ok(hitbox_redis::ConnectionMode::sentinel(
+                sentinel.master_name.clone(),
+                sentinel.sentinels.clone(),
+            )),
+            #[cfg(not(feature = "redis-sentinel"))]
+            Some(_) => Err(ConfigError::BackendNotAvailable(
+                "Redis Sentinel".to_string(),
+            )),
+            None => Ok(hitbox_redis::ConnectionMode::single(
+                self.connection_string.clone(),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "redis")]
+impl RedisTls {
+    #[cfg(feature = "redis-tls")]
+    fn apply<S, C>(
+        self,
+        builder: hitbox_redis::RedisBackendBuilder<S, C>,
+    ) -> Result<hitbox_redis::RedisBackendBuilder<S, C>, ConfigError>
+    where
+        S: hitbox_backend::format::Format,
+        C: hitbox_backend::Compressor,
+    {
+        let read = |path: PathBuf| {
+            std::fs::read(&path).map_err(|error| ConfigError::ReadFile { path, error })
+        };
+
+        let mut tls = hitbox_redis::TlsConfig::new();
+        if let Some(path) = self.ca_cert {
+            tls = tls.ca_cert(read(path)?);
+        }
+        if let (Some(cert), Some(key)) = (self.client_cert, self.client_key) {
+            tls = tls.client_cert(read(cert)?, read(key)?);
+        }
+        Ok(builder.tls(tls))
+    }
+
+    #[cfg(not(feature = "redis-tls"))]
+    fn apply<S, C>(
+        self,
+        _builder: hitbox_redis::RedisBackendBuilder<S, C>,
+    ) -> Result<hitbox_redis::RedisBackendBuilder<S, C>, ConfigError>
+    where
+        S: hitbox_backend::format::Format,
+        C: hitbox_backend::Compressor,
+    {
+        Err(ConfigError::BackendNotAvailable("Redis TLS".to_string()))
+    }
+}
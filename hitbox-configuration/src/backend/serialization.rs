@@ -47,6 +47,12 @@ pub enum ValueSerialization {
     Bincode,
     #[cfg(feature = "rkyv_format")]
     Rkyv,
+    #[cfg(feature = "msgpack_format")]
+    MsgPack,
+    #[cfg(feature = "cbor_format")]
+    Cbor,
+    #[cfg(feature = "postcard_format")]
+    Postcard,
 }
 
 impl ValueSerialization {
@@ -59,6 +65,12 @@ impl ValueSerialization {
                 use hitbox_backend::format::RkyvFormat;
                 Arc::new(RkyvFormat::new())
             }
+            #[cfg(feature = "msgpack_format")]
+            ValueSerialization::MsgPack => Arc::new(hitbox_backend::format::MsgPackFormat),
+            #[cfg(feature = "cbor_format")]
+            ValueSerialization::Cbor => Arc::new(hitbox_backend::format::CborFormat),
+            #[cfg(feature = "postcard_format")]
+            ValueSerialization::Postcard => Arc::new(hitbox_backend::format::PostcardFormat),
         }
     }
 }
@@ -0,0 +1,41 @@
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ConfigError;
+
+use super::core::Backend;
+
+/// Configuration for spreading keys across several backends.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ShardedConfig {
+    /// Shards, identified by their labels
+    pub shards: Vec<Backend>,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl ShardedConfig {
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_backend::ShardedBackend;
+
+        if self.shards.is_empty() {
+            return Err(ConfigError::EmptyShardList);
+        }
+
+        let shards = self
+            .shards
+            .into_iter()
+            .map(Backend::into_backend)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let backend = ShardedBackend::new(shards);
+        let backend = if let Some(label) = self.label {
+            backend.label(label)
+        } else {
+            backend
+        };
+        Ok(Arc::new(backend))
+    }
+}
@@ -0,0 +1,79 @@
+use bytesize::ByteSize;
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::ConfigError;
+
+use super::serialization::BackendConfig;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Sqlite {
+    /// Database file, or a directory to create `cache.sqlite` in. In memory when unset.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Table holding cache entries, `hitbox_cache` when unset.
+    #[serde(default)]
+    pub table: Option<String>,
+    /// Database size above which least recently accessed entries are evicted.
+    #[serde(default)]
+    pub max_size: Option<ByteSize>,
+    /// Time between sweeps deleting expired rows and evicting down to `max_size`.
+    #[serde(
+        default,
+        serialize_with = "humantime_serde::serialize",
+        deserialize_with = "super::deserialize_sweep_interval"
+    )]
+    pub sweep_interval: Option<Duration>,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl BackendConfig<Sqlite> {
+    #[cfg(feature = "sqlite")]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_sqlite::SqliteBackend;
+
+        let key_format = self.key.format.to_cache_key_format();
+        let serializer = self.value.format.to_serializer();
+        let compressor = self.value.compression.to_compressor()?;
+
+        let mut builder = SqliteBackend::builder()
+            .key_format(key_format)
+            .value_format(serializer)
+            .compressor(compressor);
+
+        if let Some(path) = self.backend.path {
+            builder = builder.path(path);
+        }
+
+        if let Some(table) = self.backend.table {
+            builder = builder.table(table);
+        }
+
+        if let Some(max_size) = self.backend.max_size {
+            builder = builder.max_size(max_size.as_u64());
+        }
+
+        if let Some(interval) = self.backend.sweep_interval {
+            builder = builder.sweep_interval(interval);
+        }
+
+        if let Some(label) = self.backend.label {
+            builder = builder.label(label);
+        }
+
+        let backend = builder
+            .build()
+            .map_err(|e| ConfigError::BackendNotAvailable(format!("Sqlite: {}", e)))?;
+
+        Ok(Arc::new(backend))
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        Err(ConfigError::BackendNotAvailable("Sqlite".to_string()))
+    }
+}
@@ -0,0 +1,81 @@
+use hitbox_backend::Backend as BackendTrait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ConfigError;
+
+use super::serialization::BackendConfig;
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Tarantool {
+    /// Tarantool `host:port`.
+    pub address: String,
+    /// User name; connects as `guest` when unset.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Password for `user`; never printed by `Debug`.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Space holding cache entries, `hitbox_cache` when unset.
+    #[serde(default)]
+    pub space: Option<String>,
+    /// Create the space and Lua functions on every new connection.
+    #[serde(default)]
+    pub init_schema: bool,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl std::fmt::Debug for Tarantool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tarantool")
+            .field("address", &self.address)
+            .field("user", &self.user)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("space", &self.space)
+            .field("init_schema", &self.init_schema)
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+impl BackendConfig<Tarantool> {
+    #[cfg(feature = "tarantool")]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox_tarantool::TarantoolBackend;
+
+        let key_format = self.key.format.to_cache_key_format();
+        let serializer = self.value.format.to_serializer();
+        let compressor = self.value.compression.to_compressor()?;
+
+        let mut builder = TarantoolBackend::builder()
+            .address(self.backend.address)
+            .key_format(key_format)
+            .value_format(serializer)
+            .compressor(compressor);
+
+        if let Some(user) = self.backend.user {
+            builder = builder.credentials(user, self.backend.password.unwrap_or_default());
+        }
+
+        if let Some(space) = self.backend.space {
+            builder = builder.space(space);
+        }
+
+        if self.backend.init_schema {
+            builder = builder.init_schema();
+        }
+
+        if let Some(label) = self.backend.label {
+            builder = builder.label(label);
+        }
+
+        Ok(Arc::new(builder.build()))
+    }
+
+    #[cfg(not(feature = "tarantool"))]
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        Err(ConfigError::BackendNotAvailable("Tarantool".to_string()))
+    }
+}
@@ -0,0 +1,80 @@
+use hitbox_backend::Backend as BackendTrait;
+use hitbox_backend::composition::policy::TierPolicy;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::ConfigError;
+
+use super::composition::{CompositionPolicyConfig, ReadPolicy, WritePolicy};
+use super::core::Backend;
+
+/// A single tier of a tiered backend.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TierConfig {
+    /// Backend of this tier
+    pub backend: Backend,
+    /// Policies of this tier relative to the slower tiers
+    #[serde(default)]
+    pub policy: CompositionPolicyConfig,
+}
+
+/// Configuration for layering any number of backends, fastest first.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TieredConfig {
+    /// Tiers, fastest first
+    pub tiers: Vec<TierConfig>,
+    /// Optional label for this backend (used in metrics/tracing).
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+fn tier_policy(config: CompositionPolicyConfig) -> Result<TierPolicy, ConfigError> {
+    use hitbox_backend::composition::CompositionPolicy;
+    use hitbox_backend::composition::policy::{
+        OptimisticParallelWritePolicy, ParallelReadPolicy, RaceReadPolicy, RaceWritePolicy,
+        SequentialReadPolicy, SequentialWritePolicy, TierReadPolicy, TierWritePolicy,
+    };
+
+    let read = match config.read {
+        ReadPolicy::Sequential => TierReadPolicy::from(SequentialReadPolicy::new()),
+        ReadPolicy::Race => TierReadPolicy::from(RaceReadPolicy::new()),
+        ReadPolicy::Parallel => TierReadPolicy::from(ParallelReadPolicy::new()),
+    };
+    let write = match config.write {
+        WritePolicy::Sequential => TierWritePolicy::from(SequentialWritePolicy::new()),
+        WritePolicy::OptimisticParallel => {
+            TierWritePolicy::from(OptimisticParallelWritePolicy::new())
+        }
+        WritePolicy::Race => TierWritePolicy::from(RaceWritePolicy::new()),
+    };
+
+    Ok(CompositionPolicy::new()
+        .read(read)
+        .write(write)
+        .refill(config.refill.into_policy())
+        .ttl(config.ttl.into_policy()?))
+}
+
+impl TieredConfig {
+    pub fn into_backend(self) -> Result<Arc<dyn BackendTrait + Send + 'static>, ConfigError> {
+        use hitbox::offload::OffloadManager;
+        use hitbox_backend::TieredBackend;
+
+        if self.tiers.is_empty() {
+            return Err(ConfigError::EmptyTierList);
+        }
+
+        let mut backend = TieredBackend::new(OffloadManager::default());
+        for tier in self.tiers {
+            let policy = tier_policy(tier.policy)?;
+            backend = backend.tier_with_policy(tier.backend.into_backend()?, policy);
+        }
+
+        let backend = if let Some(label) = self.label {
+            backend.label(label)
+        } else {
+            backend
+        };
+        Ok(Arc::new(backend))
+    }
+}
@@ -46,6 +46,30 @@ pub enum ConfigError {
     /// Empty path list in 'in' operation
     #[error("Path 'in' operation requires at least one pattern")]
     EmptyPathList,
+
+    /// Empty shard list in a sharded backend
+    #[error("Sharded backend requires at least one shard")]
+    EmptyShardList,
+
+    /// Empty tier list in a tiered backend
+    #[error("Tiered backend requires at least one tier")]
+    EmptyTierList,
+
+    /// TTL ratio outside of (0, 1]
+    #[error("TTL ratio must be in (0, 1], got {0}")]
+    InvalidTtlRatio(f64),
+
+    /// Invalid Redis connection settings
+    #[error("Invalid Redis connection: {0}")]
+    InvalidRedisConnection(String),
+
+    /// File referenced by the configuration could not be read
+    #[error("Failed to read '{}': {error}", path.display())]
+    ReadFile {
+        path: std::path::PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
 }
 
 impl From<http::method::InvalidMethod> for ConfigError {
@@ -1,6 +1,11 @@
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+use hitbox_configuration::ConfigError;
 use hitbox_configuration::backend::{
-    Backend, BackendConfig, Compression, KeyFormat, KeySerialization, Moka, ReadPolicy,
-    RefillPolicyConfig, ValueFormat, ValueSerialization, WritePolicy,
+    Backend, BackendConfig, CompositionPolicyConfig, Compression, KeyFormat, KeySerialization,
+    Moka, ReadPolicy, RedisSentinel, RedisTls, RefillPolicyConfig, TtlConfig, ValueFormat,
+    ValueSerialization, WritePolicy,
 };
 
 #[test]
@@ -57,6 +62,22 @@ value:
     }
 }
 
+#[test]
+fn test_compression_deserialize() {
+    let cases = [
+        ("type: Lz4", Compression::Lz4 { level: 0 }),
+        ("{type: Lz4, level: 9}", Compression::Lz4 { level: 9 }),
+        ("type: Snappy", Compression::Snappy),
+        ("type: Brotli", Compression::Brotli { level: 9 }),
+        ("{type: Brotli, level: 4}", Compression::Brotli { level: 4 }),
+    ];
+
+    for (yaml, expected) in cases {
+        let compression: Compression = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+        assert_eq!(compression, expected);
+    }
+}
+
 #[test]
 fn test_redis_backend_deserialize() {
     let yaml = r#"
@@ -81,6 +102,274 @@ value:
     }
 }
 
+#[test]
+fn test_backend_namespace_deserialize() {
+    let yaml = r#"
+type: Redis
+connection_string: "redis://localhost:6379"
+namespace: orders
+key:
+  format: Bitcode
+value:
+  format: Json
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Redis(config) = backend else {
+        panic!("expected Redis backend");
+    };
+    assert_eq!(config.backend.namespace.as_deref(), Some("orders"));
+
+    let yaml = r#"
+type: Moka
+max_capacity: 1000
+key:
+  format: Bitcode
+value:
+  format: Json
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Moka(config) = backend else {
+        panic!("expected Moka backend");
+    };
+    assert_eq!(config.backend.namespace, None);
+}
+
+#[test]
+fn test_tarantool_backend_deserialize() {
+    let yaml = r#"
+type: Tarantool
+address: "127.0.0.1:3301"
+user: hitbox
+password: secret
+init_schema: true
+key:
+  format: Bitcode
+value:
+  format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Tarantool(config) = backend else {
+        panic!("expected Tarantool backend");
+    };
+    assert_eq!(config.backend.address, "127.0.0.1:3301");
+    assert_eq!(config.backend.user.as_deref(), Some("hitbox"));
+    assert_eq!(config.backend.password.as_deref(), Some("secret"));
+    assert_eq!(config.backend.space, None);
+    assert!(config.backend.init_schema);
+
+    let debug = format!("{:?}", config.backend);
+    assert!(!debug.contains("secret"), "password leaked: {debug}");
+    assert!(debug.contains("<redacted>"));
+}
+
+#[test]
+fn test_memcached_backend_deserialize() {
+    let yaml = r#"
+type: Memcached
+servers:
+  - "10.0.0.1:11211"
+  - "10.0.0.2:11211"
+pool_size: 8
+key:
+  format: Bitcode
+value:
+  format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Memcached(config) = backend else {
+        panic!("expected Memcached backend");
+    };
+    assert_eq!(
+        config.backend.servers,
+        vec!["10.0.0.1:11211".to_string(), "10.0.0.2:11211".to_string()]
+    );
+    assert_eq!(config.backend.pool_size, Some(8));
+    assert_eq!(config.backend.namespace, None);
+}
+
+#[test]
+fn test_sqlite_backend_deserialize() {
+    let yaml = r#"
+type: Sqlite
+path: /var/cache/app/cache.sqlite
+max_size: 512 MiB
+sweep_interval: 10s
+key:
+  format: Bitcode
+value:
+  format: Json
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Sqlite(config) = backend else {
+        panic!("expected Sqlite backend");
+    };
+    assert_eq!(
+        config.backend.path.as_deref(),
+        Some("/var/cache/app/cache.sqlite")
+    );
+    assert_eq!(
+        config.backend.max_size.map(|size| size.as_u64()),
+        Some(512 * 1024 * 1024)
+    );
+    assert_eq!(config.backend.sweep_interval, Some(Duration::from_secs(10)));
+    assert_eq!(config.backend.table, None);
+}
+
+#[test]
+fn test_filesystem_backend_deserialize() {
+    let yaml = r#"
+type: Filesystem
+path: /var/cache/app
+max_size: 10 GiB
+key:
+  format: Bitcode
+value:
+  format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Filesystem(config) = backend else {
+        panic!("expected Filesystem backend");
+    };
+    assert_eq!(config.backend.path, "/var/cache/app");
+    assert_eq!(
+        config.backend.max_size.map(|size| size.as_u64()),
+        Some(10 * 1024 * 1024 * 1024)
+    );
+    assert_eq!(config.backend.sweep_interval, None);
+}
+
+#[test]
+fn test_postgres_backend_deserialize() {
+    let yaml = r#"
+type: Postgres
+url: postgres://hitbox@db.internal/app
+table: http_cache
+init_schema: false
+sweep_interval: 1m
+key:
+  format: Bitcode
+value:
+  format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    let Backend::Postgres(config) = backend else {
+        panic!("expected Postgres backend");
+    };
+    assert_eq!(config.backend.url, "postgres://hitbox@db.internal/app");
+    assert_eq!(config.backend.table.as_deref(), Some("http_cache"));
+    assert_eq!(config.backend.init_schema, Some(false));
+    assert_eq!(config.backend.sweep_interval, Some(Duration::from_secs(60)));
+    assert_eq!(config.backend.pool_size, None);
+}
+
+#[test]
+fn test_sweep_interval_must_be_non_zero() {
+    let backends = [
+        "type: Sqlite",
+        "type: Filesystem\npath: /var/cache/app",
+        "type: Postgres\nurl: postgres://hitbox@db.internal/app",
+    ];
+    let cases = [("0s", false), ("0ms", false), ("1ms", true), ("30s", true)];
+
+    for backend in backends {
+        for (interval, valid) in cases {
+            let yaml = format!(
+                "{backend}\nsweep_interval: {interval}\nkey:\n  format: Bitcode\nvalue:\n  format: Bincode\n"
+            );
+            let result: Result<Backend, _> = serde_saphyr::from_str(&yaml);
+            assert_eq!(result.is_ok(), valid, "{backend:?} with {interval}");
+        }
+    }
+}
+
+#[test]
+fn test_redis_sentinel_tls_deserialize() {
+    let yaml = r#"
+type: Redis
+sentinel:
+  master_name: mymaster
+  sentinels:
+    - "redis://sentinel1:26379"
+    - "redis://sentinel2:26379"
+tls:
+  ca_cert: /etc/redis/ca.pem
+  client_cert: /etc/redis/client.pem
+  client_key: /etc/redis/client.key
+key:
+  format: Bitcode
+value:
+  format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Redis(config) => {
+            assert_eq!(
+                config.backend.sentinel,
+                Some(RedisSentinel {
+                    master_name: "mymaster".to_string(),
+                    sentinels: vec![
+                        "redis://sentinel1:26379".to_string(),
+                        "redis://sentinel2:26379".to_string(),
+                    ],
+                })
+            );
+            assert_eq!(
+                config.backend.tls,
+                Some(RedisTls {
+                    ca_cert: Some("/etc/redis/ca.pem".into()),
+                    client_cert: Some("/etc/redis/client.pem".into()),
+                    client_key: Some("/etc/redis/client.key".into()),
+                })
+            );
+            assert!(config.backend.validate().is_ok());
+        }
+        _ => panic!("expected Redis backend"),
+    }
+}
+
+#[test]
+fn test_redis_connection_validation() {
+    let yaml = r#"
+type: Redis
+tls:
+  client_cert: /etc/redis/client.pem
+key:
+  format: Bitcode
+value:
+  format: Bincode
+"#;
+
+    let Backend::Redis(mut config) = serde_saphyr::from_str(yaml).expect("failed to deserialize")
+    else {
+        panic!("expected Redis backend");
+    };
+
+    // Neither a URL nor Sentinel
+    assert!(matches!(
+        config.backend.validate(),
+        Err(ConfigError::InvalidRedisConnection(_))
+    ));
+
+    // Client certificate without its key
+    config.backend.connection_string = "rediss://localhost:6380".to_string();
+    assert!(matches!(
+        config.backend.validate(),
+        Err(ConfigError::InvalidRedisConnection(_))
+    ));
+
+    config.backend.tls = Some(RedisTls::default());
+    assert!(config.backend.validate().is_ok());
+}
+
 #[test]
 fn test_backend_serialize_roundtrip() {
     let backend = Backend::Moka(BackendConfig {
@@ -93,6 +382,7 @@ fn test_backend_serialize_roundtrip() {
         },
         backend: Moka {
             max_capacity: 5000,
+            namespace: None,
             label: None,
         },
     });
@@ -266,3 +556,399 @@ policy:
         _ => panic!("expected Composition backend"),
     }
 }
+
+#[test]
+fn test_circuit_breaker_backend_deserialize() {
+    let yaml = r#"
+type: CircuitBreaker
+failure_threshold: 3
+failure_window: 5s
+open_duration: 1m
+backend:
+  type: Redis
+  connection_string: "redis://localhost:6379"
+  key:
+    format: Bitcode
+  value:
+    format: Json
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::CircuitBreaker(config) => {
+            assert_eq!(config.failure_threshold, NonZeroU32::new(3));
+            assert_eq!(config.failure_window, Some(Duration::from_secs(5)));
+            assert_eq!(config.open_duration, Some(Duration::from_secs(60)));
+            assert!(matches!(config.backend.as_ref(), Backend::Redis(_)));
+        }
+        _ => panic!("expected CircuitBreaker backend"),
+    }
+}
+
+#[test]
+fn test_circuit_breaker_rejects_zero_threshold() {
+    let yaml = r#"
+type: CircuitBreaker
+failure_threshold: 0
+backend:
+  type: Moka
+  max_capacity: 1000
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+"#;
+
+    let result: Result<Backend, _> = serde_saphyr::from_str(yaml);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_circuit_breaker_backend_defaults() {
+    let yaml = r#"
+type: CircuitBreaker
+backend:
+  type: Moka
+  max_capacity: 1000
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::CircuitBreaker(config) => {
+            assert_eq!(config.failure_threshold, None);
+            assert_eq!(config.failure_window, None);
+            assert_eq!(config.open_duration, None);
+            assert!(matches!(config.backend.as_ref(), Backend::Moka(_)));
+        }
+        _ => panic!("expected CircuitBreaker backend"),
+    }
+}
+
+#[test]
+fn test_failover_backend_deserialize() {
+    let yaml = r#"
+type: Failover
+label: redis
+probe_interval: 2s
+primary:
+  type: Redis
+  connection_string: "redis://cluster-a:6379"
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+secondary:
+  type: FeOxDb
+  path: "/tmp/fallback.db"
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Failover(config) => {
+            assert_eq!(config.label, Some("redis".to_string()));
+            assert_eq!(config.probe_interval, Some(Duration::from_secs(2)));
+            assert!(matches!(config.primary.as_ref(), Backend::Redis(_)));
+            assert!(matches!(config.secondary.as_ref(), Backend::FeOxDb(_)));
+        }
+        _ => panic!("expected Failover backend"),
+    }
+}
+
+#[test]
+fn test_failover_as_composition_layer() {
+    let yaml = r#"
+type: Composition
+l1:
+  type: Moka
+  max_capacity: 1000
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+l2:
+  type: Failover
+  primary:
+    type: Redis
+    connection_string: "redis://cluster-a:6379"
+    key:
+      format: Bitcode
+    value:
+      format: Bincode
+  secondary:
+    type: Redis
+    connection_string: "redis://cluster-b:6379"
+    key:
+      format: Bitcode
+    value:
+      format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Composition(config) => match config.l2.as_ref() {
+            Backend::Failover(failover) => {
+                assert_eq!(failover.probe_interval, None);
+                assert!(matches!(failover.secondary.as_ref(), Backend::Redis(_)));
+            }
+            _ => panic!("expected Failover as L2"),
+        },
+        _ => panic!("expected Composition backend"),
+    }
+}
+
+#[test]
+fn test_sharded_backend_deserialize() {
+    let yaml = r#"
+type: Sharded
+label: redis
+shards:
+  - type: Redis
+    connection_string: "redis://redis-1:6379"
+    label: redis-1
+    key:
+      format: Bitcode
+    value:
+      format: Bincode
+  - type: Redis
+    connection_string: "redis://redis-2:6379"
+    label: redis-2
+    key:
+      format: Bitcode
+    value:
+      format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Sharded(config) => {
+            assert_eq!(config.label, Some("redis".to_string()));
+            assert_eq!(config.shards.len(), 2);
+            match &config.shards[1] {
+                Backend::Redis(redis) => {
+                    assert_eq!(redis.backend.label, Some("redis-2".to_string()));
+                }
+                _ => panic!("expected Redis shard"),
+            }
+        }
+        _ => panic!("expected Sharded backend"),
+    }
+}
+
+#[test]
+fn test_sharded_backend_without_shards() {
+    let yaml = r#"
+type: Sharded
+shards: []
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    assert!(matches!(
+        backend.into_backend(),
+        Err(ConfigError::EmptyShardList)
+    ));
+}
+
+#[test]
+fn test_tiered_backend_deserialize() {
+    let yaml = r#"
+type: Tiered
+label: cache
+tiers:
+  - backend:
+      type: Moka
+      max_capacity: 1000
+      key:
+        format: Bitcode
+      value:
+        format: Bincode
+    policy:
+      refill: Always
+  - backend:
+      type: FeOxDb
+      path: "/tmp/tier.db"
+      key:
+        format: Bitcode
+      value:
+        format: Bincode
+    policy:
+      read: Race
+      refill: Always
+  - backend:
+      type: Redis
+      connection_string: "redis://localhost:6379"
+      key:
+        format: Bitcode
+      value:
+        format: Bincode
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Tiered(config) => {
+            assert_eq!(config.label, Some("cache".to_string()));
+            assert_eq!(config.tiers.len(), 3);
+            assert!(matches!(config.tiers[0].backend, Backend::Moka(_)));
+            assert_eq!(config.tiers[0].policy.refill, RefillPolicyConfig::Always);
+            assert_eq!(config.tiers[1].policy.read, ReadPolicy::Race);
+            assert_eq!(config.tiers[2].policy, CompositionPolicyConfig::default());
+        }
+        _ => panic!("expected Tiered backend"),
+    }
+}
+
+#[test]
+fn test_tiered_backend_without_tiers() {
+    let yaml = r#"
+type: Tiered
+tiers: []
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    assert!(matches!(
+        backend.into_backend(),
+        Err(ConfigError::EmptyTierList)
+    ));
+}
+
+#[test]
+fn test_composition_policy_ttl_deserialize() {
+    let yaml = r#"
+type: Composition
+l1:
+  type: Moka
+  max_capacity: 1000
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+l2:
+  type: Redis
+  connection_string: "redis://localhost:6379"
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+policy:
+  refill: Always
+  ttl:
+    max: 5s
+    ratio: 0.1
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Composition(config) => {
+            assert_eq!(config.policy.refill, RefillPolicyConfig::Always);
+            assert_eq!(config.policy.ttl.max, Some(Duration::from_secs(5)));
+            assert_eq!(config.policy.ttl.ratio, Some(0.1));
+        }
+        _ => panic!("expected Composition backend"),
+    }
+}
+
+#[test]
+fn test_composition_policy_invalid_ttl_ratio() {
+    let yaml = r#"
+type: Composition
+l1:
+  type: Moka
+  max_capacity: 1000
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+l2:
+  type: Moka
+  max_capacity: 1000
+  key:
+    format: Bitcode
+  value:
+    format: Bincode
+policy:
+  ttl:
+    ratio: 1.5
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+    assert!(matches!(
+        backend.into_backend(),
+        Err(ConfigError::InvalidTtlRatio(_))
+    ));
+}
+
+#[test]
+fn test_composition_ttl_ratio_rejects_nan() {
+    let yaml = r#"
+max: 5s
+ratio: .nan
+"#;
+
+    let result: Result<TtlConfig, _> = serde_saphyr::from_str(yaml);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_composition_refill_admission_deserialize() {
+    let yaml = r#"
+type: Tiered
+tiers:
+  - backend:
+      type: Moka
+      max_capacity: 1000
+      key:
+        format: Bitcode
+      value:
+        format: Bincode
+    policy:
+      refill:
+        Frequency:
+          hits: 2
+          window: 1m
+  - backend:
+      type: Moka
+      max_capacity: 1000
+      key:
+        format: Bitcode
+      value:
+        format: Bincode
+    policy:
+      refill:
+        MaxSize:
+          max_bytes: 65536
+"#;
+
+    let backend: Backend = serde_saphyr::from_str(yaml).expect("failed to deserialize");
+
+    match backend {
+        Backend::Tiered(config) => {
+            assert_eq!(
+                config.tiers[0].policy.refill,
+                RefillPolicyConfig::Frequency {
+                    hits: 2,
+                    window: Duration::from_secs(60),
+                    capacity: None,
+                }
+            );
+            assert_eq!(
+                config.tiers[1].policy.refill,
+                RefillPolicyConfig::MaxSize { max_bytes: 65536 }
+            );
+        }
+        _ => panic!("expected Tiered backend"),
+    }
+}
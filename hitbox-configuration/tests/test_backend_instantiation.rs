@@ -16,6 +16,7 @@ fn test_moka_backend_instantiation() {
         },
         backend: Moka {
             max_capacity: 1000,
+            namespace: None,
             label: None,
         },
     });
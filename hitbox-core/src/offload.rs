@@ -16,6 +16,8 @@ use std::future::Future;
 
 use smol_str::SmolStr;
 
+use crate::CacheKey;
+
 /// Trait for spawning background tasks.
 ///
 /// This trait allows components like `CacheFuture` and `CompositionBackend`
@@ -69,6 +71,19 @@ pub trait Offload<'a>: Send + Sync + Clone {
     fn spawn<F>(&self, kind: impl Into<SmolStr>, future: F)
     where
         F: Future<Output = ()> + Send + 'a;
+
+    /// Spawn a future doing background work for a specific cache key.
+    ///
+    /// Implementations may use the key to route the task, e.g. into a pool
+    /// selected by the key prefix. The default implementation ignores the key
+    /// and delegates to [`spawn`](Self::spawn).
+    fn spawn_for_key<F>(&self, kind: impl Into<SmolStr>, key: &CacheKey, future: F)
+    where
+        F: Future<Output = ()> + Send + 'a,
+    {
+        let _ = key;
+        self.spawn(kind, future);
+    }
 }
 
 /// A disabled offload implementation that discards all spawned tasks.
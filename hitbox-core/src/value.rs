@@ -138,14 +138,15 @@ impl<T> CacheValue<T> {
     ///
     /// Returns `Some(Duration)` if there's a valid expire time in the future,
     /// or `None` if there's no expire time or it's already expired.
+    ///
+    /// The TTL is not rounded, so sub-second TTLs are preserved.
     pub fn ttl(&self) -> Option<Duration> {
         self.expire.and_then(|expire| {
-            let duration = expire.signed_duration_since(Utc::now());
-            if duration.num_seconds() > 0 {
-                Some(Duration::from_secs(duration.num_seconds() as u64))
-            } else {
-                None
-            }
+            expire
+                .signed_duration_since(Utc::now())
+                .to_std()
+                .ok()
+                .filter(|duration| !duration.is_zero())
         })
     }
 }
@@ -212,3 +213,26 @@ impl CacheValue<Raw> {
         fixed_overhead + content
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_keeps_sub_second_precision() {
+        let value = CacheValue::new(
+            (),
+            Some(Utc::now() + chrono::Duration::milliseconds(500)),
+            None,
+        );
+        let ttl = value.ttl().unwrap();
+        assert!(ttl > Duration::from_millis(400) && ttl <= Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_ttl_of_expired_value_is_none() {
+        let value = CacheValue::new((), Some(Utc::now() - chrono::Duration::seconds(1)), None);
+        assert_eq!(value.ttl(), None);
+        assert_eq!(CacheValue::new((), None, None).ttl(), None);
+    }
+}
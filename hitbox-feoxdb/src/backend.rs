@@ -76,6 +76,7 @@ where
 {
     store: Arc<FeoxStore>,
     key_format: CacheKeyFormat,
+    namespace: Option<String>,
     serializer: S,
     compressor: C,
     label: BackendLabel,
@@ -96,6 +97,16 @@ where
     pub fn flush(&self) {
         self.store.flush();
     }
+
+    /// Encodes `key` and prepends the namespace, if any.
+    fn storage_key(&self, key: &CacheKey) -> BackendResult<Vec<u8>> {
+        let key = encode_to_vec(key, bincode_config())
+            .map_err(|e| BackendError::InternalError(Box::new(e)))?;
+        Ok(match &self.namespace {
+            Some(namespace) => [namespace.as_bytes(), b":", &key].concat(),
+            None => key,
+        })
+    }
 }
 
 impl FeOxDbBackend<JsonFormat, PassthroughCompressor> {
@@ -120,6 +131,7 @@ impl FeOxDbBackend<JsonFormat, PassthroughCompressor> {
         Ok(Self {
             store: Arc::new(store),
             key_format: CacheKeyFormat::Bitcode,
+            namespace: None,
             serializer: JsonFormat,
             compressor: PassthroughCompressor,
             label: BackendLabel::new_static("feoxdb"),
@@ -150,6 +162,7 @@ where
     max_file_size: Option<u64>,
     max_memory: Option<usize>,
     key_format: CacheKeyFormat,
+    namespace: Option<String>,
     serializer: S,
     compressor: C,
     label: BackendLabel,
@@ -162,6 +175,7 @@ impl Default for FeOxDbBackendBuilder<JsonFormat, PassthroughCompressor> {
             max_file_size: None,
             max_memory: None,
             key_format: CacheKeyFormat::Bitcode,
+            namespace: None,
             serializer: JsonFormat,
             compressor: PassthroughCompressor,
             label: BackendLabel::new_static("feoxdb"),
@@ -214,6 +228,14 @@ where
         self
     }
 
+    /// Prefixes every stored key with `{namespace}:`.
+    ///
+    /// Keeps services sharing one database file from overwriting each other.
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
     /// Identifies this backend in multi-tier setups and metrics.
     pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
         self.label = label.into();
@@ -233,6 +255,7 @@ where
             max_file_size: self.max_file_size,
             max_memory: self.max_memory,
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer,
             compressor: self.compressor,
             label: self.label,
@@ -253,6 +276,7 @@ where
             max_file_size: self.max_file_size,
             max_memory: self.max_memory,
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor,
             label: self.label,
@@ -286,6 +310,7 @@ where
         Ok(FeOxDbBackend {
             store: Arc::new(store),
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor: self.compressor,
             label: self.label,
@@ -302,8 +327,7 @@ where
     async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
         let store = self.store.clone();
 
-        let key_bytes = encode_to_vec(key, bincode_config())
-            .map_err(|e| BackendError::InternalError(Box::new(e)))?;
+        let key_bytes = self.storage_key(key)?;
 
         tokio::task::spawn_blocking(move || match store.get(&key_bytes) {
             Ok(encoded) => {
@@ -331,8 +355,7 @@ where
     async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
         let store = self.store.clone();
 
-        let key_bytes = encode_to_vec(key, bincode_config())
-            .map_err(|e| BackendError::InternalError(Box::new(e)))?;
+        let key_bytes = self.storage_key(key)?;
 
         // Compute TTL from value.ttl() (derived from value.expire)
         let ttl = value.ttl();
@@ -342,7 +365,8 @@ where
             .map_err(|e| BackendError::InternalError(Box::new(e)))?;
 
         tokio::task::spawn_blocking(move || {
-            ttl.map(|ttl_duration| ttl_duration.as_secs())
+            // FeOxDb expires in whole seconds: round up so sub-second TTLs still expire
+            ttl.map(|ttl_duration| ttl_duration.as_millis().div_ceil(1000) as u64)
                 .map(|ttl_secs| store.insert_with_ttl(&key_bytes, &value_bytes, ttl_secs))
                 .unwrap_or_else(|| store.insert(&key_bytes, &value_bytes))
                 .map_err(|e| BackendError::InternalError(Box::new(e)))?;
@@ -355,8 +379,7 @@ where
     async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
         let store = self.store.clone();
 
-        let key_bytes = encode_to_vec(key, bincode_config())
-            .map_err(|e| BackendError::InternalError(Box::new(e)))?;
+        let key_bytes = self.storage_key(key)?;
 
         tokio::task::spawn_blocking(move || {
             let exists = store.contains_key(&key_bytes);
@@ -527,6 +550,32 @@ mod tests {
         assert_eq!(result.unwrap().data().as_ref(), b"shared-value");
     }
 
+    #[tokio::test]
+    async fn test_namespaces_share_store_without_collisions() {
+        let orders = FeOxDbBackend::builder()
+            .namespace("orders")
+            .build()
+            .unwrap();
+        let users = FeOxDbBackend {
+            namespace: Some("users".to_string()),
+            ..orders.clone()
+        };
+
+        let key = CacheKey::from_str("id", "1");
+        let value = |data: &'static [u8]| CacheValue::new(Bytes::from_static(data), None, None);
+
+        orders.write(&key, value(b"order")).await.unwrap();
+        users.write(&key, value(b"user")).await.unwrap();
+
+        let order = orders.read(&key).await.unwrap().unwrap();
+        assert_eq!(order.data().as_ref(), b"order");
+
+        users.remove(&key).await.unwrap();
+        assert!(users.read(&key).await.unwrap().is_none());
+        assert!(orders.read(&key).await.unwrap().is_some());
+        assert!(orders.storage_key(&key).unwrap().starts_with(b"orders:"));
+    }
+
     #[tokio::test]
     async fn test_per_key_ttl() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,610 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hitbox_backend::format::{BincodeFormat, Format};
+use hitbox_backend::{
+    Backend, BackendError, BackendResult, CacheKeyFormat, Compressor, DeleteStatus,
+    PassthroughCompressor,
+};
+use hitbox_core::{BackendLabel, CacheKey, CacheValue, Raw};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::FsError;
+use crate::store::Store;
+use crate::sweeper::Sweeper;
+
+/// Cache backend storing each entry as a file in a local directory.
+///
+/// Use this for large responses that should survive restarts without
+/// running a database. Files are sharded into two directory levels by the
+/// SHA-1 of the serialized key (`ab/cd/abcd…`), so no directory grows too
+/// large. Each file starts with a small header holding the expiry
+/// timestamps and the key, followed by the data.
+///
+/// Writes go to a temporary file in the same directory and are renamed into
+/// place, so readers never see partial entries. Reads memory-map files
+/// above [`mmap_threshold`](FsBackendBuilder::mmap_threshold) instead of
+/// copying them, and [`read_stream`](Self::read_stream) hands out the open
+/// file for responses that should not be buffered at all.
+///
+/// A background thread deletes expired entries and, with
+/// [`max_size`](FsBackendBuilder::max_size), evicts the least recently
+/// accessed files until the directory fits. Access time is tracked through
+/// the file modification time.
+///
+/// ```no_run
+/// use hitbox_fs::FsBackend;
+///
+/// let backend = FsBackend::builder()
+///     .path("/var/cache/myapp")
+///     .max_size(10 * 1024 * 1024 * 1024) // 10 GB
+///     .build()?;
+/// # Ok::<(), hitbox_fs::FsError>(())
+/// ```
+///
+/// Clones share the store and its sweeper thread, which exits once the last
+/// clone is dropped. Separate backends may open the same directory, each
+/// sweeping it on its own thread.
+#[derive(Clone)]
+pub struct FsBackend<S = BincodeFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    store: Arc<Store>,
+    max_size: Option<u64>,
+    _sweeper: Arc<Sweeper>,
+    key_format: CacheKeyFormat,
+    serializer: S,
+    compressor: C,
+    label: BackendLabel,
+}
+
+impl<S, C> FsBackend<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Opens an entry for streaming instead of reading it into memory.
+    ///
+    /// The reader yields the stored bytes as written by the backend, that
+    /// is serialized and compressed with the configured format and
+    /// compressor. Returns `None` for missing and expired entries.
+    pub async fn read_stream(&self, key: &CacheKey) -> BackendResult<Option<EntryReader>> {
+        let key = self.key_format.serialize(key).map_err(FsError::from)?;
+        let entry = self
+            .blocking(move |store| store.open(&key, Utc::now()))
+            .await?;
+        Ok(entry.map(|entry| EntryReader {
+            expire: entry.header.expire,
+            stale: entry.header.stale,
+            len: entry.len,
+            file: tokio::fs::File::from_std(entry.file),
+        }))
+    }
+
+    /// Runs a sweep now instead of waiting for the background thread.
+    ///
+    /// Deletes expired entries, then evicts least recently accessed entries
+    /// while the directory exceeds `max_size`. Returns the number of deleted
+    /// entries.
+    pub async fn sweep(&self) -> BackendResult<usize> {
+        let max_size = self.max_size;
+        self.blocking(move |store| store.sweep(Utc::now(), max_size))
+            .await
+    }
+
+    /// Total size of the entry files in bytes.
+    pub async fn size(&self) -> BackendResult<u64> {
+        self.blocking(|store| store.size()).await
+    }
+
+    /// Runs `f` on the blocking thread pool.
+    async fn blocking<T, F>(&self, f: F) -> BackendResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Store) -> Result<T, FsError> + Send + 'static,
+    {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || f(&store))
+            .await
+            .map_err(|e| BackendError::InternalError(Box::new(e)))?
+            .map_err(BackendError::from)
+    }
+}
+
+impl FsBackend<BincodeFormat, PassthroughCompressor> {
+    /// Starts building a new backend.
+    pub fn builder() -> FsBackendBuilder<BincodeFormat, PassthroughCompressor> {
+        FsBackendBuilder::default()
+    }
+}
+
+/// Streaming reader over the data of a cache entry.
+///
+/// Returned by [`FsBackend::read_stream`]. The file stays readable even if
+/// the entry is replaced or evicted while streaming.
+#[derive(Debug)]
+pub struct EntryReader {
+    expire: Option<DateTime<Utc>>,
+    stale: Option<DateTime<Utc>>,
+    len: u64,
+    file: tokio::fs::File,
+}
+
+impl EntryReader {
+    /// When the entry expires.
+    pub fn expire(&self) -> Option<DateTime<Utc>> {
+        self.expire
+    }
+
+    /// When the entry becomes stale.
+    pub fn stale(&self) -> Option<DateTime<Utc>> {
+        self.stale
+    }
+
+    /// Number of data bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the entry holds no data.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The underlying file, positioned at the first data byte.
+    pub fn into_file(self) -> tokio::fs::File {
+        self.file
+    }
+}
+
+impl AsyncRead for EntryReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.file).poll_read(cx, buf)
+    }
+}
+
+/// Builder for [`FsBackend`].
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use hitbox_fs::FsBackend;
+///
+/// let backend = FsBackend::builder()
+///     .path("/var/cache/myapp")
+///     .max_size(512 * 1024 * 1024)  // 512 MB
+///     .sweep_interval(Duration::from_secs(10))
+///     .mmap_threshold(1024 * 1024)
+///     .build()?;
+/// # Ok::<(), hitbox_fs::FsError>(())
+/// ```
+pub struct FsBackendBuilder<S = BincodeFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    path: Option<PathBuf>,
+    max_size: Option<u64>,
+    sweep_interval: Duration,
+    mmap_threshold: u64,
+    key_format: CacheKeyFormat,
+    serializer: S,
+    compressor: C,
+    label: BackendLabel,
+}
+
+impl Default for FsBackendBuilder<BincodeFormat, PassthroughCompressor> {
+    fn default() -> Self {
+        Self {
+            path: None,
+            max_size: None,
+            sweep_interval: Duration::from_secs(60),
+            mmap_threshold: 64 * 1024,
+            key_format: CacheKeyFormat::Bitcode,
+            serializer: BincodeFormat,
+            compressor: PassthroughCompressor,
+            label: BackendLabel::new_static("fs"),
+        }
+    }
+}
+
+impl<S, C> FsBackendBuilder<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Directory holding the cache files, created if needed. Required.
+    ///
+    /// The directory should be dedicated to the cache: the sweeper deletes
+    /// anything that looks like an entry file under it.
+    pub fn path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Caps the total size of entry files, evicting least recently
+    /// accessed entries.
+    ///
+    /// Enforced by the sweeper, so the directory can exceed the limit until
+    /// the next sweep. Access times are tracked with one-second resolution.
+    ///
+    /// Default: unbounded
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// How often the sweeper deletes expired entries and enforces
+    /// [`max_size`](Self::max_size).
+    ///
+    /// Each sweep walks the whole directory, so keep this well above the
+    /// time it takes to list it; the sweeper logs a warning when a walk
+    /// outlasts the interval. Zero fails [`build`](Self::build).
+    ///
+    /// Default: 60 seconds
+    pub fn sweep_interval(mut self, interval: Duration) -> Self {
+        self.sweep_interval = interval;
+        self
+    }
+
+    /// Files of at least this many bytes are memory-mapped on read instead
+    /// of copied into memory.
+    ///
+    /// Mapping has a fixed setup cost, so small files are cheaper to read.
+    ///
+    /// Default: 64 KiB
+    pub fn mmap_threshold(mut self, bytes: u64) -> Self {
+        self.mmap_threshold = bytes;
+        self
+    }
+
+    /// Cache key serialization format. Rarely needs changing.
+    pub fn key_format(mut self, format: CacheKeyFormat) -> Self {
+        self.key_format = format;
+        self
+    }
+
+    /// Identifies this backend in multi-tier setups and metrics.
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Value serialization format.
+    pub fn value_format<NewS>(self, serializer: NewS) -> FsBackendBuilder<NewS, C>
+    where
+        NewS: Format,
+    {
+        FsBackendBuilder {
+            path: self.path,
+            max_size: self.max_size,
+            sweep_interval: self.sweep_interval,
+            mmap_threshold: self.mmap_threshold,
+            key_format: self.key_format,
+            serializer,
+            compressor: self.compressor,
+            label: self.label,
+        }
+    }
+
+    /// Compression for cached values.
+    pub fn compressor<NewC>(self, compressor: NewC) -> FsBackendBuilder<S, NewC>
+    where
+        NewC: Compressor,
+    {
+        FsBackendBuilder {
+            path: self.path,
+            max_size: self.max_size,
+            sweep_interval: self.sweep_interval,
+            mmap_threshold: self.mmap_threshold,
+            key_format: self.key_format,
+            serializer: self.serializer,
+            compressor,
+            label: self.label,
+        }
+    }
+
+    /// Creates the directory and starts the sweeper thread.
+    ///
+    /// Fails if no path is set, the sweep interval is zero or the directory
+    /// can't be created.
+    pub fn build(self) -> Result<FsBackend<S, C>, FsError> {
+        let path = self
+            .path
+            .ok_or_else(|| FsError::InvalidConfig("path is required".to_string()))?;
+        if self.sweep_interval.is_zero() {
+            return Err(FsError::InvalidConfig(
+                "sweep interval must be non-zero".to_string(),
+            ));
+        }
+        let store = Arc::new(Store::new(path, self.mmap_threshold)?);
+        let sweeper = Sweeper::spawn(store.clone(), self.sweep_interval, self.max_size)?;
+
+        Ok(FsBackend {
+            store,
+            max_size: self.max_size,
+            _sweeper: Arc::new(sweeper),
+            key_format: self.key_format,
+            serializer: self.serializer,
+            compressor: self.compressor,
+            label: self.label,
+        })
+    }
+}
+
+#[async_trait]
+impl<S, C> Backend for FsBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let key = self.key_format.serialize(key).map_err(FsError::from)?;
+        self.blocking(move |store| store.read(&key, Utc::now()))
+            .await
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let key = self.key_format.serialize(key).map_err(FsError::from)?;
+        self.blocking(move |store| store.write(&key, &value)).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        let key = self.key_format.serialize(key).map_err(FsError::from)?;
+        match self.blocking(move |store| store.remove(&key)).await? {
+            true => Ok(DeleteStatus::Deleted(1)),
+            false => Ok(DeleteStatus::Missing),
+        }
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        &self.serializer
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        &self.key_format
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        &self.compressor
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+}
+
+// Explicit CacheBackend implementation using default trait methods
+impl<S, C> hitbox_backend::CacheBackend for FsBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use chrono::SubsecRound;
+    use std::fs::File;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+    use tokio::io::AsyncReadExt;
+
+    fn backend(dir: &TempDir) -> FsBackend {
+        FsBackend::builder().path(dir.path()).build().unwrap()
+    }
+
+    fn value(data: impl Into<Bytes>) -> CacheValue<Raw> {
+        CacheValue::new(
+            data.into(),
+            Some(Utc::now() + chrono::Duration::hours(1)),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = backend(&temp_dir);
+        let key = CacheKey::from_str("test-key", "1");
+        let expire = Utc::now().trunc_subsecs(3) + chrono::Duration::hours(1);
+        let stale = Utc::now().trunc_subsecs(3) + chrono::Duration::minutes(30);
+
+        backend
+            .write(
+                &key,
+                CacheValue::new(Bytes::from_static(b"test-value"), Some(expire), Some(stale)),
+            )
+            .await
+            .unwrap();
+
+        let result = backend.read(&key).await.unwrap().unwrap();
+        assert_eq!(result.data().as_ref(), b"test-value");
+        assert_eq!(result.expire(), Some(expire));
+        assert_eq!(result.stale(), Some(stale));
+    }
+
+    #[tokio::test]
+    async fn test_read_memory_mapped() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = FsBackend::builder()
+            .path(temp_dir.path())
+            .mmap_threshold(1024)
+            .build()
+            .unwrap();
+        let key = CacheKey::from_str("large-key", "1");
+        let data: Vec<u8> = (0..64 * 1024).map(|i| i as u8).collect();
+
+        backend.write(&key, value(data.clone())).await.unwrap();
+        let result = backend.read(&key).await.unwrap().unwrap();
+        assert_eq!(result.data().as_ref(), data.as_slice());
+    }
+
+    #[tokio::test]
+    async fn test_read_stream() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = backend(&temp_dir);
+        let key = CacheKey::from_str("stream-key", "1");
+
+        assert!(backend.read_stream(&key).await.unwrap().is_none());
+        backend.write(&key, value("streamed")).await.unwrap();
+
+        let mut reader = backend.read_stream(&key).await.unwrap().unwrap();
+        assert_eq!(reader.len(), 8);
+        assert!(reader.expire().is_some());
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await.unwrap();
+        assert_eq!(data, b"streamed");
+    }
+
+    #[tokio::test]
+    async fn test_overwrite_and_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = backend(&temp_dir);
+        let key = CacheKey::from_str("delete-key", "1");
+
+        assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+        backend.write(&key, value("first")).await.unwrap();
+        backend.write(&key, value("second")).await.unwrap();
+        let result = backend.read(&key).await.unwrap().unwrap();
+        assert_eq!(result.data().as_ref(), b"second");
+
+        assert_eq!(
+            backend.remove(&key).await.unwrap(),
+            DeleteStatus::Deleted(1)
+        );
+        assert!(backend.read(&key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_not_returned_and_swept() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = backend(&temp_dir);
+        let key = CacheKey::from_str("expired-key", "1");
+        let expired = CacheValue::new(
+            Bytes::from_static(b"expired"),
+            Some(Utc::now() - chrono::Duration::seconds(10)),
+            None,
+        );
+
+        backend.write(&key, expired).await.unwrap();
+        backend
+            .write(&CacheKey::from_str("live-key", "1"), value("live"))
+            .await
+            .unwrap();
+        assert!(backend.read(&key).await.unwrap().is_none());
+        assert!(backend.read_stream(&key).await.unwrap().is_none());
+
+        assert_eq!(backend.sweep().await.unwrap(), 1);
+        assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+    }
+
+    #[tokio::test]
+    async fn test_max_size_evicts_least_recently_accessed() {
+        let temp_dir = TempDir::new().unwrap();
+        let max_size = 256 * 1024;
+        let backend = FsBackend::builder()
+            .path(temp_dir.path())
+            .max_size(max_size)
+            .build()
+            .unwrap();
+        let chunk = Bytes::from(vec![0u8; 32 * 1024]);
+        let key = |i: usize| CacheKey::from_str("chunk", &i.to_string());
+        let path = |i: usize| {
+            backend
+                .store
+                .path(&backend.key_format.serialize(&key(i)).unwrap())
+        };
+
+        for i in 0..16 {
+            backend.write(&key(i), value(chunk.clone())).await.unwrap();
+            // Writes within the same second would tie on modification time
+            let modified = SystemTime::now() - Duration::from_secs(100 - i as u64);
+            File::options()
+                .write(true)
+                .open(path(i))
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+        // Oldest write, most recent access
+        backend.read(&key(0)).await.unwrap().unwrap();
+        assert!(backend.size().await.unwrap() > max_size);
+
+        assert!(backend.sweep().await.unwrap() > 0);
+        assert!(backend.size().await.unwrap() <= max_size);
+        assert!(backend.read(&key(0)).await.unwrap().is_some());
+        assert!(backend.read(&key(1)).await.unwrap().is_none());
+        assert!(backend.read(&key(15)).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_entry_is_deleted_as_miss() {
+        let temp_dir = TempDir::new().unwrap();
+        let backend = backend(&temp_dir);
+        let key = CacheKey::from_str("corrupt-key", "1");
+        let path = backend
+            .store
+            .path(&backend.key_format.serialize(&key).unwrap());
+
+        backend.write(&key, value("value")).await.unwrap();
+        std::fs::write(&path, b"garbage").unwrap();
+        assert!(backend.read(&key).await.unwrap().is_none());
+        assert!(!path.exists());
+
+        backend.write(&key, value("value")).await.unwrap();
+        std::fs::write(&path, b"garbage").unwrap();
+        assert!(backend.read_stream(&key).await.unwrap().is_none());
+        assert!(!path.exists());
+
+        backend.write(&key, value("value")).await.unwrap();
+        std::fs::write(&path, b"garbage").unwrap();
+        assert_eq!(backend.sweep().await.unwrap(), 1);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_persists_across_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = CacheKey::from_str("persist-key", "1");
+
+        backend(&temp_dir)
+            .write(&key, value("persist-value"))
+            .await
+            .unwrap();
+
+        let result = backend(&temp_dir).read(&key).await.unwrap().unwrap();
+        assert_eq!(result.data().as_ref(), b"persist-value");
+    }
+
+    #[test]
+    fn test_path_required() {
+        let result = FsBackend::builder().build();
+        assert!(matches!(result, Err(FsError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_zero_sweep_interval() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = FsBackend::builder()
+            .path(temp_dir.path())
+            .sweep_interval(Duration::ZERO)
+            .build();
+        assert!(matches!(result, Err(FsError::InvalidConfig(_))));
+    }
+}
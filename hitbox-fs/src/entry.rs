@@ -0,0 +1,137 @@
+//! Layout of a cache entry file.
+//!
+//! ```text
+//! magic "HBX" | version u8 | flags u8 | key length u32 LE
+//! [stale i64 LE] [expire i64 LE]   Unix milliseconds, present per flag
+//! key bytes
+//! data
+//! ```
+//!
+//! The serialized key is stored to detect hash collisions between keys
+//! sharing a file name.
+
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+use hitbox_core::{CacheValue, Raw};
+
+use crate::FsError;
+
+const MAGIC: &[u8; 3] = b"HBX";
+const VERSION: u8 = 1;
+const HAS_STALE: u8 = 0b01;
+const HAS_EXPIRE: u8 = 0b10;
+
+/// Length of the header up to the key.
+const FIXED_LEN: usize = MAGIC.len() + 2 + 4;
+
+/// Enough bytes to decode the timestamps of any entry.
+pub(crate) const MAX_META_LEN: usize = FIXED_LEN + 16;
+
+/// Decoded header of an entry file.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Header {
+    pub stale: Option<DateTime<Utc>>,
+    pub expire: Option<DateTime<Utc>>,
+    /// Position of the key in the file.
+    pub key: Range<usize>,
+}
+
+impl Header {
+    /// Position of the first data byte.
+    pub(crate) fn data_start(&self) -> usize {
+        self.key.end
+    }
+
+    /// Reads the header from the start of a file.
+    ///
+    /// `bytes` must hold the timestamps but may end before the key.
+    pub(crate) fn decode(bytes: &[u8]) -> Result<Self, FsError> {
+        let fixed = bytes
+            .get(..FIXED_LEN)
+            .ok_or(FsError::Corrupt("file shorter than its header"))?;
+        if &fixed[..3] != MAGIC || fixed[3] != VERSION {
+            return Err(FsError::Corrupt("not a cache entry file"));
+        }
+        let flags = fixed[4];
+        let key_len = u32::from_le_bytes(fixed[5..9].try_into().expect("4 bytes")) as usize;
+
+        let mut offset = FIXED_LEN;
+        let mut timestamp = |flag: u8| -> Result<_, FsError> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+            let millis = bytes
+                .get(offset..offset + 8)
+                .map(|millis| i64::from_le_bytes(millis.try_into().expect("8 bytes")))
+                .ok_or(FsError::Corrupt("file shorter than its header"))?;
+            offset += 8;
+            DateTime::from_timestamp_millis(millis)
+                .map(Some)
+                .ok_or(FsError::Corrupt("timestamp out of range"))
+        };
+        let stale = timestamp(HAS_STALE)?;
+        let expire = timestamp(HAS_EXPIRE)?;
+
+        Ok(Self {
+            stale,
+            expire,
+            key: offset..offset + key_len,
+        })
+    }
+}
+
+/// Encodes the header and key written in front of the data.
+pub(crate) fn encode(key: &[u8], value: &CacheValue<Raw>) -> Vec<u8> {
+    let mut flags = 0;
+    if value.stale().is_some() {
+        flags |= HAS_STALE;
+    }
+    if value.expire().is_some() {
+        flags |= HAS_EXPIRE;
+    }
+
+    let mut header = Vec::with_capacity(MAX_META_LEN + key.len());
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&[VERSION, flags]);
+    header.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    for timestamp in [value.stale(), value.expire()].into_iter().flatten() {
+        header.extend_from_slice(&timestamp.timestamp_millis().to_le_bytes());
+    }
+    header.extend_from_slice(key);
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_roundtrip() {
+        let now = DateTime::from_timestamp_millis(Utc::now().timestamp_millis()).unwrap();
+        let values = [
+            CacheValue::new(Bytes::from_static(b"data"), None, None),
+            CacheValue::new(Bytes::from_static(b"data"), Some(now), None),
+            CacheValue::new(Bytes::from_static(b"data"), Some(now), Some(now)),
+        ];
+        for value in values {
+            let mut file = encode(b"key", &value);
+            file.extend_from_slice(value.data());
+
+            let header = Header::decode(&file).unwrap();
+            assert_eq!(header.expire, value.expire());
+            assert_eq!(header.stale, value.stale());
+            assert_eq!(&file[header.key.clone()], b"key");
+            assert_eq!(&file[header.data_start()..], b"data");
+        }
+    }
+
+    #[test]
+    fn test_corrupt() {
+        assert!(Header::decode(b"HBX").is_err());
+        assert!(Header::decode(b"XXX\x01\x00\x00\x00\x00\x00").is_err());
+        // Expire flag without timestamp
+        assert!(Header::decode(b"HBX\x01\x02\x00\x00\x00\x00").is_err());
+    }
+}
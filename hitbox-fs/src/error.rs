@@ -0,0 +1,28 @@
+use hitbox_backend::BackendError;
+use thiserror::Error;
+
+/// Errors that can occur when using [`FsBackend`](crate::FsBackend).
+#[derive(Debug, Error)]
+pub enum FsError {
+    /// An I/O error occurred while accessing the cache directory.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Failed to serialize a cache key.
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] hitbox_backend::format::FormatError),
+
+    /// A file in the cache directory is not a valid cache entry.
+    #[error("Corrupt cache entry: {0}")]
+    Corrupt(&'static str),
+
+    /// The provided configuration is invalid.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl From<FsError> for BackendError {
+    fn from(error: FsError) -> Self {
+        Self::InternalError(Box::new(error))
+    }
+}
@@ -0,0 +1,12 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod backend;
+mod entry;
+mod error;
+mod store;
+mod sweeper;
+
+pub use backend::{EntryReader, FsBackend, FsBackendBuilder};
+pub use error::FsError;
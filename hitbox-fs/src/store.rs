@@ -0,0 +1,338 @@
+//! Blocking file operations on the cache directory.
+
+use std::fs::{self, File, Metadata};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use hitbox_core::{CacheValue, Raw};
+use memmap2::Mmap;
+
+use crate::FsError;
+use crate::entry::{self, Header, MAX_META_LEN};
+
+/// Reads refresh the modification time at most this often; it doubles as
+/// the last access time for eviction.
+const ACCESS_RESOLUTION: Duration = Duration::from_secs(1);
+
+/// Temporary files older than this are leftovers of interrupted writes.
+const TEMP_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Entry file opened for streaming, positioned at the first data byte.
+pub(crate) struct OpenEntry {
+    pub header: Header,
+    pub file: File,
+    pub len: u64,
+}
+
+/// Cache directory, sharded as `{root}/ab/cd/abcd…` by the SHA-1 of the key.
+pub(crate) struct Store {
+    root: PathBuf,
+    mmap_threshold: u64,
+    temp_counter: AtomicU64,
+}
+
+impl Store {
+    pub(crate) fn new(root: PathBuf, mmap_threshold: u64) -> Result<Self, FsError> {
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            root,
+            mmap_threshold,
+            temp_counter: AtomicU64::new(0),
+        })
+    }
+
+    pub(crate) fn path(&self, key: &[u8]) -> PathBuf {
+        let name = sha1_smol::Sha1::from(key).digest().to_string();
+        self.root.join(&name[..2]).join(&name[2..4]).join(name)
+    }
+
+    /// Reads an entry, memory-mapping files of at least `mmap_threshold` bytes.
+    ///
+    /// A corrupt entry is deleted and reported as a miss.
+    pub(crate) fn read(
+        &self,
+        key: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<Option<CacheValue<Raw>>, FsError> {
+        let path = self.path(key);
+        discard_corrupt(&path, self.read_entry(&path, key, now))
+    }
+
+    fn read_entry(
+        &self,
+        path: &Path,
+        key: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<Option<CacheValue<Raw>>, FsError> {
+        let Some(file) = open(path)? else {
+            return Ok(None);
+        };
+        let metadata = file.metadata()?;
+        let bytes = if metadata.len() >= self.mmap_threshold.max(1) {
+            // Safety: entry files are only ever replaced by rename, never
+            // modified in place, so the mapping cannot change under us.
+            Bytes::from_owner(unsafe { Mmap::map(&file)? })
+        } else {
+            let mut bytes = Vec::with_capacity(metadata.len() as usize);
+            (&file).read_to_end(&mut bytes)?;
+            Bytes::from(bytes)
+        };
+
+        let header = Header::decode(&bytes)?;
+        // A different key with the same hash
+        if bytes.get(header.key.clone()) != Some(key) {
+            return Ok(None);
+        }
+        if header.expire.is_some_and(|expire| expire <= now) {
+            return Ok(None);
+        }
+        touch(&file, &metadata);
+
+        Ok(Some(CacheValue::new(
+            bytes.slice(header.data_start()..),
+            header.expire,
+            header.stale,
+        )))
+    }
+
+    /// Opens an entry for streaming its data.
+    ///
+    /// A corrupt entry is deleted and reported as a miss.
+    pub(crate) fn open(
+        &self,
+        key: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<Option<OpenEntry>, FsError> {
+        let path = self.path(key);
+        discard_corrupt(&path, self.open_entry(&path, key, now))
+    }
+
+    fn open_entry(
+        &self,
+        path: &Path,
+        key: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<Option<OpenEntry>, FsError> {
+        let Some(mut file) = open(path)? else {
+            return Ok(None);
+        };
+        let metadata = file.metadata()?;
+        let header = read_header(&file)?;
+
+        let mut stored_key = vec![0; header.key.len()];
+        file.seek(SeekFrom::Start(header.key.start as u64))?;
+        if let Err(error) = file.read_exact(&mut stored_key) {
+            return match error.kind() {
+                ErrorKind::UnexpectedEof => Err(FsError::Corrupt("file shorter than its key")),
+                _ => Err(error.into()),
+            };
+        }
+        if stored_key != key || header.expire.is_some_and(|expire| expire <= now) {
+            return Ok(None);
+        }
+        touch(&file, &metadata);
+
+        let len = metadata.len() - header.data_start() as u64;
+        Ok(Some(OpenEntry { header, file, len }))
+    }
+
+    /// Writes an entry to a temporary file and renames it into place.
+    pub(crate) fn write(&self, key: &[u8], value: &CacheValue<Raw>) -> Result<(), FsError> {
+        let path = self.path(key);
+        let dir = path.parent().expect("entry path has a parent");
+        fs::create_dir_all(dir)?;
+
+        let temp = dir.join(format!(
+            ".{}.{}.{}.tmp",
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default(),
+            std::process::id(),
+            self.temp_counter.fetch_add(1, Ordering::Relaxed),
+        ));
+        let result = File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(&entry::encode(key, value))?;
+                file.write_all(value.data())?;
+                // Otherwise a crash can leave the renamed file empty
+                file.sync_data()
+            })
+            .and_then(|()| fs::rename(&temp, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        Ok(result?)
+    }
+
+    /// Deletes an entry, returning whether it existed.
+    pub(crate) fn remove(&self, key: &[u8]) -> Result<bool, FsError> {
+        match fs::remove_file(self.path(key)) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Deletes expired and corrupt entries and leftover temporary files, then
+    /// the least recently accessed entries while the total size exceeds
+    /// `max_size`.
+    /// Returns the number of deleted entries.
+    pub(crate) fn sweep(
+        &self,
+        now: DateTime<Utc>,
+        max_size: Option<u64>,
+    ) -> Result<usize, FsError> {
+        let mut deleted = 0;
+        let mut total = 0;
+        let mut live = Vec::new();
+
+        for (path, metadata) in self.files()? {
+            let modified = metadata.modified()?;
+            if is_temp(&path) {
+                if modified.elapsed().unwrap_or_default() > TEMP_MAX_AGE {
+                    remove_file(&path)?;
+                }
+                continue;
+            }
+
+            let expired = match open(&path)
+                .and_then(|file| file.map(|file| read_header(&file)).transpose())
+            {
+                Ok(Some(header)) => header.expire.is_some_and(|expire| expire <= now),
+                // Removed concurrently
+                Ok(None) => continue,
+                Err(FsError::Corrupt(reason)) => {
+                    tracing::warn!(path = %path.display(), reason, "deleting corrupt cache file");
+                    true
+                }
+                Err(error) => {
+                    tracing::warn!(path = %path.display(), %error, "skipping unreadable cache file");
+                    continue;
+                }
+            };
+            if expired {
+                remove_file(&path)?;
+                deleted += 1;
+            } else {
+                total += metadata.len();
+                live.push((modified, metadata.len(), path));
+            }
+        }
+
+        if let Some(max_size) = max_size
+            && total > max_size
+        {
+            live.sort_unstable_by_key(|(modified, _, _)| *modified);
+            for (_, len, path) in live {
+                if total <= max_size {
+                    break;
+                }
+                remove_file(&path)?;
+                total -= len;
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Total size of the entry files.
+    pub(crate) fn size(&self) -> Result<u64, FsError> {
+        Ok(self
+            .files()?
+            .into_iter()
+            .filter(|(path, _)| !is_temp(path))
+            .map(|(_, metadata)| metadata.len())
+            .sum())
+    }
+
+    /// Lists the files of the two directory levels below the root.
+    fn files(&self) -> Result<Vec<(PathBuf, Metadata)>, FsError> {
+        let mut files = Vec::new();
+        for first in subdirectories(&self.root)? {
+            for second in subdirectories(&first)? {
+                for file in read_dir(&second)? {
+                    if let Ok(metadata) = file.metadata()
+                        && metadata.is_file()
+                    {
+                        files.push((file.path(), metadata));
+                    }
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// Opens a file, returning `None` if it does not exist.
+fn open(path: &Path) -> Result<Option<File>, FsError> {
+    match File::open(path) {
+        Ok(file) => Ok(Some(file)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Turns a corrupt entry into a miss, deleting its file so it is rewritten.
+fn discard_corrupt<T>(
+    path: &Path,
+    result: Result<Option<T>, FsError>,
+) -> Result<Option<T>, FsError> {
+    match result {
+        Err(FsError::Corrupt(reason)) => {
+            tracing::warn!(path = %path.display(), reason, "deleting corrupt cache file");
+            remove_file(path)?;
+            Ok(None)
+        }
+        result => result,
+    }
+}
+
+/// Reads and decodes the header at the start of `file`.
+fn read_header(file: &File) -> Result<Header, FsError> {
+    let mut meta = Vec::with_capacity(MAX_META_LEN);
+    file.take(MAX_META_LEN as u64).read_to_end(&mut meta)?;
+    Header::decode(&meta)
+}
+
+/// Records an access by bumping the modification time.
+fn touch(file: &File, metadata: &Metadata) {
+    let now = SystemTime::now();
+    let stale = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_none_or(|age| age >= ACCESS_RESOLUTION);
+    if stale {
+        // Best effort: eviction order is approximate anyway
+        let _ = file.set_modified(now);
+    }
+}
+
+fn is_temp(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+/// Removes a file that may have been removed concurrently.
+fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+fn read_dir(dir: &Path) -> io::Result<impl Iterator<Item = fs::DirEntry>> {
+    Ok(fs::read_dir(dir)?.filter_map(Result::ok))
+}
+
+fn subdirectories(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    Ok(read_dir(dir)?
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| entry.path())
+        .collect())
+}
@@ -0,0 +1,51 @@
+//! Background thread walking the cache directory.
+//!
+//! A sweep reads the header of every entry file, deleting expired and corrupt
+//! entries and stale temp files, then evicts by modification time while the
+//! directory is over `max_size`.
+
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+
+use crate::store::Store;
+
+/// Stops the sweeper thread when dropped.
+pub(crate) struct Sweeper {
+    _stop: Sender<()>,
+}
+
+impl Sweeper {
+    /// Starts sweeping `store` every `interval`, counted from the end of the
+    /// previous sweep so walks of a large directory never overlap.
+    pub(crate) fn spawn(
+        store: Arc<Store>,
+        interval: Duration,
+        max_size: Option<u64>,
+    ) -> std::io::Result<Self> {
+        let (stop, stopped) = mpsc::channel::<()>();
+        thread::Builder::new()
+            .name("hitbox-fs-sweeper".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    let started = Instant::now();
+                    if let Err(error) = store.sweep(Utc::now(), max_size) {
+                        tracing::warn!(%error, "filesystem cache sweep failed");
+                    }
+                    let took = started.elapsed();
+                    if took > interval {
+                        // The directory holds more files than the interval allows for
+                        tracing::warn!(
+                            ?took,
+                            ?interval,
+                            "filesystem cache sweep outlasted its interval"
+                        );
+                    }
+                }
+            })?;
+        Ok(Self { _stop: stop })
+    }
+}
@@ -0,0 +1,517 @@
+//! Memcached backend implementation.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hitbox::{BackendLabel, CacheKey, CacheValue, Raw};
+use hitbox_backend::{
+    Backend, BackendResult, CacheKeyFormat, Compressor, DeleteStatus, PassthroughCompressor,
+    format::{BincodeFormat, Format, FormatError},
+};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::error::Error;
+use crate::protocol::{Connection, Key};
+use crate::ring::Ring;
+use crate::value;
+
+/// Largest `exptime` memcached treats as relative seconds.
+const MAX_RELATIVE_EXPTIME: i64 = 60 * 60 * 24 * 30;
+
+/// Pooled connections to one memcached server.
+struct Server {
+    address: String,
+    /// Idle connection of each slot, `None` until first use or after an error.
+    slots: Vec<Mutex<Option<Connection>>>,
+    /// Round-robin start for picking a slot.
+    next: AtomicUsize,
+}
+
+impl Server {
+    fn new(address: String, pool_size: usize) -> Self {
+        Self {
+            address,
+            slots: (0..pool_size).map(|_| Mutex::new(None)).collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Locks an idle slot, or waits for the next one in round-robin order.
+    async fn slot(&self) -> MutexGuard<'_, Option<Connection>> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let count = self.slots.len();
+        for i in 0..count {
+            if let Ok(slot) = self.slots[(start + i) % count].try_lock() {
+                return slot;
+            }
+        }
+        self.slots[start % count].lock().await
+    }
+}
+
+/// Memcached cache backend.
+///
+/// `MemcachedBackend` stores cache entries on one or more memcached servers
+/// using the meta protocol (memcached 1.6 or newer). Keys are distributed
+/// across servers with ketama consistent hashing, so adding or removing a
+/// server only remaps a fraction of the keys.
+///
+/// # Examples
+///
+/// ```
+/// use hitbox_memcached::MemcachedBackend;
+///
+/// # fn main() -> Result<(), hitbox_memcached::Error> {
+/// let backend = MemcachedBackend::builder()
+///     .servers(["10.0.0.1:11211", "10.0.0.2:11211"])
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Storage
+///
+/// Each entry is one memcached item. The value starts with a small header
+/// holding the stale and expire timestamps, followed by the serialized data.
+/// The item's `exptime` is the entry TTL rounded up to whole seconds, and
+/// reads skip entries whose exact expire timestamp has passed.
+///
+/// Serialized keys that are printable ASCII are sent as is, binary keys are
+/// base64 encoded, and keys that would exceed memcached's 250-byte limit are
+/// replaced by their SHA-1 digest.
+///
+/// # Caveats
+///
+/// - **No failover**: Operations on keys owned by an unreachable server fail;
+///   the ring is not rebuilt without it
+/// - **Value size**: Values above the server's item size limit (1 MB by
+///   default) are rejected by memcached
+/// - **Far expiry**: TTLs over 30 days are sent as an absolute Unix time and
+///   depend on the server clock
+#[derive(Clone)]
+pub struct MemcachedBackend<S = BincodeFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Servers, indexed by the ring.
+    servers: Arc<[Server]>,
+    /// Consistent hashing ring over `servers`.
+    ring: Arc<Ring>,
+    /// Timeout for establishing connections.
+    connection_timeout: Option<Duration>,
+    /// Timeout for waiting on memcached responses.
+    response_timeout: Option<Duration>,
+
+    /// Format used to serialize cache values.
+    serializer: S,
+    /// Format used to serialize cache keys.
+    key_format: CacheKeyFormat,
+    /// Prefix of every memcached key written by this backend, if any.
+    namespace: Option<String>,
+    /// Compressor used for cache values.
+    compressor: C,
+    /// Label identifying this backend in multi-tier compositions.
+    label: BackendLabel,
+}
+
+impl MemcachedBackend<BincodeFormat, PassthroughCompressor> {
+    /// Creates a new builder for `MemcachedBackend`.
+    ///
+    /// Use the builder to configure the servers, serialization format,
+    /// key format, compression, and label. See [`MemcachedBackend`] for examples.
+    #[must_use]
+    pub fn builder() -> MemcachedBackendBuilder<BincodeFormat, PassthroughCompressor> {
+        MemcachedBackendBuilder::default()
+    }
+}
+
+impl<S, C> MemcachedBackend<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    fn storage_key(&self, key: &CacheKey) -> Result<Key, FormatError> {
+        let serialized = self.key_format.serialize(key)?;
+        Ok(match &self.namespace {
+            Some(namespace) => Key::new(&[namespace.as_bytes(), b":", &serialized].concat()),
+            None => Key::new(&serialized),
+        })
+    }
+
+    /// Runs `command` on a pooled connection to the server owning `key`.
+    ///
+    /// The connection is only returned to the pool when the command
+    /// succeeds; after an error or cancellation its state is unknown.
+    async fn execute<T>(
+        &self,
+        key: &Key,
+        command: impl AsyncFnOnce(&mut Connection) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let server = &self.servers[self.ring.server(key.as_bytes())];
+        let mut slot = server.slot().await;
+        let mut connection = match slot.take() {
+            Some(connection) => connection,
+            None => {
+                with_timeout(
+                    self.connection_timeout,
+                    Connection::connect(&server.address),
+                )
+                .await?
+            }
+        };
+        let result = with_timeout(self.response_timeout, command(&mut connection)).await?;
+        *slot = Some(connection);
+        Ok(result)
+    }
+}
+
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T, Error>>,
+) -> Result<T, Error> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| Error::Timeout)?,
+        None => future.await,
+    }
+}
+
+/// Converts an expire timestamp into a memcached `exptime`.
+///
+/// Rounds up to whole seconds so memcached never evicts an entry before it
+/// expires; already expired entries get a negative `exptime`.
+fn exptime(expire: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    let millis = (expire - now).num_milliseconds();
+    if millis <= 0 {
+        return -1;
+    }
+    let seconds = (millis + 999) / 1000;
+    if seconds <= MAX_RELATIVE_EXPTIME {
+        seconds
+    } else {
+        expire.timestamp() + 1
+    }
+}
+
+/// Builder for creating and configuring a [`MemcachedBackend`].
+///
+/// Use [`MemcachedBackend::builder`] to create a new builder instance.
+/// See [`MemcachedBackend`] for usage examples.
+pub struct MemcachedBackendBuilder<S = BincodeFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    servers: Vec<String>,
+    pool_size: usize,
+    serializer: S,
+    key_format: CacheKeyFormat,
+    namespace: Option<String>,
+    compressor: C,
+    label: BackendLabel,
+    connection_timeout: Option<Duration>,
+    response_timeout: Option<Duration>,
+}
+
+impl Default for MemcachedBackendBuilder<BincodeFormat, PassthroughCompressor> {
+    fn default() -> Self {
+        Self {
+            servers: Vec::new(),
+            pool_size: 4,
+            serializer: BincodeFormat,
+            key_format: CacheKeyFormat::default(),
+            namespace: None,
+            compressor: PassthroughCompressor,
+            label: BackendLabel::new_static("memcached"),
+            connection_timeout: None,
+            response_timeout: None,
+        }
+    }
+}
+
+impl<S, C> MemcachedBackendBuilder<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Adds a memcached server by its `host:port` address.
+    ///
+    /// At least one server is required before calling [`build`].
+    ///
+    /// [`build`]: Self::build
+    pub fn server(mut self, address: impl Into<String>) -> Self {
+        self.servers.push(address.into());
+        self
+    }
+
+    /// Adds several memcached servers by their `host:port` addresses.
+    ///
+    /// Keys are distributed with ketama consistent hashing over the server
+    /// addresses, so every instance sharing the fleet must list the same
+    /// addresses, in any order.
+    pub fn servers<I, A>(mut self, addresses: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<String>,
+    {
+        self.servers.extend(addresses.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the number of connections kept to each server.
+    ///
+    /// Each connection serves one request at a time.
+    ///
+    /// # Default
+    ///
+    /// 4
+    pub fn pool_size(mut self, size: usize) -> Self {
+        self.pool_size = size.max(1);
+        self
+    }
+
+    /// Sets the connection timeout.
+    ///
+    /// # Default
+    ///
+    /// No timeout (waits indefinitely).
+    pub fn connection_timeout(mut self, timeout: Duration) -> Self {
+        self.connection_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the response timeout.
+    ///
+    /// A connection whose response timed out is closed.
+    ///
+    /// # Default
+    ///
+    /// No timeout (waits indefinitely).
+    pub fn response_timeout(mut self, timeout: Duration) -> Self {
+        self.response_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the cache value serialization format.
+    ///
+    /// # Default
+    ///
+    /// [`BincodeFormat`]
+    pub fn value_format<NewS>(self, serializer: NewS) -> MemcachedBackendBuilder<NewS, C>
+    where
+        NewS: Format,
+    {
+        MemcachedBackendBuilder {
+            servers: self.servers,
+            pool_size: self.pool_size,
+            serializer,
+            key_format: self.key_format,
+            namespace: self.namespace,
+            compressor: self.compressor,
+            label: self.label,
+            connection_timeout: self.connection_timeout,
+            response_timeout: self.response_timeout,
+        }
+    }
+
+    /// Sets the cache key serialization format.
+    ///
+    /// With [`CacheKeyFormat::UrlEncoded`], keys without spaces are stored
+    /// readably; other keys are base64 encoded, and keys over 250 bytes
+    /// are hashed.
+    ///
+    /// # Default
+    ///
+    /// [`CacheKeyFormat::Bitcode`]
+    pub fn key_format(mut self, key_format: CacheKeyFormat) -> Self {
+        self.key_format = key_format;
+        self
+    }
+
+    /// Prefixes every memcached key with `{namespace}:`.
+    ///
+    /// Lets several services share one memcached fleet without key collisions.
+    ///
+    /// # Default
+    ///
+    /// None (keys are stored as serialized by the key format)
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Sets a custom label for this backend.
+    ///
+    /// # Default
+    ///
+    /// `"memcached"`
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the compression strategy for cache values.
+    ///
+    /// # Default
+    ///
+    /// [`PassthroughCompressor`] (no compression)
+    pub fn compressor<NewC>(self, compressor: NewC) -> MemcachedBackendBuilder<S, NewC>
+    where
+        NewC: Compressor,
+    {
+        MemcachedBackendBuilder {
+            servers: self.servers,
+            pool_size: self.pool_size,
+            serializer: self.serializer,
+            key_format: self.key_format,
+            namespace: self.namespace,
+            compressor,
+            label: self.label,
+            connection_timeout: self.connection_timeout,
+            response_timeout: self.response_timeout,
+        }
+    }
+
+    /// Builds the [`MemcachedBackend`] with the configured settings.
+    ///
+    /// Connections are established lazily on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::MissingServers`] if no server was specified.
+    pub fn build(self) -> Result<MemcachedBackend<S, C>, Error> {
+        if self.servers.is_empty() {
+            return Err(Error::MissingServers);
+        }
+
+        let ring = Ring::new(&self.servers);
+        let servers = self
+            .servers
+            .into_iter()
+            .map(|address| Server::new(address, self.pool_size))
+            .collect();
+
+        Ok(MemcachedBackend {
+            servers,
+            ring: Arc::new(ring),
+            connection_timeout: self.connection_timeout,
+            response_timeout: self.response_timeout,
+            serializer: self.serializer,
+            key_format: self.key_format,
+            namespace: self.namespace,
+            compressor: self.compressor,
+            label: self.label,
+        })
+    }
+}
+
+#[async_trait]
+impl<S, C> Backend for MemcachedBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let key = self.storage_key(key)?;
+        let Some(bytes) = self
+            .execute(&key, async |connection| connection.get(&key).await)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let value = value::decode(bytes)?;
+        // memcached expires items on whole seconds
+        if value.expire().is_some_and(|expire| expire <= Utc::now()) {
+            return Ok(None);
+        }
+        Ok(Some(value))
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let key = self.storage_key(key)?;
+        let exptime = value
+            .expire()
+            .map_or(0, |expire| exptime(expire, Utc::now()));
+        let data = value::encode(&value);
+
+        self.execute(&key, async |connection| {
+            connection.set(&key, &data, exptime).await
+        })
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        let key = self.storage_key(key)?;
+        let deleted = self
+            .execute(&key, async |connection| connection.delete(&key).await)
+            .await?;
+
+        Ok(if deleted {
+            DeleteStatus::Deleted(1)
+        } else {
+            DeleteStatus::Missing
+        })
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        &self.serializer
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        &self.key_format
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        &self.compressor
+    }
+}
+
+// Explicit CacheBackend implementation using default trait methods
+impl<S, C> hitbox_backend::CacheBackend for MemcachedBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn test_exptime() {
+        let now = Utc::now();
+        assert_eq!(exptime(now - TimeDelta::seconds(1), now), -1);
+        assert_eq!(exptime(now, now), -1);
+        assert_eq!(exptime(now + TimeDelta::milliseconds(1), now), 1);
+        assert_eq!(exptime(now + TimeDelta::milliseconds(1500), now), 2);
+        assert_eq!(
+            exptime(now + TimeDelta::days(30), now),
+            MAX_RELATIVE_EXPTIME
+        );
+
+        let far = now + TimeDelta::days(31);
+        assert_eq!(exptime(far, now), far.timestamp() + 1);
+    }
+
+    #[test]
+    fn test_build_requires_servers() {
+        assert!(matches!(
+            MemcachedBackend::builder().build(),
+            Err(Error::MissingServers)
+        ));
+    }
+}
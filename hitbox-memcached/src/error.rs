@@ -0,0 +1,48 @@
+//! Error types for memcached backend operations.
+//!
+//! All errors can be converted to [`BackendError`] for uniform error handling
+//! across different cache backends.
+//!
+//! [`BackendError`]: hitbox_backend::BackendError
+
+use hitbox_backend::BackendError;
+
+/// Error type for memcached backend operations.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Connecting to or talking with a memcached server failed.
+    #[error("memcached I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Connecting or waiting for a response took longer than the configured timeout.
+    #[error("memcached operation timed out")]
+    Timeout,
+
+    /// The server answered with `ERROR`, `CLIENT_ERROR` or `SERVER_ERROR`,
+    /// e.g. because it does not support the meta protocol.
+    #[error("memcached error: {0}")]
+    Server(String),
+
+    /// A response did not follow the protocol, or a stored value has no
+    /// valid header.
+    #[error("memcached protocol error: {0}")]
+    Protocol(&'static str),
+
+    /// No servers were specified when building the backend.
+    ///
+    /// Call [`MemcachedBackendBuilder::server`] or
+    /// [`MemcachedBackendBuilder::servers`] before
+    /// [`MemcachedBackendBuilder::build`].
+    ///
+    /// [`MemcachedBackendBuilder::server`]: crate::MemcachedBackendBuilder::server
+    /// [`MemcachedBackendBuilder::servers`]: crate::MemcachedBackendBuilder::servers
+    /// [`MemcachedBackendBuilder::build`]: crate::MemcachedBackendBuilder::build
+    #[error("No memcached servers specified. Call .server() or .servers() before .build()")]
+    MissingServers,
+}
+
+impl From<Error> for BackendError {
+    fn from(error: Error) -> Self {
+        Self::InternalError(Box::new(error))
+    }
+}
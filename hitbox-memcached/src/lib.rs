@@ -0,0 +1,14 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub mod backend;
+pub mod error;
+mod protocol;
+mod ring;
+mod value;
+
+#[doc(inline)]
+pub use crate::backend::{MemcachedBackend, MemcachedBackendBuilder};
+#[doc(inline)]
+pub use crate::error::Error;
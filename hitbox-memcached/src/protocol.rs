@@ -0,0 +1,226 @@
+//! Meta protocol commands (`mg`, `ms`, `md`) over one TCP connection.
+//!
+//! Meta commands are part of the text protocol since memcached 1.6 and
+//! accept base64 encoded binary keys, which hitbox keys usually are.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+
+use crate::error::Error;
+
+/// Longest key memcached accepts.
+pub(crate) const MAX_KEY_LENGTH: usize = 250;
+
+/// A key as sent to memcached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Key {
+    token: String,
+    /// Whether `token` is base64 and needs the `b` flag.
+    base64: bool,
+}
+
+impl Key {
+    /// Sends printable keys as is and binary keys base64 encoded. Keys that
+    /// would not fit into [`MAX_KEY_LENGTH`] are replaced by their SHA-1 digest.
+    pub(crate) fn new(bytes: &[u8]) -> Self {
+        if bytes.len() <= MAX_KEY_LENGTH && bytes.iter().all(u8::is_ascii_graphic) {
+            return Self {
+                // All bytes are ASCII
+                token: String::from_utf8_lossy(bytes).into_owned(),
+                base64: false,
+            };
+        }
+        if bytes.len().div_ceil(3) * 4 <= MAX_KEY_LENGTH {
+            return Self {
+                token: STANDARD.encode(bytes),
+                base64: true,
+            };
+        }
+        Self {
+            token: STANDARD.encode(sha1_smol::Sha1::from(bytes).digest().bytes()),
+            base64: true,
+        }
+    }
+
+    /// Bytes hashed to pick the server.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        self.token.as_bytes()
+    }
+
+    fn flags(&self) -> &'static str {
+        if self.base64 { " b" } else { "" }
+    }
+}
+
+/// Buffered connection to one memcached server.
+///
+/// Requests are not cancel-safe: a connection whose request was interrupted
+/// must be dropped, since its response is still on the wire.
+pub(crate) struct Connection {
+    stream: BufStream<TcpStream>,
+}
+
+impl Connection {
+    pub(crate) async fn connect(address: &str) -> Result<Self, Error> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream: BufStream::new(stream),
+        })
+    }
+
+    /// Returns the value stored under `key`, if any.
+    pub(crate) async fn get(&mut self, key: &Key) -> Result<Option<Vec<u8>>, Error> {
+        let request = format!("mg {} v{}\r\n", key.token, key.flags());
+        self.stream.write_all(request.as_bytes()).await?;
+        self.stream.flush().await?;
+
+        let line = self.read_line().await?;
+        if line == "EN" {
+            return Ok(None);
+        }
+        let length = line
+            .strip_prefix("VA ")
+            .and_then(|rest| rest.split(' ').next())
+            .and_then(|length| length.parse::<usize>().ok())
+            .ok_or_else(|| unexpected(line))?;
+
+        let mut data = vec![0; length + 2];
+        self.stream.read_exact(&mut data).await?;
+        if !data.ends_with(b"\r\n") {
+            return Err(Error::Protocol("value not terminated by CRLF"));
+        }
+        data.truncate(length);
+        Ok(Some(data))
+    }
+
+    /// Stores `data` under `key`.
+    ///
+    /// `exptime` follows memcached rules: `0` never expires, up to 30 days is
+    /// relative seconds, larger values are a Unix timestamp and negative
+    /// values expire immediately.
+    pub(crate) async fn set(&mut self, key: &Key, data: &[u8], exptime: i64) -> Result<(), Error> {
+        let request = format!(
+            "ms {} {} T{exptime}{}\r\n",
+            key.token,
+            data.len(),
+            key.flags()
+        );
+        self.stream.write_all(request.as_bytes()).await?;
+        self.stream.write_all(data).await?;
+        self.stream.write_all(b"\r\n").await?;
+        self.stream.flush().await?;
+
+        match self.read_line().await? {
+            line if line == "HD" => Ok(()),
+            line if line == "NS" => Err(Error::Server("value not stored".to_string())),
+            line => Err(unexpected(line)),
+        }
+    }
+
+    /// Deletes `key`, returning whether it existed.
+    pub(crate) async fn delete(&mut self, key: &Key) -> Result<bool, Error> {
+        let request = format!("md {}{}\r\n", key.token, key.flags());
+        self.stream.write_all(request.as_bytes()).await?;
+        self.stream.flush().await?;
+
+        match self.read_line().await? {
+            line if line == "HD" => Ok(true),
+            line if line == "NF" => Ok(false),
+            line => Err(unexpected(line)),
+        }
+    }
+
+    /// Reads one response line without the trailing CRLF.
+    async fn read_line(&mut self) -> Result<String, Error> {
+        let mut line = Vec::new();
+        if self.stream.read_until(b'\n', &mut line).await? == 0 {
+            return Err(Error::Io(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        let line = line
+            .strip_suffix(b"\r\n")
+            .ok_or(Error::Protocol("response line not terminated by CRLF"))?;
+        String::from_utf8(line.to_vec()).map_err(|_| Error::Protocol("response line is not UTF-8"))
+    }
+}
+
+/// Turns an error line into [`Error::Server`] and anything else into a protocol error.
+fn unexpected(line: String) -> Error {
+    if line == "ERROR" || line.starts_with("CLIENT_ERROR") || line.starts_with("SERVER_ERROR") {
+        Error::Server(line)
+    } else {
+        Error::Protocol("unexpected response")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_key_encoding() {
+        let key = Key::new(b"_prefix=api&user=1");
+        assert_eq!(key.token, "_prefix=api&user=1");
+        assert!(!key.base64);
+
+        let key = Key::new(b"with space");
+        assert_eq!(key.token, "d2l0aCBzcGFjZQ==");
+        assert!(key.base64);
+
+        let long = vec![7u8; 1000];
+        let key = Key::new(&long);
+        assert!(key.base64);
+        assert!(key.token.len() <= MAX_KEY_LENGTH);
+        assert_ne!(key, Key::new(&long[1..]));
+    }
+
+    /// Serves canned responses and checks the requests against `exchange`.
+    async fn mock_server(exchange: &'static [(&'static [u8], &'static [u8])]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            for (request, response) in exchange {
+                let mut received = vec![0; request.len()];
+                socket.read_exact(&mut received).await.unwrap();
+                assert_eq!(
+                    String::from_utf8_lossy(&received),
+                    String::from_utf8_lossy(request)
+                );
+                socket.write_all(response).await.unwrap();
+            }
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn test_commands() {
+        let address = mock_server(&[
+            (b"ms a 5 T60\r\nhello\r\n", b"HD\r\n"),
+            (b"mg a v\r\n", b"VA 5\r\nhello\r\n"),
+            (b"md a\r\n", b"HD\r\n"),
+            (b"mg a v\r\n", b"EN\r\n"),
+            (b"md a\r\n", b"NF\r\n"),
+            (b"mg IA== v b\r\n", b"SERVER_ERROR out of memory\r\n"),
+        ])
+        .await;
+        let mut connection = Connection::connect(&address).await.unwrap();
+        let key = Key::new(b"a");
+
+        connection.set(&key, b"hello", 60).await.unwrap();
+        assert_eq!(
+            connection.get(&key).await.unwrap().as_deref(),
+            Some(&b"hello"[..])
+        );
+        assert!(connection.delete(&key).await.unwrap());
+        assert_eq!(connection.get(&key).await.unwrap(), None);
+        assert!(!connection.delete(&key).await.unwrap());
+        assert!(matches!(
+            connection.get(&Key::new(b" ")).await,
+            Err(Error::Server(message)) if message == "SERVER_ERROR out of memory"
+        ));
+    }
+}
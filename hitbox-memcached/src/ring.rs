@@ -0,0 +1,85 @@
+//! Ketama consistent hashing of keys onto servers.
+
+use md5::{Digest, Md5};
+
+/// Points hashed per server: 40 MD5 digests of four points each.
+const POINTS_PER_SERVER: usize = 160;
+
+/// Continuum of server points, as in libmemcached's ketama distribution.
+///
+/// Each server owns 160 points on a 32-bit circle and a key belongs to the
+/// first point at or after its hash. Adding or removing a server only moves
+/// the keys falling between its points and their predecessors.
+#[derive(Debug)]
+pub(crate) struct Ring {
+    /// `(point, server index)` sorted by point.
+    points: Vec<(u32, usize)>,
+}
+
+impl Ring {
+    pub(crate) fn new<S: AsRef<str>>(servers: &[S]) -> Self {
+        let mut points = Vec::with_capacity(servers.len() * POINTS_PER_SERVER);
+        for (index, server) in servers.iter().enumerate() {
+            for i in 0..POINTS_PER_SERVER / 4 {
+                let digest = Md5::digest(format!("{}-{i}", server.as_ref()));
+                for chunk in digest.chunks_exact(4) {
+                    let point = u32::from_le_bytes(chunk.try_into().expect("4-byte chunk"));
+                    points.push((point, index));
+                }
+            }
+        }
+        points.sort_unstable();
+        Self { points }
+    }
+
+    /// Returns the index of the server owning `key`.
+    pub(crate) fn server(&self, key: &[u8]) -> usize {
+        let digest = Md5::digest(key);
+        let hash = u32::from_le_bytes(digest[..4].try_into().expect("4-byte prefix"));
+        let position = self.points.partition_point(|&(point, _)| point < hash);
+        self.points
+            .get(position)
+            .or_else(|| self.points.first())
+            .map_or(0, |&(_, index)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> impl Iterator<Item = Vec<u8>> {
+        (0..10_000).map(|i| format!("key-{i}").into_bytes())
+    }
+
+    #[test]
+    fn test_single_server() {
+        let ring = Ring::new(&["127.0.0.1:11211"]);
+        assert!(keys().all(|key| ring.server(&key) == 0));
+    }
+
+    #[test]
+    fn test_keys_are_spread_across_servers() {
+        let ring = Ring::new(&["10.0.0.1:11211", "10.0.0.2:11211", "10.0.0.3:11211"]);
+        let mut counts = [0; 3];
+        for key in keys() {
+            counts[ring.server(&key)] += 1;
+        }
+        assert!(counts.iter().all(|&count| count > 2_000), "{counts:?}");
+    }
+
+    #[test]
+    fn test_adding_server_moves_few_keys() {
+        let before = Ring::new(&["10.0.0.1:11211", "10.0.0.2:11211", "10.0.0.3:11211"]);
+        let after = Ring::new(&[
+            "10.0.0.1:11211",
+            "10.0.0.2:11211",
+            "10.0.0.3:11211",
+            "10.0.0.4:11211",
+        ]);
+        for key in keys() {
+            let server = after.server(&key);
+            assert!(server == 3 || server == before.server(&key));
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Header stored in front of every cache value.
+//!
+//! Layout: a version byte, a flags byte, then the stale and expire
+//! timestamps as big-endian Unix milliseconds for each flag set, then the
+//! data. memcached only keeps whole-second expiry times, so the exact expire
+//! timestamp travels with the value.
+
+use bytes::Bytes;
+use chrono::DateTime;
+use hitbox::{CacheValue, Raw};
+
+use crate::error::Error;
+
+const VERSION: u8 = 1;
+const HAS_STALE: u8 = 0b01;
+const HAS_EXPIRE: u8 = 0b10;
+
+pub(crate) fn encode(value: &CacheValue<Raw>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(18 + value.data().len());
+    let mut flags = 0;
+    if value.stale().is_some() {
+        flags |= HAS_STALE;
+    }
+    if value.expire().is_some() {
+        flags |= HAS_EXPIRE;
+    }
+    bytes.extend_from_slice(&[VERSION, flags]);
+    for timestamp in [value.stale(), value.expire()].into_iter().flatten() {
+        bytes.extend_from_slice(&timestamp.timestamp_millis().to_be_bytes());
+    }
+    bytes.extend_from_slice(value.data());
+    bytes
+}
+
+pub(crate) fn decode(bytes: Vec<u8>) -> Result<CacheValue<Raw>, Error> {
+    let bytes = Bytes::from(bytes);
+    let [version, flags] = *bytes
+        .first_chunk::<2>()
+        .ok_or(Error::Protocol("value shorter than its header"))?;
+    if version != VERSION {
+        return Err(Error::Protocol("unknown value header version"));
+    }
+
+    let mut offset = 2;
+    let mut timestamp = |flag: u8| -> Result<_, Error> {
+        if flags & flag == 0 {
+            return Ok(None);
+        }
+        let millis = bytes
+            .get(offset..offset + 8)
+            .and_then(|millis| millis.try_into().ok())
+            .map(i64::from_be_bytes)
+            .ok_or(Error::Protocol("value shorter than its header"))?;
+        offset += 8;
+        DateTime::from_timestamp_millis(millis)
+            .map(Some)
+            .ok_or(Error::Protocol("timestamp out of range"))
+    };
+    let stale = timestamp(HAS_STALE)?;
+    let expire = timestamp(HAS_EXPIRE)?;
+
+    Ok(CacheValue::new(bytes.slice(offset..), expire, stale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, Utc};
+
+    #[test]
+    fn test_roundtrip() {
+        let now = DateTime::from_timestamp_millis(Utc::now().timestamp_millis()).unwrap();
+        let values = [
+            CacheValue::new(Bytes::from_static(b"data"), None, None),
+            CacheValue::new(Bytes::from_static(b"data"), Some(now), None),
+            CacheValue::new(Bytes::from_static(b""), None, Some(now)),
+            CacheValue::new(
+                Bytes::from_static(b"data"),
+                Some(now + TimeDelta::seconds(60)),
+                Some(now + TimeDelta::seconds(30)),
+            ),
+        ];
+        for value in values {
+            let decoded = decode(encode(&value)).unwrap();
+            assert_eq!(decoded.data(), value.data());
+            assert_eq!(decoded.expire(), value.expire());
+            assert_eq!(decoded.stale(), value.stale());
+        }
+    }
+
+    #[test]
+    fn test_truncated_header() {
+        assert!(decode(vec![]).is_err());
+        assert!(decode(vec![VERSION, HAS_STALE, 0, 0]).is_err());
+        assert!(decode(vec![0, 0]).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Tests against local memcached processes.
+//!
+//! Run with `cargo test -p hitbox-memcached -- --ignored`. Each test starts
+//! its own `memcached` on a free port; set `MEMCACHED_BIN` to use a binary
+//! outside `PATH`.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{SubsecRound, Utc};
+use hitbox::{CacheKey, CacheValue, KeyPart};
+use hitbox_backend::{Backend, CacheKeyFormat, DeleteStatus};
+use hitbox_memcached::MemcachedBackend;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::{Child, Command};
+
+struct Memcached {
+    address: String,
+    _process: Child,
+}
+
+async fn memcached() -> Memcached {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let binary = std::env::var("MEMCACHED_BIN").unwrap_or_else(|_| "memcached".into());
+    let process = Command::new(binary)
+        .args(["-l", "127.0.0.1", "-U", "0", "-p", &port.to_string()])
+        .stdout(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .expect("failed to start memcached");
+
+    let address = format!("127.0.0.1:{port}");
+    for _ in 0..100 {
+        if TcpStream::connect(&address).await.is_ok() {
+            return Memcached {
+                address,
+                _process: process,
+            };
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("memcached did not start listening on {address}");
+}
+
+fn value(data: &'static [u8]) -> CacheValue<Bytes> {
+    CacheValue::new(Bytes::from_static(data), None, None)
+}
+
+#[tokio::test]
+#[ignore = "requires a memcached binary"]
+async fn test_write_and_read() {
+    let server = memcached().await;
+    let backend = MemcachedBackend::builder()
+        .server(&server.address)
+        .build()
+        .unwrap();
+    let key = CacheKey::from_str("test_key", "1");
+    let expire = Utc::now().trunc_subsecs(3) + chrono::Duration::seconds(60);
+    let stale = Utc::now().trunc_subsecs(3) + chrono::Duration::seconds(30);
+
+    backend
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"value"), Some(expire), Some(stale)),
+        )
+        .await
+        .unwrap();
+
+    let read = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"value");
+    assert_eq!(read.expire(), Some(expire));
+    assert_eq!(read.stale(), Some(stale));
+}
+
+#[tokio::test]
+#[ignore = "requires a memcached binary"]
+async fn test_expire() {
+    let server = memcached().await;
+    let backend = MemcachedBackend::builder()
+        .server(&server.address)
+        .build()
+        .unwrap();
+    let key = CacheKey::from_str("test_key", "1");
+    let expire = Utc::now() + chrono::Duration::milliseconds(200);
+
+    backend
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"value"), Some(expire), None),
+        )
+        .await
+        .unwrap();
+    assert!(backend.read(&key).await.unwrap().is_some());
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(backend.read(&key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires a memcached binary"]
+async fn test_remove() {
+    let server = memcached().await;
+    let backend = MemcachedBackend::builder()
+        .server(&server.address)
+        .build()
+        .unwrap();
+    let key = CacheKey::from_str("test_key", "1");
+
+    assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+    backend.write(&key, value(b"value")).await.unwrap();
+    assert_eq!(
+        backend.remove(&key).await.unwrap(),
+        DeleteStatus::Deleted(1)
+    );
+    assert!(backend.read(&key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires a memcached binary"]
+async fn test_long_keys() {
+    let server = memcached().await;
+    let backend = MemcachedBackend::builder()
+        .server(&server.address)
+        .key_format(CacheKeyFormat::UrlEncoded)
+        .build()
+        .unwrap();
+    let long = |suffix: &str| {
+        CacheKey::new(
+            "api",
+            1,
+            vec![KeyPart::new(
+                "path",
+                Some(format!("{}{suffix}", "a".repeat(300))),
+            )],
+        )
+    };
+
+    backend.write(&long("1"), value(b"first")).await.unwrap();
+    backend.write(&long("2"), value(b"second")).await.unwrap();
+
+    let read = backend.read(&long("1")).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"first");
+    let read = backend.read(&long("2")).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"second");
+}
+
+#[tokio::test]
+#[ignore = "requires a memcached binary"]
+async fn test_keys_are_distributed_across_servers() {
+    let first = memcached().await;
+    let second = memcached().await;
+    let backend = MemcachedBackend::builder()
+        .servers([&first.address, &second.address])
+        .build()
+        .unwrap();
+    let keys: Vec<_> = (0..100)
+        .map(|i| CacheKey::from_str("test_key", &i.to_string()))
+        .collect();
+    for key in &keys {
+        backend.write(key, value(b"value")).await.unwrap();
+    }
+
+    let mut found = Vec::new();
+    for server in [&first, &second] {
+        let single = MemcachedBackend::builder()
+            .server(&server.address)
+            .build()
+            .unwrap();
+        let mut count = 0;
+        for key in &keys {
+            if single.read(key).await.unwrap().is_some() {
+                count += 1;
+            }
+        }
+        found.push(count);
+    }
+    assert_eq!(found.iter().sum::<usize>(), keys.len());
+    assert!(found.iter().all(|&count| count > 0), "{found:?}");
+}
+
+#[tokio::test]
+#[ignore = "requires a memcached binary"]
+async fn test_namespaces_are_isolated() {
+    let server = memcached().await;
+    let orders = MemcachedBackend::builder()
+        .server(&server.address)
+        .namespace("orders")
+        .build()
+        .unwrap();
+    let users = MemcachedBackend::builder()
+        .server(&server.address)
+        .namespace("users")
+        .build()
+        .unwrap();
+    let key = CacheKey::from_str("test_key", "1");
+
+    orders.write(&key, value(b"value")).await.unwrap();
+    assert!(users.read(&key).await.unwrap().is_none());
+    assert!(orders.read(&key).await.unwrap().is_some());
+}
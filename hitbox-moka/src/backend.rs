@@ -1,5 +1,7 @@
 //! Moka backend implementation.
 
+use std::borrow::Cow;
+
 use async_trait::async_trait;
 use hitbox::{BackendLabel, CacheKey, CacheValue, Raw};
 use hitbox_backend::Backend;
@@ -69,6 +71,7 @@ where
 {
     pub(crate) cache: Cache<CacheKey, CacheValue<Raw>>,
     pub(crate) key_format: CacheKeyFormat,
+    pub(crate) namespace: Option<String>,
     pub(crate) serializer: S,
     pub(crate) compressor: C,
     pub(crate) label: BackendLabel,
@@ -135,6 +138,18 @@ where
             self.weighted_size(),
         );
     }
+
+    /// Returns `key` with the namespace prepended to its prefix, if any.
+    pub(crate) fn storage_key<'a>(&self, key: &'a CacheKey) -> Cow<'a, CacheKey> {
+        match &self.namespace {
+            Some(namespace) => Cow::Owned(CacheKey::new(
+                format!("{namespace}:{}", key.prefix()),
+                key.version(),
+                key.parts().cloned().collect(),
+            )),
+            None => Cow::Borrowed(key),
+        }
+    }
 }
 
 impl MokaBackend<JsonFormat, PassthroughCompressor> {
@@ -161,17 +176,23 @@ where
     C: Compressor + Send + Sync,
 {
     async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
-        self.cache.get(key).await.map(Ok).transpose()
+        self.cache
+            .get(self.storage_key(key).as_ref())
+            .await
+            .map(Ok)
+            .transpose()
     }
 
     async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
-        self.cache.insert(key.clone(), value).await;
+        self.cache
+            .insert(self.storage_key(key).into_owned(), value)
+            .await;
         self.record_metrics();
         Ok(())
     }
 
     async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
-        let value = self.cache.remove(key).await;
+        let value = self.cache.remove(self.storage_key(key).as_ref()).await;
         self.record_metrics();
         match value {
             Some(_) => Ok(DeleteStatus::Deleted(1)),
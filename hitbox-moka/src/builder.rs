@@ -1,16 +1,23 @@
 //! Builder for configuring [`MokaBackend`].
 
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use moka::Expiry;
 use moka::future::{Cache, CacheBuilder};
+use moka::notification::{ListenerFuture, RemovalCause};
 use moka::policy::EvictionPolicy;
 
 use crate::backend::MokaBackend;
+use crate::hybrid::HybridBackendBuilder;
 use hitbox::{BackendLabel, CacheKey, CacheValue, Raw};
 use hitbox_backend::format::{Format, JsonFormat};
-use hitbox_backend::{CacheKeyFormat, Compressor, PassthroughCompressor};
+use hitbox_backend::{Backend, CacheKeyFormat, Compressor, PassthroughCompressor};
+
+/// Callback invoked by Moka for every entry leaving the cache.
+pub(crate) type EvictionListener =
+    Arc<dyn Fn(Arc<CacheKey>, CacheValue<Raw>, RemovalCause) -> ListenerFuture + Send + Sync>;
 
 /// Custom expiration policy that calculates TTL from [`CacheValue::expire`] timestamps.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -139,10 +146,12 @@ where
 {
     capacity: Cap,
     key_format: CacheKeyFormat,
+    namespace: Option<String>,
     serializer: S,
     compressor: C,
     label: BackendLabel,
     eviction_policy: Option<EvictionPolicy>,
+    eviction_listener: Option<EvictionListener>,
 }
 
 impl MokaBackendBuilder<NoCapacity, JsonFormat, PassthroughCompressor> {
@@ -154,10 +163,12 @@ impl MokaBackendBuilder<NoCapacity, JsonFormat, PassthroughCompressor> {
         Self {
             capacity: NoCapacity,
             key_format: CacheKeyFormat::Bitcode,
+            namespace: None,
             serializer: JsonFormat,
             compressor: PassthroughCompressor,
             label: BackendLabel::new_static("moka"),
             eviction_policy: None,
+            eviction_listener: None,
         }
     }
 }
@@ -191,10 +202,12 @@ where
         MokaBackendBuilder {
             capacity: EntryCapacity(capacity),
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor: self.compressor,
             label: self.label,
             eviction_policy: self.eviction_policy,
+            eviction_listener: self.eviction_listener,
         }
     }
 
@@ -221,10 +234,12 @@ where
         MokaBackendBuilder {
             capacity: ByteCapacity(bytes),
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor: self.compressor,
             label: self.label,
             eviction_policy: self.eviction_policy,
+            eviction_listener: self.eviction_listener,
         }
     }
 }
@@ -269,6 +284,20 @@ where
         self
     }
 
+    /// Prefixes the prefix of every stored key with `{namespace}:`.
+    ///
+    /// Entries written through backends with different namespaces never
+    /// collide, even when they end up in the same cache. Keys returned by
+    /// [`MokaBackend::cache`] carry the namespace.
+    ///
+    /// # Default
+    ///
+    /// None (keys are stored as given)
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
     /// Sets the eviction policy for the cache.
     ///
     /// The eviction policy determines how entries are selected for removal when
@@ -329,10 +358,12 @@ where
         MokaBackendBuilder {
             capacity: self.capacity,
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer,
             compressor: self.compressor,
             label: self.label,
             eviction_policy: self.eviction_policy,
+            eviction_listener: self.eviction_listener,
         }
     }
 
@@ -370,12 +401,47 @@ where
         MokaBackendBuilder {
             capacity: self.capacity,
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor,
             label: self.label,
             eviction_policy: self.eviction_policy,
+            eviction_listener: self.eviction_listener,
         }
     }
+
+    /// Demotes entries evicted for capacity to `disk` instead of dropping
+    /// them, turning the backend into a [`HybridBackend`].
+    ///
+    /// Entries are promoted back to memory when read. See [`HybridBackend`]
+    /// for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hitbox_feoxdb::FeOxDbBackend;
+    /// use hitbox_moka::MokaBackend;
+    ///
+    /// let disk = FeOxDbBackend::in_memory().expect("Failed to create disk tier");
+    /// let backend = MokaBackend::builder()
+    ///     .max_entries(10_000)
+    ///     .demote_to(disk)
+    ///     .build();
+    /// ```
+    ///
+    /// [`HybridBackend`]: crate::HybridBackend
+    pub fn demote_to<D>(self, disk: D) -> HybridBackendBuilder<Cap, D, S, C>
+    where
+        D: Backend + 'static,
+    {
+        HybridBackendBuilder::new(self, disk)
+    }
+
+    /// Registers a callback for entries leaving the cache.
+    pub(crate) fn eviction_listener(mut self, listener: EvictionListener) -> Self {
+        self.eviction_listener = Some(listener);
+        self
+    }
 }
 
 impl<S, C> MokaBackendBuilder<EntryCapacity, S, C>
@@ -390,14 +456,15 @@ where
         let policy = self
             .eviction_policy
             .unwrap_or_else(EvictionPolicy::tiny_lfu);
-        let cache: Cache<CacheKey, CacheValue<Raw>> = CacheBuilder::new(self.capacity.0)
+        let cache = CacheBuilder::new(self.capacity.0)
             .eviction_policy(policy)
-            .expire_after(Expiration)
-            .build();
+            .expire_after(Expiration);
+        let cache = with_listener(cache, self.eviction_listener).build();
 
         MokaBackend {
             cache,
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor: self.compressor,
             label: self.label,
@@ -421,15 +488,16 @@ where
     /// [`eviction_policy()`](MokaBackendBuilder::eviction_policy) if needed.
     pub fn build(self) -> MokaBackend<S, C> {
         let policy = self.eviction_policy.unwrap_or_else(EvictionPolicy::lru);
-        let cache: Cache<CacheKey, CacheValue<Raw>> = CacheBuilder::new(self.capacity.0)
+        let cache = CacheBuilder::new(self.capacity.0)
             .weigher(Self::byte_weigher)
             .eviction_policy(policy)
-            .expire_after(Expiration)
-            .build();
+            .expire_after(Expiration);
+        let cache = with_listener(cache, self.eviction_listener).build();
 
         MokaBackend {
             cache,
             key_format: self.key_format,
+            namespace: self.namespace,
             serializer: self.serializer,
             compressor: self.compressor,
             label: self.label,
@@ -444,3 +512,16 @@ where
         (key.memory_size() + value.memory_size()).min(u32::MAX as usize) as u32
     }
 }
+
+/// Attaches `listener`, if any, to the cache being built.
+fn with_listener(
+    builder: CacheBuilder<CacheKey, CacheValue<Raw>, Cache<CacheKey, CacheValue<Raw>>>,
+    listener: Option<EvictionListener>,
+) -> CacheBuilder<CacheKey, CacheValue<Raw>, Cache<CacheKey, CacheValue<Raw>>> {
+    match listener {
+        Some(listener) => {
+            builder.async_eviction_listener(move |key, value, cause| listener(key, value, cause))
+        }
+        None => builder,
+    }
+}
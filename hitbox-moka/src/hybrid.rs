@@ -0,0 +1,297 @@
+//! Memory tier backed by a disk tier for evicted entries.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hitbox::{BackendLabel, CacheKey, CacheValue, Raw};
+use hitbox_backend::format::{Format, JsonFormat};
+use hitbox_backend::{
+    Backend, BackendResult, CacheKeyFormat, Compressor, DeleteStatus, PassthroughCompressor,
+};
+use moka::notification::{ListenerFuture, RemovalCause};
+
+use crate::backend::MokaBackend;
+use crate::builder::{ByteCapacity, EntryCapacity, EvictionListener, MokaBackendBuilder};
+
+/// In-memory cache that demotes evicted entries to a disk tier.
+///
+/// When Moka evicts an entry to stay within capacity, `HybridBackend`
+/// writes it to the disk tier instead of dropping it. Reads that miss
+/// memory check the disk tier and move hits back into memory. Every key
+/// lives in one tier at a time, so the effective capacity is the sum of
+/// both tiers while each write touches only memory.
+///
+/// This differs from [`CompositionBackend`], which writes every entry to
+/// both layers up front and keeps them in both.
+///
+/// Create one with [`MokaBackendBuilder::demote_to`]. Any [`Backend`] works
+/// as the disk tier, typically `FeOxDbBackend` or `FsBackend`. Values move
+/// between tiers as stored bytes, serialized with the memory tier's format
+/// and compressor; the disk tier's own format settings are not used.
+///
+/// ```no_run
+/// use hitbox_feoxdb::FeOxDbBackend;
+/// use hitbox_moka::MokaBackend;
+/// use hitbox_backend::format::BincodeFormat;
+///
+/// let disk = FeOxDbBackend::builder()
+///     .path("/tmp/hitbox-hybrid")
+///     .build()
+///     .expect("Failed to open disk tier");
+/// let backend = MokaBackend::builder()
+///     .max_bytes(64 * 1024 * 1024)
+///     .value_format(BincodeFormat)
+///     .demote_to(disk)
+///     .build();
+/// ```
+///
+/// # Caveats
+///
+/// - Only entries evicted for capacity are demoted. Expired, replaced and
+///   removed entries are dropped.
+/// - Demotion runs as part of Moka's housekeeping, so an evicted entry can
+///   be briefly missing from both tiers.
+/// - Removes, and writes of keys not in memory, wait for pending
+///   housekeeping so an in-flight demotion cannot bring back the old value.
+///   Such a write may also drop its own value if housekeeping demotes it
+///   meanwhile, turning the next read into a miss.
+/// - A failed demotion is logged and the entry is lost, as with a plain
+///   Moka eviction.
+///
+/// [`CompositionBackend`]: hitbox_backend::CompositionBackend
+pub struct HybridBackend<D, S = JsonFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    memory: MokaBackend<S, C>,
+    disk: Arc<D>,
+    label: BackendLabel,
+}
+
+impl<D, S, C> Clone for HybridBackend<D, S, C>
+where
+    S: Format + Clone,
+    C: Compressor + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            memory: self.memory.clone(),
+            disk: self.disk.clone(),
+            label: self.label.clone(),
+        }
+    }
+}
+
+impl<D, S, C> HybridBackend<D, S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Returns the memory tier.
+    ///
+    /// Use [`MokaBackend::cache`] to run pending evictions, and with them
+    /// demotions, in tests.
+    pub fn memory(&self) -> &MokaBackend<S, C> {
+        &self.memory
+    }
+
+    /// Returns the disk tier.
+    ///
+    /// Entries are stored under the keys of the memory tier, including its
+    /// namespace.
+    pub fn disk(&self) -> &D {
+        &self.disk
+    }
+}
+
+/// Builder for [`HybridBackend`], created by [`MokaBackendBuilder::demote_to`].
+///
+/// Memory tier settings are configured on the [`MokaBackendBuilder`] before
+/// calling `demote_to`.
+pub struct HybridBackendBuilder<Cap, D, S = JsonFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    memory: MokaBackendBuilder<Cap, S, C>,
+    disk: D,
+    label: BackendLabel,
+}
+
+impl<Cap, D, S, C> HybridBackendBuilder<Cap, D, S, C>
+where
+    D: Backend + 'static,
+    S: Format,
+    C: Compressor,
+{
+    pub(crate) fn new(memory: MokaBackendBuilder<Cap, S, C>, disk: D) -> Self {
+        Self {
+            memory,
+            disk,
+            label: BackendLabel::new_static("hybrid"),
+        }
+    }
+
+    /// Sets a custom label for this backend.
+    ///
+    /// # Default
+    ///
+    /// `"hybrid"`
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    fn split(self) -> (MokaBackendBuilder<Cap, S, C>, Arc<D>, BackendLabel) {
+        let disk = Arc::new(self.disk);
+        let memory = self.memory.eviction_listener(demote(disk.clone()));
+        (memory, disk, self.label)
+    }
+}
+
+impl<D, S, C> HybridBackendBuilder<EntryCapacity, D, S, C>
+where
+    D: Backend + 'static,
+    S: Format,
+    C: Compressor,
+{
+    /// Builds the [`HybridBackend`] with an entry-count bounded memory tier.
+    pub fn build(self) -> HybridBackend<D, S, C> {
+        let (memory, disk, label) = self.split();
+        HybridBackend {
+            memory: memory.build(),
+            disk,
+            label,
+        }
+    }
+}
+
+impl<D, S, C> HybridBackendBuilder<ByteCapacity, D, S, C>
+where
+    D: Backend + 'static,
+    S: Format + 'static,
+    C: Compressor + 'static,
+{
+    /// Builds the [`HybridBackend`] with a byte bounded memory tier.
+    pub fn build(self) -> HybridBackend<D, S, C> {
+        let (memory, disk, label) = self.split();
+        HybridBackend {
+            memory: memory.build(),
+            disk,
+            label,
+        }
+    }
+}
+
+/// Listener writing entries evicted for capacity to `disk`.
+fn demote<D>(disk: Arc<D>) -> EvictionListener
+where
+    D: Backend + 'static,
+{
+    Arc::new(move |key, value, cause| -> ListenerFuture {
+        let expired = value.expire().is_some_and(|expire| expire <= Utc::now());
+        if cause != RemovalCause::Size || expired {
+            return Box::pin(std::future::ready(()));
+        }
+        let disk = disk.clone();
+        Box::pin(async move {
+            if let Err(error) = disk.write(&key, value).await {
+                tracing::warn!(%error, "failed to demote evicted entry to disk");
+            }
+        })
+    })
+}
+
+#[async_trait]
+impl<D, S, C> Backend for HybridBackend<D, S, C>
+where
+    D: Backend + 'static,
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        if let Some(value) = self.memory.read(key).await? {
+            return Ok(Some(value));
+        }
+
+        let storage_key = self.memory.storage_key(key);
+        let Some(value) = self.disk.read(&storage_key).await? else {
+            return Ok(None);
+        };
+        // Promote: move the entry back into memory, which may demote another.
+        // A concurrent write may have stored a newer value meanwhile; keep it.
+        let entry = self
+            .memory
+            .cache
+            .entry(storage_key.clone().into_owned())
+            .or_insert(value)
+            .await;
+        if let Err(error) = self.disk.remove(&storage_key).await {
+            tracing::warn!(%error, "failed to remove promoted entry from disk");
+        }
+        Ok(Some(entry.into_value()))
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let storage_key = self.memory.storage_key(key);
+        let entry = self
+            .memory
+            .cache
+            .entry(storage_key.clone().into_owned())
+            .and_upsert_with(|_| std::future::ready(value))
+            .await;
+        self.memory.record_metrics();
+        // Replacing an entry in memory drops it without demotion. Otherwise an
+        // older copy may be on disk or still being demoted; wait for pending
+        // demotions so none lands after the removal.
+        if !entry.is_old_value_replaced() {
+            self.memory.cache.run_pending_tasks().await;
+            self.disk.remove(&storage_key).await?;
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        let memory = self.memory.remove(key).await?;
+        // An entry evicted just before may still be on its way to disk
+        self.memory.cache.run_pending_tasks().await;
+        let disk = self.disk.remove(&self.memory.storage_key(key)).await?;
+        match (memory, disk) {
+            (DeleteStatus::Deleted(memory), DeleteStatus::Deleted(disk)) => {
+                Ok(DeleteStatus::Deleted(memory + disk))
+            }
+            (DeleteStatus::Deleted(count), DeleteStatus::Missing)
+            | (DeleteStatus::Missing, DeleteStatus::Deleted(count)) => {
+                Ok(DeleteStatus::Deleted(count))
+            }
+            (DeleteStatus::Missing, DeleteStatus::Missing) => Ok(DeleteStatus::Missing),
+        }
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        self.memory.value_format()
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        self.memory.key_format()
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        self.memory.compressor()
+    }
+}
+
+// Explicit CacheBackend implementation using default trait methods
+impl<D, S, C> hitbox_backend::CacheBackend for HybridBackend<D, S, C>
+where
+    D: Backend + 'static,
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+}
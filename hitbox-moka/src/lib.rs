@@ -4,8 +4,10 @@
 
 mod backend;
 mod builder;
+mod hybrid;
 pub mod metrics;
 
 pub use backend::MokaBackend;
 pub use builder::{ByteCapacity, EntryCapacity, MokaBackendBuilder, NoCapacity};
+pub use hybrid::{HybridBackend, HybridBackendBuilder};
 pub use moka::policy::EvictionPolicy;
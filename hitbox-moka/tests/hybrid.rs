@@ -0,0 +1,304 @@
+//! Tests for demotion of evicted entries to a disk tier.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use hitbox::backend::{Backend, DeleteStatus};
+use hitbox_backend::BackendResult;
+use hitbox_core::{CacheKey, CacheValue, KeyPart, Raw};
+use hitbox_feoxdb::FeOxDbBackend;
+use hitbox_moka::{EvictionPolicy, HybridBackend, MokaBackend};
+
+fn make_key(id: u32) -> CacheKey {
+    CacheKey::new("test", 1, vec![KeyPart::new("id", Some(id.to_string()))])
+}
+
+fn make_value(data: &'static str) -> CacheValue<Bytes> {
+    let expire = Some(Utc::now() + chrono::Duration::hours(1));
+    CacheValue::new(Bytes::from_static(data.as_bytes()), expire, None)
+}
+
+/// Hybrid backend holding at most `capacity` entries in memory.
+fn make_backend(capacity: u64) -> HybridBackend<FeOxDbBackend> {
+    MokaBackend::builder()
+        .max_entries(capacity)
+        .eviction_policy(EvictionPolicy::lru())
+        .demote_to(FeOxDbBackend::in_memory().unwrap())
+        .build()
+}
+
+/// Fills memory with entries 1..=count, evicting and demoting the oldest.
+async fn fill(backend: &HybridBackend<FeOxDbBackend>, count: u32) {
+    for i in 1..=count {
+        backend
+            .write(&make_key(i), make_value("data"))
+            .await
+            .unwrap();
+        backend.memory().cache().run_pending_tasks().await;
+    }
+}
+
+#[tokio::test]
+async fn test_evicted_entry_demoted_and_promoted() {
+    let backend = make_backend(2);
+    fill(&backend, 3).await;
+
+    let key = make_key(1);
+    assert!(backend.memory().read(&key).await.unwrap().is_none());
+    assert!(
+        backend.disk().read(&key).await.unwrap().is_some(),
+        "Evicted entry should be demoted to disk"
+    );
+
+    let value = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(value.data().as_ref(), b"data");
+    assert!(backend.memory().read(&key).await.unwrap().is_some());
+    assert!(
+        backend.disk().read(&key).await.unwrap().is_none(),
+        "Promoted entry should leave disk"
+    );
+
+    // Promotion evicted the least recently used entry in turn
+    backend.memory().cache().run_pending_tasks().await;
+    assert!(backend.disk().read(&make_key(2)).await.unwrap().is_some());
+    for i in 1..=3 {
+        assert!(backend.read(&make_key(i)).await.unwrap().is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_expired_entry_not_demoted() {
+    let backend = make_backend(10);
+    let key = make_key(1);
+    let expiring = CacheValue::new(
+        Bytes::from_static(b"expiring"),
+        Some(Utc::now() + chrono::Duration::milliseconds(50)),
+        None,
+    );
+
+    backend.write(&key, expiring).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    backend.memory().cache().run_pending_tasks().await;
+
+    assert!(backend.disk().read(&key).await.unwrap().is_none());
+    assert!(backend.read(&key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_write_replaces_demoted_entry() {
+    let backend = make_backend(2);
+    fill(&backend, 3).await;
+
+    let key = make_key(1);
+    backend.write(&key, make_value("updated")).await.unwrap();
+    assert!(backend.disk().read(&key).await.unwrap().is_none());
+
+    let value = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(value.data().as_ref(), b"updated");
+}
+
+#[tokio::test]
+async fn test_remove_from_either_tier() {
+    let backend = make_backend(2);
+    fill(&backend, 3).await;
+
+    // Entry 1 is on disk, entry 3 in memory
+    for i in [1, 3] {
+        assert_eq!(
+            backend.remove(&make_key(i)).await.unwrap(),
+            DeleteStatus::Deleted(1)
+        );
+        assert!(backend.read(&make_key(i)).await.unwrap().is_none());
+    }
+    assert_eq!(
+        backend.remove(&make_key(1)).await.unwrap(),
+        DeleteStatus::Missing
+    );
+}
+
+#[tokio::test]
+async fn test_namespace_applies_to_disk_tier() {
+    let backend = MokaBackend::builder()
+        .max_entries(1)
+        .eviction_policy(EvictionPolicy::lru())
+        .namespace("app")
+        .demote_to(FeOxDbBackend::in_memory().unwrap())
+        .build();
+    fill(&backend, 2).await;
+
+    let stored = CacheKey::new(
+        "app:test",
+        1,
+        vec![KeyPart::new("id", Some("1".to_string()))],
+    );
+    assert!(backend.disk().read(&stored).await.unwrap().is_some());
+    assert!(backend.read(&make_key(1)).await.unwrap().is_some());
+}
+
+/// Disk tier whose reads pause after fetching until released.
+struct GatedDisk {
+    inner: FeOxDbBackend,
+    fetched: Arc<tokio::sync::Notify>,
+    release: Arc<tokio::sync::Notify>,
+}
+
+#[async_trait]
+impl Backend for GatedDisk {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let value = self.inner.read(key).await?;
+        self.fetched.notify_one();
+        self.release.notified().await;
+        Ok(value)
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        self.inner.write(key, value).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.inner.remove(key).await
+    }
+}
+
+#[tokio::test]
+async fn test_promotion_keeps_concurrent_write() {
+    let fetched = Arc::new(tokio::sync::Notify::new());
+    let release = Arc::new(tokio::sync::Notify::new());
+    let backend = Arc::new(
+        MokaBackend::builder()
+            .max_entries(10)
+            .demote_to(GatedDisk {
+                inner: FeOxDbBackend::in_memory().unwrap(),
+                fetched: fetched.clone(),
+                release: release.clone(),
+            })
+            .build(),
+    );
+    let key = make_key(1);
+    backend
+        .disk()
+        .inner
+        .write(&key, make_value("demoted"))
+        .await
+        .unwrap();
+
+    // The read fetches the demoted copy, then a write lands before promotion
+    let read = tokio::spawn({
+        let backend = backend.clone();
+        let key = key.clone();
+        async move { backend.read(&key).await }
+    });
+    fetched.notified().await;
+    backend.write(&key, make_value("fresh")).await.unwrap();
+    release.notify_one();
+
+    let value = read.await.unwrap().unwrap().unwrap();
+    assert_eq!(value.data().as_ref(), b"fresh");
+    let value = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(value.data().as_ref(), b"fresh");
+}
+
+/// Disk tier whose writes wait until `release` is closed.
+struct SlowDemotionDisk {
+    inner: FeOxDbBackend,
+    writing: Arc<tokio::sync::Notify>,
+    release: Arc<tokio::sync::Semaphore>,
+}
+
+#[async_trait]
+impl Backend for SlowDemotionDisk {
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        self.inner.read(key).await
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        self.writing.notify_one();
+        // Fails once closed, which opens the gate for good
+        let _ = self.release.acquire().await;
+        self.inner.write(key, value).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        self.inner.remove(key).await
+    }
+}
+
+/// Hybrid backend holding one entry in memory, with entry 1 being demoted
+/// until `release` is closed.
+async fn demoting_first_entry() -> (
+    Arc<HybridBackend<SlowDemotionDisk>>,
+    Arc<tokio::sync::Semaphore>,
+) {
+    let writing = Arc::new(tokio::sync::Notify::new());
+    let release = Arc::new(tokio::sync::Semaphore::new(0));
+    let backend = Arc::new(
+        MokaBackend::builder()
+            .max_entries(1)
+            .eviction_policy(EvictionPolicy::lru())
+            .demote_to(SlowDemotionDisk {
+                inner: FeOxDbBackend::in_memory().unwrap(),
+                writing: writing.clone(),
+                release: release.clone(),
+            })
+            .build(),
+    );
+    // Through the memory tier, as hybrid writes would run the eviction
+    let memory = backend.memory();
+    memory.write(&make_key(1), make_value("old")).await.unwrap();
+    memory.cache().run_pending_tasks().await;
+    memory
+        .write(&make_key(2), make_value("data"))
+        .await
+        .unwrap();
+
+    tokio::spawn({
+        let backend = backend.clone();
+        async move { backend.memory().cache().run_pending_tasks().await }
+    });
+    writing.notified().await;
+    (backend, release)
+}
+
+#[tokio::test]
+async fn test_remove_waits_for_inflight_demotion() {
+    let (backend, release) = demoting_first_entry().await;
+    let key = make_key(1);
+
+    let mut remove = tokio::spawn({
+        let backend = backend.clone();
+        let key = key.clone();
+        async move { backend.remove(&key).await }
+    });
+    assert!(
+        tokio::time::timeout(std::time::Duration::from_millis(50), &mut remove)
+            .await
+            .is_err(),
+        "Remove should wait for the demotion"
+    );
+    release.close();
+
+    assert_eq!(remove.await.unwrap().unwrap(), DeleteStatus::Deleted(1));
+    assert!(backend.disk().inner.read(&key).await.unwrap().is_none());
+    assert!(backend.read(&key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_write_waits_for_inflight_demotion() {
+    let (backend, release) = demoting_first_entry().await;
+    let key = make_key(1);
+
+    let write = tokio::spawn({
+        let backend = backend.clone();
+        let key = key.clone();
+        async move { backend.write(&key, make_value("new")).await }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    release.close();
+    write.await.unwrap().unwrap();
+
+    assert!(backend.disk().inner.read(&key).await.unwrap().is_none());
+    let value = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(value.data().as_ref(), b"new");
+}
@@ -0,0 +1,33 @@
+//! Tests for key namespaces.
+
+use bytes::Bytes;
+use hitbox::backend::{Backend, DeleteStatus};
+use hitbox_core::{CacheKey, CacheValue, KeyPart};
+use hitbox_moka::MokaBackend;
+
+#[tokio::test]
+async fn test_namespace_prefixes_stored_keys() {
+    let backend = MokaBackend::builder()
+        .max_entries(100)
+        .namespace("orders")
+        .build();
+
+    let key = CacheKey::new("api", 1, vec![KeyPart::new("id", Some("1"))]);
+    let value = CacheValue::new(Bytes::from_static(b"order"), None, None);
+    backend.write(&key, value).await.unwrap();
+
+    let read = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"order");
+
+    backend.cache().run_pending_tasks().await;
+    let stored: Vec<_> = backend.cache().iter().map(|(key, _)| key).collect();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].prefix(), "orders:api");
+    assert_eq!(stored[0].version(), 1);
+
+    assert_eq!(
+        backend.remove(&key).await.unwrap(),
+        DeleteStatus::Deleted(1)
+    );
+    assert!(backend.read(&key).await.unwrap().is_none());
+}
@@ -0,0 +1,176 @@
+//! SQL access to the cache table.
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use deadpool_postgres::Pool;
+use hitbox::{CacheValue, Raw};
+
+use crate::error::Error;
+
+/// Rows deleted per statement while sweeping, so writers are not blocked for long.
+const SWEEP_BATCH: u64 = 1000;
+
+/// A row to write.
+pub(crate) struct Entry {
+    pub key: Vec<u8>,
+    pub prefix: String,
+    pub value: CacheValue<Raw>,
+}
+
+/// Connection pool and the statements for the cache table.
+pub(crate) struct Database {
+    pool: Pool,
+    sql: Statements,
+}
+
+/// SQL with the table name filled in.
+struct Statements {
+    schema: String,
+    select: String,
+    upsert: String,
+    delete: String,
+    delete_prefix: String,
+    delete_expired: String,
+}
+
+impl Statements {
+    fn new(table: &str) -> Self {
+        Self {
+            // The advisory lock serializes instances creating the table concurrently
+            schema: format!(
+                "SELECT pg_advisory_xact_lock(hashtext('{table}'));
+                 CREATE UNLOGGED TABLE IF NOT EXISTS {table} (
+                     key BYTEA PRIMARY KEY,
+                     prefix TEXT NOT NULL,
+                     data BYTEA NOT NULL,
+                     expire TIMESTAMPTZ,
+                     stale TIMESTAMPTZ
+                 );
+                 CREATE INDEX IF NOT EXISTS {table}_expire ON {table} (expire) WHERE expire IS NOT NULL;
+                 CREATE INDEX IF NOT EXISTS {table}_prefix ON {table} (prefix);"
+            ),
+            select: format!(
+                "SELECT key, data, expire, stale FROM {table} \
+                 WHERE key = ANY($1) AND (expire IS NULL OR expire > $2)"
+            ),
+            upsert: format!(
+                "INSERT INTO {table} (key, prefix, data, expire, stale) \
+                 VALUES ($1, $2, $3, $4, $5) \
+                 ON CONFLICT (key) DO UPDATE SET \
+                 prefix = EXCLUDED.prefix, data = EXCLUDED.data, \
+                 expire = EXCLUDED.expire, stale = EXCLUDED.stale"
+            ),
+            delete: format!("DELETE FROM {table} WHERE key = ANY($1)"),
+            delete_prefix: format!("DELETE FROM {table} WHERE prefix = $1"),
+            delete_expired: format!(
+                "DELETE FROM {table} WHERE key IN \
+                 (SELECT key FROM {table} WHERE expire <= $1 LIMIT {SWEEP_BATCH})"
+            ),
+        }
+    }
+}
+
+impl Database {
+    /// Wraps `pool`, checking that `table` is a plain identifier.
+    pub(crate) fn new(pool: Pool, table: &str) -> Result<Self, Error> {
+        if table.is_empty() || !table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(Error::InvalidConfig(format!(
+                "table name {table:?} must only contain ASCII letters, digits and underscores"
+            )));
+        }
+        Ok(Self {
+            pool,
+            sql: Statements::new(table),
+        })
+    }
+
+    pub(crate) fn pool(&self) -> &Pool {
+        &self.pool
+    }
+
+    /// Creates the table and its indexes if they do not exist.
+    pub(crate) async fn init(&self) -> Result<(), Error> {
+        let mut client = self.pool.get().await?;
+        let tx = client.transaction().await?;
+        tx.batch_execute(&self.sql.schema).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Reads the entries stored under `keys`, skipping expired ones.
+    pub(crate) async fn get_many(
+        &self,
+        keys: &[Vec<u8>],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Option<CacheValue<Raw>>>, Error> {
+        let client = self.pool.get().await?;
+        let select = client.prepare_cached(&self.sql.select).await?;
+        let mut rows: HashMap<Vec<u8>, CacheValue<Raw>> = client
+            .query(&select, &[&keys, &now])
+            .await?
+            .into_iter()
+            .map(|row| {
+                let value = CacheValue::new(
+                    Bytes::from(row.get::<_, Vec<u8>>(1)),
+                    row.get(2),
+                    row.get(3),
+                );
+                (row.get(0), value)
+            })
+            .collect();
+        Ok(keys.iter().map(|key| rows.remove(key)).collect())
+    }
+
+    /// Inserts or replaces `entries` in one transaction.
+    pub(crate) async fn put_many(&self, entries: &[Entry]) -> Result<(), Error> {
+        let mut client = self.pool.get().await?;
+        let upsert = client.prepare_cached(&self.sql.upsert).await?;
+        let tx = client.transaction().await?;
+        for entry in entries {
+            tx.execute(
+                &upsert,
+                &[
+                    &entry.key,
+                    &entry.prefix,
+                    &entry.value.data().as_ref(),
+                    &entry.value.expire(),
+                    &entry.value.stale(),
+                ],
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Deletes `keys`, returning how many existed.
+    pub(crate) async fn delete_many(&self, keys: &[Vec<u8>]) -> Result<u64, Error> {
+        let client = self.pool.get().await?;
+        let delete = client.prepare_cached(&self.sql.delete).await?;
+        Ok(client.execute(&delete, &[&keys]).await?)
+    }
+
+    /// Deletes every entry whose cache key has `prefix`.
+    pub(crate) async fn delete_prefix(&self, prefix: &str) -> Result<u64, Error> {
+        let client = self.pool.get().await?;
+        let delete = client.prepare_cached(&self.sql.delete_prefix).await?;
+        Ok(client.execute(&delete, &[&prefix]).await?)
+    }
+
+    /// Deletes rows expired at `now`, in batches. Returns the number of
+    /// deleted rows.
+    pub(crate) async fn sweep(&self, now: DateTime<Utc>) -> Result<u64, Error> {
+        let client = self.pool.get().await?;
+        let delete = client.prepare_cached(&self.sql.delete_expired).await?;
+        let mut deleted = 0;
+        loop {
+            let batch = client.execute(&delete, &[&now]).await?;
+            deleted += batch;
+            if batch < SWEEP_BATCH {
+                return Ok(deleted);
+            }
+        }
+    }
+}
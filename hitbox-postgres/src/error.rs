@@ -0,0 +1,49 @@
+//! Error types for Postgres backend operations.
+//!
+//! All errors can be converted to [`BackendError`] for uniform error handling
+//! across different cache backends.
+//!
+//! [`BackendError`]: hitbox_backend::BackendError
+
+use hitbox_backend::BackendError;
+
+/// Error type for Postgres backend operations.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An error from Postgres or the connection to it, including an invalid
+    /// connection URL.
+    #[error("Postgres error: {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+
+    /// No pooled connection could be obtained.
+    #[error("Postgres pool error: {0}")]
+    Pool(#[from] deadpool_postgres::PoolError),
+
+    /// The connection pool could not be created.
+    #[error("Postgres pool build error: {0}")]
+    Build(#[from] deadpool_postgres::BuildError),
+
+    /// Failed to serialize a cache key or an invalidation message.
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] hitbox_backend::format::FormatError),
+
+    /// No connection URL was specified when building the backend.
+    ///
+    /// Call [`PostgresBackendBuilder::url`] before
+    /// [`PostgresBackendBuilder::build`].
+    ///
+    /// [`PostgresBackendBuilder::url`]: crate::PostgresBackendBuilder::url
+    /// [`PostgresBackendBuilder::build`]: crate::PostgresBackendBuilder::build
+    #[error("No Postgres URL specified. Call .url() before .build()")]
+    MissingUrl,
+
+    /// The provided configuration is invalid.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl From<Error> for BackendError {
+    fn from(error: Error) -> Self {
+        Self::InternalError(Box::new(error))
+    }
+}
@@ -0,0 +1,165 @@
+//! Postgres LISTEN/NOTIFY transport for L1 invalidation.
+//!
+//! [`PostgresInvalidationBus`] carries invalidations between the instances of
+//! a [`CompositionBackend`] sharing a Postgres L2. Messages are sent with
+//! `pg_notify` on a single channel; each subscriber holds one connection
+//! running `LISTEN`.
+//!
+//! Payloads are base64 encoded, since `NOTIFY` payloads must be text shorter
+//! than 8000 bytes. Keys encoding to more than that cannot be published.
+//! Instances that are disconnected while a message is sent never receive it.
+//!
+//! [`CompositionBackend`]: hitbox_backend::CompositionBackend
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use deadpool_postgres::{Manager, ManagerConfig, Pool};
+use futures::StreamExt;
+use hitbox_backend::BackendResult;
+use hitbox_backend::composition::invalidation::{
+    InvalidationBus, InvalidationMessage, InvalidationStream,
+};
+use tokio::sync::mpsc;
+use tokio_postgres::{AsyncMessage, Config, NoTls};
+
+use crate::error::Error;
+
+/// Default notification channel name.
+const DEFAULT_CHANNEL: &str = "hitbox_invalidation";
+
+/// Invalidation bus over Postgres `LISTEN`/`NOTIFY`.
+///
+/// Publishing uses a small connection pool, connected lazily. Every
+/// [`subscribe`](InvalidationBus::subscribe) opens a dedicated listening
+/// connection. Use [`PostgresBackend::invalidation_bus`] to publish through
+/// the backend's pool instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use hitbox_postgres::PostgresInvalidationBus;
+///
+/// # fn main() -> Result<(), hitbox_postgres::Error> {
+/// let bus = PostgresInvalidationBus::new("postgres://hitbox@localhost/hitbox")?
+///     .channel("myapp_invalidation");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`PostgresBackend::invalidation_bus`]: crate::PostgresBackend::invalidation_bus
+#[derive(Clone)]
+pub struct PostgresInvalidationBus {
+    config: Config,
+    pool: Pool,
+    channel: String,
+}
+
+impl PostgresInvalidationBus {
+    /// Creates a bus for the Postgres server at `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Postgres`] if the URL is invalid.
+    pub fn new(url: &str) -> Result<Self, Error> {
+        let config = Config::from_str(url)?;
+        let manager = Manager::from_config(config.clone(), NoTls, ManagerConfig::default());
+        let pool = Pool::builder(manager).max_size(2).build()?;
+        Ok(Self::with_pool(config, pool))
+    }
+
+    pub(crate) fn with_pool(config: Config, pool: Pool) -> Self {
+        Self {
+            config,
+            pool,
+            channel: DEFAULT_CHANNEL.to_owned(),
+        }
+    }
+
+    /// Sets the notification channel (default: `hitbox_invalidation`).
+    ///
+    /// Instances exchange invalidations only when they use the same channel
+    /// on the same database.
+    #[must_use]
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = channel.into();
+        self
+    }
+}
+
+impl std::fmt::Debug for PostgresInvalidationBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostgresInvalidationBus")
+            .field("channel", &self.channel)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl InvalidationBus for PostgresInvalidationBus {
+    async fn publish(&self, message: InvalidationMessage) -> BackendResult<()> {
+        let payload = STANDARD.encode(message.encode()?);
+        let client = self.pool.get().await.map_err(Error::from)?;
+        client
+            .execute("SELECT pg_notify($1, $2)", &[&self.channel, &payload])
+            .await
+            .map_err(Error::from)?;
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> BackendResult<InvalidationStream> {
+        let (client, mut connection) = self.config.connect(NoTls).await.map_err(Error::from)?;
+
+        // Notifications arrive on the connection, which must be polled to
+        // make progress; forward them until the subscriber is dropped.
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut messages = futures::stream::poll_fn(|cx| connection.poll_message(cx));
+            while let Some(message) = messages.next().await {
+                match message {
+                    Ok(AsyncMessage::Notification(notification)) => {
+                        if sender.send(notification.payload().to_owned()).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        tracing::warn!(%error, "Postgres invalidation listener disconnected");
+                        break;
+                    }
+                }
+            }
+        });
+
+        let channel = self.channel.replace('"', "\"\"");
+        client
+            .batch_execute(&format!("LISTEN \"{channel}\""))
+            .await
+            .map_err(Error::from)?;
+
+        // The stream owns the client, so the connection closes with it
+        let stream =
+            futures::stream::unfold((client, receiver), |(client, mut receiver)| async move {
+                let payload = receiver.recv().await?;
+                Some((payload, (client, receiver)))
+            })
+            .filter_map(|payload| async move {
+                let message = STANDARD
+                    .decode(payload)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| {
+                        InvalidationMessage::decode(&bytes).map_err(|e| e.to_string())
+                    });
+                match message {
+                    Ok(message) => Some(message),
+                    Err(error) => {
+                        tracing::warn!(%error, "Skipping malformed invalidation message");
+                        None
+                    }
+                }
+            });
+        Ok(stream.boxed())
+    }
+}
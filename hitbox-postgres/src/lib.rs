@@ -0,0 +1,16 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub mod backend;
+mod db;
+pub mod error;
+pub mod invalidation;
+mod sweeper;
+
+#[doc(inline)]
+pub use crate::backend::{PostgresBackend, PostgresBackendBuilder};
+#[doc(inline)]
+pub use crate::error::Error;
+#[doc(inline)]
+pub use crate::invalidation::PostgresInvalidationBus;
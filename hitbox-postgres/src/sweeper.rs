@@ -0,0 +1,39 @@
+//! Background task deleting expired rows.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::task::JoinHandle;
+
+use crate::db::Database;
+
+/// Handle to the sweeper task; the task is aborted when this is dropped.
+pub(crate) struct Sweeper {
+    task: JoinHandle<()>,
+}
+
+impl Sweeper {
+    /// Spawns the sweeper on the current Tokio runtime.
+    pub(crate) fn spawn(db: Arc<Database>, interval: Duration) -> Self {
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                if let Err(error) = db.sweep(Utc::now()).await {
+                    tracing::warn!(%error, "Postgres cache sweep failed");
+                }
+            }
+        });
+        Self { task }
+    }
+}
+
+impl Drop for Sweeper {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
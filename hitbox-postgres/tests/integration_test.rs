@@ -0,0 +1,250 @@
+//! Tests against a local Postgres server.
+//!
+//! Run with `cargo test -p hitbox-postgres -- --ignored`. Connects to
+//! `HITBOX_POSTGRES_URL`, or `postgres://postgres@localhost/postgres` when
+//! unset. Each test uses its own table, dropped at the start of the test.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{SubsecRound, Utc};
+use futures::StreamExt;
+use hitbox::{CacheKey, CacheValue, KeyPart};
+use hitbox_backend::composition::invalidation::{InvalidationBus, InvalidationMessage};
+use hitbox_backend::{Backend, DeleteStatus};
+use hitbox_postgres::{Error, PostgresBackend};
+use tokio_postgres::NoTls;
+
+fn url() -> String {
+    std::env::var("HITBOX_POSTGRES_URL")
+        .unwrap_or_else(|_| "postgres://postgres@localhost/postgres".into())
+}
+
+/// Drops `table` left over from a previous run.
+async fn fresh_table(table: &str) {
+    let (client, connection) = tokio_postgres::connect(&url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+        .batch_execute(&format!("DROP TABLE IF EXISTS {table}"))
+        .await
+        .unwrap();
+}
+
+async fn backend(table: &str) -> PostgresBackend {
+    fresh_table(table).await;
+    PostgresBackend::builder()
+        .url(url())
+        .table(table)
+        .build()
+        .unwrap()
+}
+
+fn value(data: &'static [u8]) -> CacheValue<Bytes> {
+    CacheValue::new(
+        Bytes::from_static(data),
+        Some(Utc::now() + chrono::Duration::hours(1)),
+        None,
+    )
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_write_and_read() {
+    let backend = backend("hitbox_test_write_and_read").await;
+    let key = CacheKey::from_str("test-key", "1");
+    let expire = Utc::now().trunc_subsecs(6) + chrono::Duration::hours(1);
+    let stale = Utc::now().trunc_subsecs(6) + chrono::Duration::minutes(30);
+
+    backend
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"test-value"), Some(expire), Some(stale)),
+        )
+        .await
+        .unwrap();
+
+    let result = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(result.data().as_ref(), b"test-value");
+    assert_eq!(result.expire(), Some(expire));
+    assert_eq!(result.stale(), Some(stale));
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_overwrite() {
+    let backend = backend("hitbox_test_overwrite").await;
+    let key = CacheKey::from_str("overwrite-key", "1");
+
+    backend.write(&key, value(b"old")).await.unwrap();
+    backend.write(&key, value(b"new")).await.unwrap();
+
+    let result = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(result.data().as_ref(), b"new");
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_delete() {
+    let backend = backend("hitbox_test_delete").await;
+    let key = CacheKey::from_str("delete-key", "1");
+
+    assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+    backend.write(&key, value(b"test-value")).await.unwrap();
+    assert_eq!(
+        backend.remove(&key).await.unwrap(),
+        DeleteStatus::Deleted(1)
+    );
+    assert!(backend.read(&key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_expired_entry_not_returned_and_swept() {
+    let backend = backend("hitbox_test_expired").await;
+    let key = CacheKey::from_str("expired-key", "1");
+    let expired = CacheValue::new(
+        Bytes::from_static(b"expired"),
+        Some(Utc::now() - chrono::Duration::seconds(10)),
+        None,
+    );
+
+    backend.write(&key, expired).await.unwrap();
+    backend
+        .write(&CacheKey::from_str("live-key", "1"), value(b"live"))
+        .await
+        .unwrap();
+    assert!(backend.read(&key).await.unwrap().is_none());
+
+    assert_eq!(backend.sweep().await.unwrap(), 1);
+    assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_sweeper_deletes_expired_rows() {
+    let table = "hitbox_test_sweeper";
+    fresh_table(table).await;
+    let backend = PostgresBackend::builder()
+        .url(url())
+        .table(table)
+        .sweep_interval(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let key = CacheKey::from_str("expiring-key", "1");
+    let expiring = CacheValue::new(
+        Bytes::from_static(b"expiring"),
+        Some(Utc::now() + chrono::Duration::milliseconds(50)),
+        None,
+    );
+
+    backend.write(&key, expiring).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_batch_operations() {
+    let backend = backend("hitbox_test_batch").await;
+    let keys: Vec<_> = (0..3)
+        .map(|i| CacheKey::from_str("batch", &i.to_string()))
+        .collect();
+
+    backend
+        .write_many([
+            (keys[0].clone(), value(b"zero")),
+            (keys[2].clone(), value(b"two")),
+        ])
+        .await
+        .unwrap();
+
+    let values = backend.read_many(&keys).await.unwrap();
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[0].as_ref().unwrap().data().as_ref(), b"zero");
+    assert!(values[1].is_none());
+    assert_eq!(values[2].as_ref().unwrap().data().as_ref(), b"two");
+
+    assert_eq!(backend.remove_many(&keys).await.unwrap(), 2);
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_remove_prefix() {
+    let backend = backend("hitbox_test_remove_prefix").await;
+    let user = |id: &str| CacheKey::new("users", 1, vec![KeyPart::new("id", Some(id))]);
+    let order = CacheKey::new("orders", 1, vec![KeyPart::new("id", Some("1"))]);
+
+    backend.write(&user("1"), value(b"a")).await.unwrap();
+    backend.write(&user("2"), value(b"b")).await.unwrap();
+    backend.write(&order, value(b"c")).await.unwrap();
+
+    assert_eq!(backend.remove_prefix("users").await.unwrap(), 2);
+    assert!(backend.read(&user("1")).await.unwrap().is_none());
+    assert!(backend.read(&order).await.unwrap().is_some());
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_table_is_unlogged() {
+    let backend = backend("hitbox_test_unlogged").await;
+    backend.init().await.unwrap();
+
+    let (client, connection) = tokio_postgres::connect(&url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    let persistence: i8 = client
+        .query_one(
+            "SELECT relpersistence FROM pg_class WHERE relname = 'hitbox_test_unlogged'",
+            &[],
+        )
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(persistence as u8, b'u');
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_missing_table_without_init_schema() {
+    let table = "hitbox_test_no_init";
+    fresh_table(table).await;
+    let backend = PostgresBackend::builder()
+        .url(url())
+        .table(table)
+        .init_schema(false)
+        .build()
+        .unwrap();
+
+    let result = backend.read(&CacheKey::from_str("key", "1")).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+#[ignore = "requires a postgres server"]
+async fn test_invalidation_bus_roundtrip() {
+    let backend = backend("hitbox_test_invalidation").await;
+    let publisher = backend
+        .invalidation_bus()
+        .channel("hitbox_test_invalidation");
+    let subscriber = hitbox_postgres::PostgresInvalidationBus::new(&url())
+        .unwrap()
+        .channel("hitbox_test_invalidation");
+
+    let mut stream = subscriber.subscribe().await.unwrap();
+    let message = InvalidationMessage::new(7, CacheKey::from_str("user", "42"));
+    publisher.publish(message.clone()).await.unwrap();
+
+    let received = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("no notification received");
+    assert_eq!(received, Some(message));
+}
+
+#[tokio::test]
+async fn test_unreachable_server() {
+    let backend = PostgresBackend::builder()
+        .url("postgres://postgres@127.0.0.1:1/postgres")
+        .build()
+        .unwrap();
+    assert!(backend.read(&CacheKey::from_str("key", "1")).await.is_err());
+    assert!(matches!(backend.init().await, Err(Error::Pool(_))));
+}
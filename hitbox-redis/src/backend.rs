@@ -1,5 +1,6 @@
 //! Redis backend implementation.
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -8,7 +9,7 @@ use chrono::{DateTime, Utc};
 use hitbox::{BackendLabel, CacheKey, CacheValue, Raw};
 use hitbox_backend::{
     Backend, BackendResult, CacheKeyFormat, Compressor, DeleteStatus, PassthroughCompressor,
-    format::{BincodeFormat, Format},
+    format::{BincodeFormat, Format, FormatError},
 };
 use redis::Client;
 use redis::aio::ConnectionManager;
@@ -16,7 +17,13 @@ use redis::aio::ConnectionManager;
 use redis::cluster_async::ClusterConnection;
 use tokio::sync::OnceCell;
 
+use crate::chunk::{self, MANIFEST_FIELD, Manifest};
+use crate::client_cache::ClientCache;
 use crate::error::Error;
+#[cfg(feature = "sentinel")]
+use crate::sentinel::SentinelConnection;
+#[cfg(feature = "tls")]
+use crate::tls::TlsConfig;
 
 /// Configuration for a single Redis node connection.
 ///
@@ -142,9 +149,65 @@ impl ClusterConfig {
     }
 }
 
+/// Configuration for a Redis Sentinel deployment.
+///
+/// # When You'll Encounter This
+///
+/// You typically don't create this directly. It appears when:
+/// - Using [`ConnectionMode::sentinel`] which creates this internally
+/// - Accessing configuration for debugging or logging
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "sentinel")]
+/// # fn main() {
+/// use hitbox_redis::SentinelConfig;
+///
+/// let config = SentinelConfig::new("mymaster", [
+///     "redis://sentinel1:26379",
+///     "redis://sentinel2:26379",
+///     "redis://sentinel3:26379",
+/// ]);
+/// # }
+/// # #[cfg(not(feature = "sentinel"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "sentinel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sentinel")))]
+#[derive(Debug, Clone)]
+pub struct SentinelConfig {
+    /// Name of the monitored master, as configured in Sentinel.
+    pub(crate) master_name: String,
+    /// Sentinel node URLs, queried in order for the current master.
+    pub(crate) sentinels: Vec<String>,
+}
+
+#[cfg(feature = "sentinel")]
+impl SentinelConfig {
+    /// Creates a new Sentinel configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `master_name` - Name of the monitored master, as configured in Sentinel
+    /// * `sentinels` - Sentinel node URLs. Credentials in the first URL are
+    ///   used to authenticate with the Sentinel nodes.
+    pub fn new<I, S>(master_name: impl Into<String>, sentinels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            master_name: master_name.into(),
+            sentinels: sentinels.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 /// Redis connection mode.
 ///
-/// Determines whether to connect to a single Redis instance or a Redis Cluster.
+/// Determines whether to connect to a single Redis instance, a Redis Cluster,
+/// or the master of a Redis Sentinel deployment.
 ///
 /// # Examples
 ///
@@ -171,6 +234,22 @@ impl ClusterConfig {
 /// # #[cfg(not(feature = "cluster"))]
 /// # fn main() {}
 /// ```
+///
+/// Sentinel connection (requires `sentinel` feature):
+///
+/// ```
+/// # #[cfg(feature = "sentinel")]
+/// # fn main() {
+/// use hitbox_redis::ConnectionMode;
+///
+/// let mode = ConnectionMode::sentinel("mymaster", [
+///     "redis://sentinel1:26379",
+///     "redis://sentinel2:26379",
+/// ]);
+/// # }
+/// # #[cfg(not(feature = "sentinel"))]
+/// # fn main() {}
+/// ```
 #[derive(Debug, Clone)]
 pub enum ConnectionMode {
     /// Single Redis node connection.
@@ -180,6 +259,14 @@ pub enum ConnectionMode {
     #[cfg(feature = "cluster")]
     #[cfg_attr(docsrs, doc(cfg(feature = "cluster")))]
     Cluster(ClusterConfig),
+
+    /// Connection to the master of a Redis Sentinel deployment.
+    ///
+    /// The master is looked up through Sentinel when connecting and again
+    /// after a failover.
+    #[cfg(feature = "sentinel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sentinel")))]
+    Sentinel(SentinelConfig),
 }
 
 impl ConnectionMode {
@@ -207,6 +294,22 @@ impl ConnectionMode {
         Self::Cluster(ClusterConfig::new(nodes))
     }
 
+    /// Create a Sentinel connection mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `master_name` - Name of the monitored master, as configured in Sentinel
+    /// * `sentinels` - Sentinel node URLs, queried in order for the current master
+    #[cfg(feature = "sentinel")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sentinel")))]
+    pub fn sentinel<I, S>(master_name: impl Into<String>, sentinels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Sentinel(SentinelConfig::new(master_name, sentinels))
+    }
+
     /// Sets the exponential backoff base for retries (single-node only).
     ///
     /// The delay between reconnection attempts is calculated as `base^attempt` milliseconds.
@@ -218,7 +321,7 @@ impl ConnectionMode {
     /// # Caveats
     ///
     /// This option only applies to single-node connections and is silently ignored
-    /// for cluster mode.
+    /// for cluster and Sentinel modes.
     ///
     /// # Examples
     ///
@@ -250,7 +353,7 @@ impl ConnectionMode {
     ///
     /// - Replicas may have slightly stale data due to replication lag
     /// - This option only applies to cluster connections and is silently ignored
-    ///   for single-node mode
+    ///   for single-node and Sentinel modes
     ///
     /// # Examples
     ///
@@ -278,12 +381,46 @@ impl ConnectionMode {
     }
 }
 
+/// How [`RedisBackend`] sets the expiry of written entries.
+///
+/// # Examples
+///
+/// ```
+/// use hitbox_redis::{ConnectionMode, ExpiryMode, RedisBackend};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let backend = RedisBackend::builder()
+///     .connection(ConnectionMode::single("redis://localhost:6379/"))
+///     .expiry(ExpiryMode::Absolute)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExpiryMode {
+    /// `PEXPIRE` with the remaining TTL in milliseconds, computed by the
+    /// writer at write time.
+    ///
+    /// Independent of the Redis server clock.
+    #[default]
+    Relative,
+
+    /// `PEXPIREAT` with the entry's absolute `expire` timestamp.
+    ///
+    /// The entry expires at the same instant no matter how long the write
+    /// took to reach Redis, and a writer whose clock runs behind does not
+    /// shorten the TTL. Requires the Redis server clock to be accurate.
+    Absolute,
+}
+
 /// Internal wrapper for Redis connection types.
 #[derive(Clone)]
 enum RedisConnection {
     Single(ConnectionManager),
     #[cfg(feature = "cluster")]
     Cluster(ClusterConnection),
+    #[cfg(feature = "sentinel")]
+    Sentinel(SentinelConnection),
 }
 
 impl RedisConnection {
@@ -296,6 +433,11 @@ impl RedisConnection {
             Self::Single(conn) => pipe.query_async(conn).await,
             #[cfg(feature = "cluster")]
             Self::Cluster(conn) => pipe.query_async(conn).await,
+            #[cfg(feature = "sentinel")]
+            Self::Sentinel(conn) => {
+                conn.run(|mut conn| async move { pipe.query_async(&mut conn).await })
+                    .await
+            }
         }
     }
 
@@ -308,6 +450,12 @@ impl RedisConnection {
             Self::Single(conn) => cmd.query_async(conn).await,
             #[cfg(feature = "cluster")]
             Self::Cluster(conn) => cmd.query_async(conn).await,
+            #[cfg(feature = "sentinel")]
+            Self::Sentinel(conn) => {
+                let cmd = &*cmd;
+                conn.run(|mut conn| async move { cmd.query_async(&mut conn).await })
+                    .await
+            }
         }
     }
 }
@@ -357,6 +505,7 @@ impl RedisConnection {
 ///     .password("secret")
 ///     .label("user-sessions")
 ///     .key_format(CacheKeyFormat::UrlEncoded)
+///     .namespace("sessions-service")
 ///     .value_format(JsonFormat)
 ///     .connection_timeout(Duration::from_secs(5))
 ///     .response_timeout(Duration::from_secs(2))
@@ -389,8 +538,12 @@ impl RedisConnection {
 /// # Performance
 ///
 /// - **Read operations**: Single pipelined request (`HMGET` + `PTTL`)
-/// - **Write operations**: Single pipelined request (`HSET` + `EXPIRE`)
+/// - **Write operations**: Single transaction (`DEL` + `HSET` + `PEXPIRE`)
+/// - **Large values**: With [`chunk_size`], split across hash fields and read
+///   back with one extra pipelined request
 /// - **Connection**: Established lazily on first use, multiplexed for concurrent access
+/// - **Client-side caching**: With [`client_side_cache`], repeated reads are
+///   served in process until Redis pushes an invalidation
 ///
 /// # Caveats
 ///
@@ -402,6 +555,8 @@ impl RedisConnection {
 /// [`BincodeFormat`]: hitbox_backend::format::BincodeFormat
 /// [`Compressor`]: hitbox_backend::Compressor
 /// [`PassthroughCompressor`]: hitbox_backend::PassthroughCompressor
+/// [`client_side_cache`]: RedisBackendBuilder::client_side_cache
+/// [`chunk_size`]: RedisBackendBuilder::chunk_size
 #[derive(Clone)]
 pub struct RedisBackend<S = BincodeFormat, C = PassthroughCompressor>
 where
@@ -423,11 +578,22 @@ where
 
     /// Lazy-initialized connection (established on first cache operation).
     connection: OnceCell<RedisConnection>,
+    /// Near-cache kept coherent by Redis invalidation pushes, if enabled.
+    client_cache: Option<Arc<ClientCache>>,
+    /// How the expiry of written entries is set.
+    expiry: ExpiryMode,
+    /// Values larger than this are split into chunks of this size, if set.
+    chunk_size: Option<usize>,
+    /// TLS settings applied to every connection, if enabled.
+    #[cfg(feature = "tls")]
+    tls: Option<TlsConfig>,
 
     /// Format used to serialize cache values.
     serializer: S,
     /// Format used to serialize cache keys.
     key_format: CacheKeyFormat,
+    /// Prefix of every Redis key written by this backend, if any.
+    namespace: Option<String>,
     /// Compressor used for cache values.
     compressor: C,
     /// Label identifying this backend in multi-tier compositions.
@@ -469,24 +635,27 @@ where
                         if let Some(ref password) = self.password {
                             redis_info = redis_info.set_password(password);
                         }
+                        if self.client_cache.is_some() {
+                            // Invalidation pushes require RESP3
+                            redis_info = redis_info.set_protocol(redis::ProtocolVersion::RESP3);
+                        }
                         conn_info = conn_info.set_redis_settings(redis_info);
 
+                        #[cfg(feature = "tls")]
+                        let client = match &self.tls {
+                            Some(tls) => Client::build_with_tls(
+                                TlsConfig::upgrade_info(conn_info),
+                                tls.certificates(),
+                            )?,
+                            None => Client::open(conn_info)?,
+                        };
+                        #[cfg(not(feature = "tls"))]
                         let client = Client::open(conn_info)?;
 
-                        // Build ConnectionManagerConfig with options
-                        let mut manager_config = redis::aio::ConnectionManagerConfig::new()
+                        let manager_config = self
+                            .manager_config()
                             .set_exponent_base(config.exponent_base);
 
-                        if let Some(timeout) = self.connection_timeout {
-                            manager_config = manager_config.set_connection_timeout(Some(timeout));
-                        }
-                        if let Some(timeout) = self.response_timeout {
-                            manager_config = manager_config.set_response_timeout(Some(timeout));
-                        }
-                        if let Some(retries) = self.number_of_retries {
-                            manager_config = manager_config.set_number_of_retries(retries);
-                        }
-
                         let conn = client
                             .get_connection_manager_with_config(manager_config)
                             .await?;
@@ -515,15 +684,152 @@ where
                         if let Some(retries) = self.number_of_retries {
                             builder = builder.retries(retries as u32);
                         }
+                        #[cfg(feature = "tls")]
+                        if let Some(ref tls) = self.tls {
+                            builder = builder
+                                .tls(redis::TlsMode::Secure)
+                                .certs(tls.certificates());
+                        }
 
                         let client = builder.build()?;
                         let conn = client.get_async_connection().await?;
                         Ok(RedisConnection::Cluster(conn))
                     }
+                    #[cfg(feature = "sentinel")]
+                    ConnectionMode::Sentinel(config) => {
+                        use redis::IntoConnectionInfo;
+                        use redis::sentinel::{SentinelClientBuilder, SentinelServerType};
+
+                        let sentinels = config
+                            .sentinels
+                            .iter()
+                            .map(|url| url.as_str().into_connection_info())
+                            .collect::<Result<Vec<_>, _>>()?;
+                        let addrs = sentinels.iter().map(|info| {
+                            #[cfg(feature = "tls")]
+                            if self.tls.is_some() {
+                                return TlsConfig::upgrade(info.addr().clone());
+                            }
+                            info.addr().clone()
+                        });
+
+                        let mut builder = SentinelClientBuilder::new(
+                            addrs.collect::<Vec<_>>(),
+                            &config.master_name,
+                            SentinelServerType::Master,
+                        )?;
+                        // Sentinel nodes authenticate with the credentials of the first URL
+                        if let Some(sentinel) = sentinels.first().map(|info| info.redis_settings())
+                        {
+                            if let Some(username) = sentinel.username() {
+                                builder = builder.set_client_to_sentinel_username(username);
+                            }
+                            if let Some(password) = sentinel.password() {
+                                builder = builder.set_client_to_sentinel_password(password);
+                            }
+                        }
+                        if let Some(ref username) = self.username {
+                            builder = builder.set_client_to_redis_username(username);
+                        }
+                        if let Some(ref password) = self.password {
+                            builder = builder.set_client_to_redis_password(password);
+                        }
+                        if self.client_cache.is_some() {
+                            builder =
+                                builder.set_client_to_redis_protocol(redis::ProtocolVersion::RESP3);
+                        }
+                        #[cfg(feature = "tls")]
+                        if let Some(ref tls) = self.tls {
+                            builder = builder
+                                .set_client_to_redis_tls_mode(redis::TlsMode::Secure)
+                                .set_client_to_redis_certificates(tls.certificates())
+                                .set_client_to_sentinel_certificates(tls.certificates());
+                        }
+
+                        let conn = SentinelConnection::connect(
+                            builder.build()?,
+                            self.manager_config(),
+                            self.client_cache.clone(),
+                        )
+                        .await?;
+                        Ok(RedisConnection::Sentinel(conn))
+                    }
                 }
             })
             .await
     }
+
+    /// Connection manager settings shared by single-node and Sentinel modes.
+    fn manager_config(&self) -> redis::aio::ConnectionManagerConfig {
+        let mut manager_config = redis::aio::ConnectionManagerConfig::new();
+
+        if let Some(timeout) = self.connection_timeout {
+            manager_config = manager_config.set_connection_timeout(Some(timeout));
+        }
+        if let Some(timeout) = self.response_timeout {
+            manager_config = manager_config.set_response_timeout(Some(timeout));
+        }
+        if let Some(retries) = self.number_of_retries {
+            manager_config = manager_config.set_number_of_retries(retries);
+        }
+        if let Some(cache) = self.client_cache.clone() {
+            manager_config = manager_config.set_push_sender(move |push| {
+                cache.handle_push(push);
+                Ok::<_, std::convert::Infallible>(())
+            });
+        }
+        manager_config
+    }
+
+    /// Serializes `key` and prepends the namespace, if any.
+    fn storage_key(&self, key: &CacheKey) -> Result<Vec<u8>, FormatError> {
+        let key = self.key_format.serialize(key)?;
+        Ok(match &self.namespace {
+            Some(namespace) => [namespace.as_bytes(), b":", &key].concat(),
+            None => key,
+        })
+    }
+
+    /// Fetches and reassembles the chunks described by an encoded manifest.
+    ///
+    /// Returns `None` for an invalid manifest or a torn value, which happens
+    /// when the entry is overwritten or expires between the two requests.
+    async fn read_chunks(
+        con: &mut RedisConnection,
+        cache_key: &[u8],
+        manifest: &[u8],
+    ) -> Result<Option<Bytes>, Error> {
+        let Some(manifest) = Manifest::decode(manifest) else {
+            tracing::warn!("Invalid chunk manifest in Redis entry");
+            return Ok(None);
+        };
+
+        let mut pipe = redis::pipe();
+        for field in manifest.fields() {
+            pipe.cmd("HGET").arg(cache_key).arg(field);
+        }
+        let chunks: Vec<Option<Vec<u8>>> = con.query_pipeline(&pipe).await?;
+
+        let data = manifest.reassemble(chunks);
+        if data.is_none() {
+            tracing::debug!("Chunked Redis entry changed while reading, treating as a miss");
+        }
+        Ok(data)
+    }
+
+    /// Enables tracking on `con` if needed and returns the near-cache epoch
+    /// to read under.
+    ///
+    /// Tracking is lost when the connection is re-established, so it is
+    /// checked before every uncached read.
+    async fn track(con: &mut RedisConnection, cache: &ClientCache) -> Result<u64, Error> {
+        if !cache.is_tracking() {
+            con.query_cmd::<()>(redis::cmd("CLIENT").arg("TRACKING").arg("ON"))
+                .await?;
+            cache.set_tracking();
+        }
+        Ok(cache.epoch())
+    }
 }
 
 /// Builder for creating and configuring a [`RedisBackend`].
@@ -538,6 +844,7 @@ where
     mode: Option<ConnectionMode>,
     serializer: S,
     key_format: CacheKeyFormat,
+    namespace: Option<String>,
     compressor: C,
     label: BackendLabel,
     // Common connection options
@@ -547,6 +854,12 @@ where
     // Authentication
     username: Option<String>,
     password: Option<String>,
+    // Client-side caching
+    client_cache_capacity: Option<usize>,
+    expiry: ExpiryMode,
+    chunk_size: Option<usize>,
+    #[cfg(feature = "tls")]
+    tls: Option<TlsConfig>,
 }
 
 impl Default for RedisBackendBuilder<BincodeFormat, PassthroughCompressor> {
@@ -555,6 +868,7 @@ impl Default for RedisBackendBuilder<BincodeFormat, PassthroughCompressor> {
             mode: None,
             serializer: BincodeFormat,
             key_format: CacheKeyFormat::default(),
+            namespace: None,
             compressor: PassthroughCompressor,
             label: BackendLabel::new_static("redis"),
             connection_timeout: None,
@@ -562,6 +876,11 @@ impl Default for RedisBackendBuilder<BincodeFormat, PassthroughCompressor> {
             number_of_retries: None,
             username: None,
             password: None,
+            client_cache_capacity: None,
+            expiry: ExpiryMode::default(),
+            chunk_size: None,
+            #[cfg(feature = "tls")]
+            tls: None,
         }
     }
 }
@@ -651,6 +970,103 @@ where
         self
     }
 
+    /// Enables server-assisted client-side caching (single-node only).
+    ///
+    /// The backend keeps up to `capacity` recently read entries in process
+    /// and serves repeated reads without a round-trip. The connection uses
+    /// RESP3 with `CLIENT TRACKING ON`, so Redis pushes an invalidation
+    /// whenever any client modifies or expires a cached key, and the entry
+    /// is dropped. This keeps the near-cache coherent across instances
+    /// without a separate pub/sub channel.
+    ///
+    /// # Default
+    ///
+    /// Disabled.
+    ///
+    /// # Caveats
+    ///
+    /// - Requires Redis 6 or newer
+    /// - Invalidations are asynchronous: another instance may serve the old
+    ///   value for the time it takes the push to arrive
+    /// - The near-cache is cleared when the connection is re-established
+    /// - This option is silently ignored for cluster mode
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hitbox_redis::{RedisBackend, ConnectionMode};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let backend = RedisBackend::builder()
+    ///     .connection(ConnectionMode::single("redis://localhost:6379/"))
+    ///     .client_side_cache(10_000)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn client_side_cache(mut self, capacity: usize) -> Self {
+        self.client_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Enables TLS for every connection.
+    ///
+    /// Plain `redis://` URLs are upgraded to TLS, and the CA and client
+    /// certificates of `tls` apply to Redis nodes and Sentinel nodes alike.
+    ///
+    /// # Default
+    ///
+    /// TLS only for `rediss://` URLs, trusting the platform's root certificates.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use hitbox_redis::{ConnectionMode, RedisBackend, TlsConfig};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let backend = RedisBackend::builder()
+    ///     .connection(ConnectionMode::single("rediss://redis.internal:6380/"))
+    ///     .tls(TlsConfig::new().ca_cert(std::fs::read("ca.pem")?))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tls")))]
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets how the expiry of written entries is set.
+    ///
+    /// # Default
+    ///
+    /// [`ExpiryMode::Relative`]
+    pub fn expiry(mut self, expiry: ExpiryMode) -> Self {
+        self.expiry = expiry;
+        self
+    }
+
+    /// Splits values larger than `bytes` into chunks of at most `bytes` each.
+    ///
+    /// Each chunk is stored in its own hash field, next to a manifest with
+    /// the chunk count and a CRC32 of the value, and all of them are written
+    /// in one transaction. Reads fetch the chunks with one pipelined request
+    /// and treat a value failing the checksum as a miss.
+    ///
+    /// Keeps multi-megabyte values from exceeding the bulk size limits of
+    /// proxies in front of Redis. Chunked entries are read correctly
+    /// whether or not chunking is enabled on the reading backend.
+    ///
+    /// # Default
+    ///
+    /// Disabled (values are stored in a single field)
+    pub fn chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = Some(bytes.max(1));
+        self
+    }
+
     /// Sets the cache value serialization format.
     ///
     /// The value format determines how cached data is serialized before storage.
@@ -676,6 +1092,7 @@ where
             mode: self.mode,
             serializer,
             key_format: self.key_format,
+            namespace: self.namespace,
             compressor: self.compressor,
             label: self.label,
             connection_timeout: self.connection_timeout,
@@ -683,6 +1100,11 @@ where
             number_of_retries: self.number_of_retries,
             username: self.username,
             password: self.password,
+            client_cache_capacity: self.client_cache_capacity,
+            expiry: self.expiry,
+            chunk_size: self.chunk_size,
+            #[cfg(feature = "tls")]
+            tls: self.tls,
         }
     }
 
@@ -708,6 +1130,19 @@ where
         self
     }
 
+    /// Prefixes every Redis key with `{namespace}:`.
+    ///
+    /// Lets several services share one Redis without key collisions, and
+    /// lists one service's keys with `SCAN 0 MATCH {namespace}:*`.
+    ///
+    /// # Default
+    ///
+    /// None (keys are stored as serialized by the key format)
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
     /// Sets a custom label for this backend.
     ///
     /// The label identifies this backend in multi-tier cache compositions and
@@ -757,6 +1192,7 @@ where
             mode: self.mode,
             serializer: self.serializer,
             key_format: self.key_format,
+            namespace: self.namespace,
             compressor,
             label: self.label,
             connection_timeout: self.connection_timeout,
@@ -764,6 +1200,11 @@ where
             number_of_retries: self.number_of_retries,
             username: self.username,
             password: self.password,
+            client_cache_capacity: self.client_cache_capacity,
+            expiry: self.expiry,
+            chunk_size: self.chunk_size,
+            #[cfg(feature = "tls")]
+            tls: self.tls,
         }
     }
 
@@ -781,6 +1222,12 @@ where
     pub fn build(self) -> Result<RedisBackend<S, C>, Error> {
         let mode = self.mode.ok_or(Error::MissingConnectionMode)?;
 
+        let client_cache = self
+            .client_cache_capacity
+            .map(|capacity| Arc::new(ClientCache::new(capacity)));
+        #[cfg(feature = "cluster")]
+        let client_cache = client_cache.filter(|_| !matches!(mode, ConnectionMode::Cluster(_)));
+
         Ok(RedisBackend {
             mode,
             connection_timeout: self.connection_timeout,
@@ -789,8 +1236,14 @@ where
             username: self.username,
             password: self.password,
             connection: OnceCell::new(),
+            client_cache,
+            expiry: self.expiry,
+            chunk_size: self.chunk_size,
+            #[cfg(feature = "tls")]
+            tls: self.tls,
             serializer: self.serializer,
             key_format: self.key_format,
+            namespace: self.namespace,
             compressor: self.compressor,
             label: self.label,
         })
@@ -804,27 +1257,47 @@ where
     C: Compressor + Send + Sync,
 {
     async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let cache_key = self.storage_key(key)?;
+        if let Some(value) = self
+            .client_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&cache_key))
+        {
+            return Ok(Some(value));
+        }
+
         let mut con = self.get_connection().await?.clone();
-        let cache_key = self.key_format.serialize(key)?;
+        let epoch = match &self.client_cache {
+            Some(cache) => Some(Self::track(&mut con, cache).await?),
+            None => None,
+        };
 
-        // Pipeline: HMGET (data, stale) + PTTL with typed decoding
-        let ((data, stale_ms), pttl): ((Option<Vec<u8>>, Option<i64>), i64) = con
+        // Pipeline: HMGET (data, stale, manifest) + PTTL with typed decoding
+        type Fields = (Option<Vec<u8>>, Option<i64>, Option<Vec<u8>>);
+        let ((data, stale_ms, manifest), pttl): (Fields, i64) = con
             .query_pipeline(
                 redis::pipe()
                     .cmd("HMGET")
                     .arg(&cache_key)
                     .arg("d")
                     .arg("s")
+                    .arg(MANIFEST_FIELD)
                     .cmd("PTTL")
                     .arg(&cache_key),
             )
             .await
             .map_err(Error::from)?;
 
-        // If data is None, key doesn't exist
-        let data = match data {
-            Some(data) => Bytes::from(data),
-            None => return Ok(None),
+        // If neither data nor a manifest is present, key doesn't exist
+        let data = match (data, manifest) {
+            (Some(data), _) => Bytes::from(data),
+            (None, Some(manifest)) => {
+                match Self::read_chunks(&mut con, &cache_key, &manifest).await? {
+                    Some(data) => data,
+                    None => return Ok(None),
+                }
+            }
+            (None, None) => return Ok(None),
         };
 
         // Convert stale millis to DateTime
@@ -834,42 +1307,86 @@ where
         // PTTL returns: -2 if key doesn't exist, -1 if no TTL, else milliseconds
         let expire = (pttl > 0).then(|| Utc::now() + chrono::Duration::milliseconds(pttl));
 
-        Ok(Some(CacheValue::new(data, expire, stale)))
+        let value = CacheValue::new(data, expire, stale);
+        if let (Some(cache), Some(epoch)) = (&self.client_cache, epoch) {
+            cache.insert(cache_key, value.clone(), epoch);
+        }
+        Ok(Some(value))
     }
 
     async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
         let mut con = self.get_connection().await?.clone();
-        let cache_key = self.key_format.serialize(key)?;
+        let cache_key = self.storage_key(key)?;
 
-        // Build HSET command with data field, optionally add stale field
+        // Build HSET command with data field (or the chunk manifest),
+        // optionally add stale field
+        let data = value.data().as_ref();
+        let chunk_size = self.chunk_size.filter(|&size| data.len() > size);
         let mut cmd = redis::cmd("HSET");
-        cmd.arg(&cache_key).arg("d").arg(value.data().as_ref());
+        cmd.arg(&cache_key);
+        match chunk_size {
+            Some(size) => cmd
+                .arg(MANIFEST_FIELD)
+                .arg(&Manifest::new(data, size).encode()),
+            None => cmd.arg("d").arg(data),
+        };
         if let Some(stale) = value.stale() {
             cmd.arg("s").arg(stale.timestamp_millis());
         }
 
-        // Pipeline: HSET + optional EXPIRE (computed from value.ttl())
+        // Transaction: DEL + HSET + optional PEXPIRE/PEXPIREAT. DEL drops the
+        // stale field and TTL of a previous entry; MULTI keeps readers from
+        // seeing the entry without its expiry.
         let mut pipe = redis::pipe();
+        pipe.atomic();
+        pipe.cmd("DEL").arg(&cache_key).ignore();
         pipe.add_command(cmd).ignore();
-        if let Some(ttl_duration) = value.ttl() {
-            pipe.cmd("EXPIRE")
+        // One HSET per chunk keeps each command under the chunk size
+        for (index, chunk) in chunk_size
+            .map(|size| data.chunks(size))
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            pipe.cmd("HSET")
                 .arg(&cache_key)
-                .arg(ttl_duration.as_secs())
+                .arg(chunk::field(index as u32))
+                .arg(chunk)
                 .ignore();
         }
+        if let Some(expire) = value.expire() {
+            match self.expiry {
+                ExpiryMode::Relative => {
+                    // An already expired entry gets the shortest possible TTL
+                    let ttl_ms = value.ttl().map_or(1, |ttl| ttl.as_millis().max(1));
+                    pipe.cmd("PEXPIRE").arg(&cache_key).arg(ttl_ms as u64)
+                }
+                ExpiryMode::Absolute => pipe
+                    .cmd("PEXPIREAT")
+                    .arg(&cache_key)
+                    .arg(expire.timestamp_millis()),
+            }
+            .ignore();
+        }
 
         con.query_pipeline::<()>(&pipe).await.map_err(Error::from)?;
+        if let Some(cache) = &self.client_cache {
+            cache.invalidate([cache_key.as_slice()]);
+        }
         Ok(())
     }
 
     async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
         let mut con = self.get_connection().await?.clone();
-        let cache_key = self.key_format.serialize(key)?;
+        let cache_key = self.storage_key(key)?;
 
         let deleted: i32 = con
-            .query_cmd(redis::cmd("DEL").arg(cache_key))
+            .query_cmd(redis::cmd("DEL").arg(&cache_key))
             .await
             .map_err(Error::from)?;
+        if let Some(cache) = &self.client_cache {
+            cache.invalidate([cache_key.as_slice()]);
+        }
 
         if deleted > 0 {
             Ok(DeleteStatus::Deleted(deleted as u32))
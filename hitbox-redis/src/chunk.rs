@@ -0,0 +1,158 @@
+//! Splitting large values across hash fields.
+//!
+//! A chunked entry has no `d` field. Instead it holds a manifest in `m` and
+//! the payload in fields `c0`, `c1`, ... of at most the configured chunk
+//! size each. The manifest records the chunk count, the total length and a
+//! CRC32 of the payload, so a reader can fetch the chunks in one pipeline
+//! and detect a torn or corrupted entry.
+
+use bytes::{Bytes, BytesMut};
+
+/// Hash field holding the encoded [`Manifest`].
+pub(crate) const MANIFEST_FIELD: &str = "m";
+
+/// Describes how a value was split into chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Manifest {
+    /// Number of chunk fields.
+    chunks: u32,
+    /// Length of the reassembled value in bytes.
+    len: u64,
+    /// CRC32 of the reassembled value.
+    checksum: u32,
+}
+
+impl Manifest {
+    const ENCODED_LEN: usize = 16;
+
+    /// Describes `data` split into chunks of `chunk_size` bytes.
+    pub(crate) fn new(data: &[u8], chunk_size: usize) -> Self {
+        Self {
+            chunks: data.len().div_ceil(chunk_size) as u32,
+            len: data.len() as u64,
+            checksum: crc32fast::hash(data),
+        }
+    }
+
+    /// Encodes the manifest as little-endian chunk count, length and checksum.
+    pub(crate) fn encode(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut buf = [0; Self::ENCODED_LEN];
+        buf[..4].copy_from_slice(&self.chunks.to_le_bytes());
+        buf[4..12].copy_from_slice(&self.len.to_le_bytes());
+        buf[12..].copy_from_slice(&self.checksum.to_le_bytes());
+        buf
+    }
+
+    /// Decodes a manifest written by [`encode`](Self::encode).
+    pub(crate) fn decode(data: &[u8]) -> Option<Self> {
+        let data: &[u8; Self::ENCODED_LEN] = data.try_into().ok()?;
+        Some(Self {
+            chunks: u32::from_le_bytes(data[..4].try_into().ok()?),
+            len: u64::from_le_bytes(data[4..12].try_into().ok()?),
+            checksum: u32::from_le_bytes(data[12..].try_into().ok()?),
+        })
+    }
+
+    /// Names of the chunk fields, in order.
+    pub(crate) fn fields(&self) -> impl Iterator<Item = String> {
+        (0..self.chunks).map(field)
+    }
+
+    /// Concatenates `chunks` into one buffer and verifies it.
+    ///
+    /// Returns `None` if a chunk is missing or the length or checksum do not
+    /// match, e.g. when the entry was overwritten between reads. The buffer is
+    /// sized from the fetched chunks, never from the stored length, and a
+    /// single chunk is returned without copying.
+    pub(crate) fn reassemble(&self, chunks: Vec<Option<Vec<u8>>>) -> Option<Bytes> {
+        if chunks.len() != self.chunks as usize {
+            return None;
+        }
+        let chunks: Vec<Vec<u8>> = chunks.into_iter().collect::<Option<_>>()?;
+        let len: usize = chunks.iter().map(Vec::len).sum();
+        if len as u64 != self.len {
+            return None;
+        }
+        let data = match <[Vec<u8>; 1]>::try_from(chunks) {
+            Ok([chunk]) => Bytes::from(chunk),
+            Err(chunks) => {
+                let mut data = BytesMut::with_capacity(len);
+                for chunk in chunks {
+                    data.extend_from_slice(&chunk);
+                }
+                data.freeze()
+            }
+        };
+        (crc32fast::hash(&data) == self.checksum).then_some(data)
+    }
+}
+
+/// Name of the hash field holding chunk `index`.
+pub(crate) fn field(index: u32) -> String {
+    format!("c{index}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(data: &[u8], chunk_size: usize) -> Vec<Option<Vec<u8>>> {
+        data.chunks(chunk_size)
+            .map(|chunk| Some(chunk.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let manifest = Manifest::new(&[7; 10], 4);
+        assert_eq!(manifest.chunks, 3);
+        assert_eq!(Manifest::decode(&manifest.encode()), Some(manifest));
+        assert_eq!(Manifest::decode(b"short"), None);
+        assert_eq!(manifest.fields().collect::<Vec<_>>(), ["c0", "c1", "c2"]);
+    }
+
+    #[test]
+    fn test_reassemble() {
+        let data: Vec<u8> = (0..=255).collect();
+        let manifest = Manifest::new(&data, 100);
+
+        let reassembled = manifest.reassemble(split(&data, 100)).unwrap();
+        assert_eq!(reassembled.as_ref(), data.as_slice());
+    }
+
+    #[test]
+    fn test_reassemble_rejects_torn_value() {
+        let data: Vec<u8> = (0..=255).collect();
+        let manifest = Manifest::new(&data, 100);
+
+        let mut missing = split(&data, 100);
+        missing[1] = None;
+        assert!(manifest.reassemble(missing).is_none());
+
+        let mut corrupted = split(&data, 100);
+        corrupted[2] = Some(vec![0; 56]);
+        assert!(manifest.reassemble(corrupted).is_none());
+
+        assert!(manifest.reassemble(split(&data, 128)).is_none());
+    }
+
+    #[test]
+    fn test_reassemble_single_chunk() {
+        let data = b"small value".to_vec();
+        let manifest = Manifest::new(&data, 100);
+        assert_eq!(manifest.chunks, 1);
+
+        let reassembled = manifest.reassemble(vec![Some(data.clone())]).unwrap();
+        assert_eq!(reassembled.as_ref(), data.as_slice());
+    }
+
+    #[test]
+    fn test_reassemble_ignores_bogus_length() {
+        let data = b"payload".to_vec();
+        let mut encoded = Manifest::new(&data, 100).encode();
+        encoded[4..12].copy_from_slice(&u64::MAX.to_le_bytes());
+        let manifest = Manifest::decode(&encoded).unwrap();
+
+        assert!(manifest.reassemble(vec![Some(data)]).is_none());
+    }
+}
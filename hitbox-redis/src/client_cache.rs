@@ -0,0 +1,206 @@
+//! In-process near-cache kept coherent by Redis client-side caching.
+//!
+//! With RESP3 and `CLIENT TRACKING ON`, Redis remembers the keys read by a
+//! connection and pushes an `invalidate` message when any client modifies
+//! them. [`ClientCache`] holds the values read through that connection and
+//! drops them on those pushes.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use chrono::Utc;
+use hitbox::{CacheValue, Raw};
+use redis::{PushInfo, PushKind, Value};
+
+/// Near-cache of values read from Redis, keyed by the serialized Redis key.
+pub(crate) struct ClientCache {
+    capacity: usize,
+    state: Mutex<State>,
+    /// Incremented on every invalidation, so reads that raced one are not cached.
+    epoch: AtomicU64,
+    /// Whether tracking is enabled on the current connection.
+    tracking: AtomicBool,
+}
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<Vec<u8>, CacheValue<Raw>>,
+    /// Keys in insertion order, for eviction. May hold keys already dropped.
+    order: VecDeque<Vec<u8>>,
+}
+
+impl ClientCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::default(),
+            epoch: AtomicU64::new(0),
+            tracking: AtomicBool::new(false),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("client cache poisoned")
+    }
+
+    /// Returns the cached value of `key` unless it has expired.
+    pub(crate) fn get(&self, key: &[u8]) -> Option<CacheValue<Raw>> {
+        let mut state = self.lock();
+        let value = state.entries.get(key)?;
+        if value.expire().is_some_and(|expire| expire <= Utc::now()) {
+            state.entries.remove(key);
+            return None;
+        }
+        Some(value.clone())
+    }
+
+    /// Returns the current epoch, to be passed to [`insert`](Self::insert).
+    pub(crate) fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Acquire)
+    }
+
+    /// Caches `value` read at `epoch`, unless an invalidation happened since.
+    pub(crate) fn insert(&self, key: Vec<u8>, value: CacheValue<Raw>, epoch: u64) {
+        let mut state = self.lock();
+        if !self.is_tracking() || self.epoch() != epoch {
+            return;
+        }
+        while state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+            let Some(oldest) = state.order.pop_front() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        if state.entries.insert(key.clone(), value).is_none() {
+            state.order.push_back(key);
+        }
+        if state.order.len() > 2 * self.capacity {
+            let State { entries, order } = &mut *state;
+            order.retain(|key| entries.contains_key(key));
+        }
+    }
+
+    /// Drops `keys`.
+    pub(crate) fn invalidate<'a>(&self, keys: impl IntoIterator<Item = &'a [u8]>) {
+        let mut state = self.lock();
+        self.epoch.fetch_add(1, Ordering::AcqRel);
+        for key in keys {
+            state.entries.remove(key);
+        }
+    }
+
+    /// Drops every entry.
+    pub(crate) fn clear(&self) {
+        let mut state = self.lock();
+        self.epoch.fetch_add(1, Ordering::AcqRel);
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    pub(crate) fn is_tracking(&self) -> bool {
+        self.tracking.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn set_tracking(&self) {
+        self.tracking.store(true, Ordering::Release);
+    }
+
+    /// Stops caching until tracking is enabled on a new connection.
+    ///
+    /// Tracking does not survive a reconnect, so everything cached so far
+    /// may miss invalidations.
+    pub(crate) fn reset(&self) {
+        self.tracking.store(false, Ordering::Release);
+        self.clear();
+    }
+
+    /// Applies a push message received on the tracked connection.
+    pub(crate) fn handle_push(&self, push: PushInfo) {
+        match push.kind {
+            PushKind::Invalidate => match push.data.first() {
+                Some(Value::Array(keys)) => {
+                    self.invalidate(keys.iter().filter_map(|key| match key {
+                        Value::BulkString(key) => Some(key.as_slice()),
+                        _ => None,
+                    }))
+                }
+                // A nil key list is sent on FLUSHALL and FLUSHDB.
+                _ => self.clear(),
+            },
+            PushKind::Disconnection => self.reset(),
+            _ => {}
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    fn value(data: &'static [u8]) -> CacheValue<Raw> {
+        CacheValue::new(Bytes::from_static(data), None, None)
+    }
+
+    fn tracked(capacity: usize) -> ClientCache {
+        let cache = ClientCache::new(capacity);
+        cache.set_tracking();
+        cache
+    }
+
+    #[test]
+    fn test_invalidate_push_drops_keys() {
+        let cache = tracked(10);
+        cache.insert(b"a".to_vec(), value(b"1"), cache.epoch());
+        cache.insert(b"b".to_vec(), value(b"2"), cache.epoch());
+
+        cache.handle_push(PushInfo {
+            kind: PushKind::Invalidate,
+            data: vec![Value::Array(vec![Value::BulkString(b"a".to_vec())])],
+        });
+
+        assert!(cache.get(b"a").is_none());
+        assert!(cache.get(b"b").is_some());
+    }
+
+    #[test]
+    fn test_insert_after_invalidation_is_skipped() {
+        let cache = tracked(10);
+        let epoch = cache.epoch();
+        cache.invalidate([b"a".as_slice()]);
+        cache.insert(b"a".to_vec(), value(b"stale"), epoch);
+        assert!(cache.get(b"a").is_none());
+    }
+
+    #[test]
+    fn test_disconnection_clears_and_stops_caching() {
+        let cache = tracked(10);
+        cache.insert(b"a".to_vec(), value(b"1"), cache.epoch());
+
+        cache.handle_push(PushInfo {
+            kind: PushKind::Disconnection,
+            data: vec![],
+        });
+        assert!(cache.get(b"a").is_none());
+
+        cache.insert(b"a".to_vec(), value(b"1"), cache.epoch());
+        assert!(cache.get(b"a").is_none());
+    }
+
+    #[test]
+    fn test_evicts_oldest_at_capacity() {
+        let cache = tracked(2);
+        for key in [b"a", b"b", b"c"] {
+            cache.insert(key.to_vec(), value(b"v"), cache.epoch());
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(b"a").is_none());
+        assert!(cache.get(b"c").is_some());
+    }
+}
@@ -0,0 +1,119 @@
+//! Redis pub/sub transport for L1 invalidation.
+//!
+//! [`RedisInvalidationBus`] carries invalidations between the instances of a
+//! [`CompositionBackend`] sharing a Redis L2. Messages are published with
+//! `PUBLISH` on a single channel; each instance holds one subscriber
+//! connection.
+//!
+//! Redis pub/sub is fire-and-forget: instances that are disconnected while a
+//! message is published never receive it.
+//!
+//! [`CompositionBackend`]: hitbox_backend::CompositionBackend
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use hitbox_backend::BackendResult;
+use hitbox_backend::composition::invalidation::{
+    InvalidationBus, InvalidationMessage, InvalidationStream,
+};
+use redis::Client;
+use redis::aio::ConnectionManager;
+use tokio::sync::OnceCell;
+
+use crate::error::Error;
+
+/// Default pub/sub channel name.
+const DEFAULT_CHANNEL: &str = "hitbox:invalidation";
+
+/// Invalidation bus over Redis pub/sub.
+///
+/// The publishing connection is established lazily on the first publish.
+/// Every [`subscribe`](InvalidationBus::subscribe) opens a dedicated
+/// subscriber connection.
+///
+/// # Examples
+///
+/// ```no_run
+/// use hitbox_redis::RedisInvalidationBus;
+///
+/// # fn main() -> Result<(), hitbox_redis::error::Error> {
+/// let bus = RedisInvalidationBus::new("redis://127.0.0.1:6379/")?
+///     .channel("myapp:invalidation");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RedisInvalidationBus {
+    client: Client,
+    channel: String,
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisInvalidationBus {
+    /// Creates a bus for the Redis server at `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Redis`] if the URL is invalid.
+    pub fn new(url: &str) -> Result<Self, Error> {
+        Ok(Self {
+            client: Client::open(url)?,
+            channel: DEFAULT_CHANNEL.to_owned(),
+            connection: OnceCell::new(),
+        })
+    }
+
+    /// Sets the pub/sub channel (default: `hitbox:invalidation`).
+    ///
+    /// Instances exchange invalidations only when they use the same channel.
+    #[must_use]
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.channel = channel.into();
+        self
+    }
+
+    async fn get_connection(&self) -> Result<&ConnectionManager, Error> {
+        self.connection
+            .get_or_try_init(|| async { Ok(self.client.get_connection_manager().await?) })
+            .await
+    }
+}
+
+impl std::fmt::Debug for RedisInvalidationBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisInvalidationBus")
+            .field("channel", &self.channel)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl InvalidationBus for RedisInvalidationBus {
+    async fn publish(&self, message: InvalidationMessage) -> BackendResult<()> {
+        let payload = message.encode()?;
+        let mut con = self.get_connection().await?.clone();
+        redis::cmd("PUBLISH")
+            .arg(&self.channel)
+            .arg(payload)
+            .exec_async(&mut con)
+            .await
+            .map_err(Error::from)?;
+        Ok(())
+    }
+
+    async fn subscribe(&self) -> BackendResult<InvalidationStream> {
+        let mut pubsub = self.client.get_async_pubsub().await.map_err(Error::from)?;
+        pubsub.subscribe(&self.channel).await.map_err(Error::from)?;
+
+        let stream = pubsub.into_on_message().filter_map(|msg| async move {
+            match InvalidationMessage::decode(msg.get_payload_bytes()) {
+                Ok(message) => Some(message),
+                Err(error) => {
+                    tracing::warn!(?error, "Skipping malformed invalidation message");
+                    None
+                }
+            }
+        });
+        Ok(stream.boxed())
+    }
+}
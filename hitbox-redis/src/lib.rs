@@ -3,11 +3,30 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod backend;
+mod chunk;
+mod client_cache;
 pub mod error;
+pub mod invalidation;
+#[cfg(feature = "sentinel")]
+mod sentinel;
+#[cfg(feature = "tls")]
+mod tls;
 
 #[doc(inline)]
-pub use crate::backend::{ConnectionMode, RedisBackend, RedisBackendBuilder, SingleConfig};
+pub use crate::backend::{
+    ConnectionMode, ExpiryMode, RedisBackend, RedisBackendBuilder, SingleConfig,
+};
+#[doc(inline)]
+pub use crate::invalidation::RedisInvalidationBus;
 
 #[cfg(feature = "cluster")]
 #[doc(inline)]
 pub use crate::backend::ClusterConfig;
+
+#[cfg(feature = "sentinel")]
+#[doc(inline)]
+pub use crate::backend::SentinelConfig;
+
+#[cfg(feature = "tls")]
+#[doc(inline)]
+pub use crate::tls::TlsConfig;
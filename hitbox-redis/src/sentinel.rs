@@ -0,0 +1,93 @@
+//! Connection to the master of a Sentinel-managed deployment.
+
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+
+use redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use redis::sentinel::SentinelClient;
+use redis::{ErrorKind, RedisError, RedisResult, ServerErrorKind};
+use tokio::sync::Mutex;
+
+use crate::client_cache::ClientCache;
+
+/// Connection to the current master, re-resolved through Sentinel on failover.
+///
+/// A [`ConnectionManager`] reconnects to the address it was created for, so
+/// after a failover it would keep talking to the demoted master. When a
+/// command fails with a connection or `READONLY` error, the master is looked
+/// up again and the command is retried once on the new connection.
+#[derive(Clone)]
+pub(crate) struct SentinelConnection {
+    sentinel: Arc<Mutex<SentinelClient>>,
+    config: ConnectionManagerConfig,
+    current: Arc<RwLock<ConnectionManager>>,
+    client_cache: Option<Arc<ClientCache>>,
+}
+
+impl SentinelConnection {
+    /// Resolves the master and connects to it.
+    pub(crate) async fn connect(
+        mut sentinel: SentinelClient,
+        config: ConnectionManagerConfig,
+        client_cache: Option<Arc<ClientCache>>,
+    ) -> RedisResult<Self> {
+        let manager = Self::connect_master(&mut sentinel, &config).await?;
+        Ok(Self {
+            sentinel: Arc::new(Mutex::new(sentinel)),
+            config,
+            current: Arc::new(RwLock::new(manager)),
+            client_cache,
+        })
+    }
+
+    async fn connect_master(
+        sentinel: &mut SentinelClient,
+        config: &ConnectionManagerConfig,
+    ) -> RedisResult<ConnectionManager> {
+        let client = sentinel.async_get_client().await?;
+        client
+            .get_connection_manager_with_config(config.clone())
+            .await
+    }
+
+    fn current(&self) -> ConnectionManager {
+        self.current
+            .read()
+            .expect("sentinel connection poisoned")
+            .clone()
+    }
+
+    /// Runs `op` on the master, retrying once after a failover.
+    pub(crate) async fn run<T, F, Fut>(&self, mut op: F) -> RedisResult<T>
+    where
+        F: FnMut(ConnectionManager) -> Fut,
+        Fut: Future<Output = RedisResult<T>>,
+    {
+        match op(self.current()).await {
+            Err(error) if Self::is_failover(&error) => {
+                tracing::warn!(?error, "Redis master unavailable, asking Sentinel");
+                self.reconnect().await?;
+                op(self.current()).await
+            }
+            result => result,
+        }
+    }
+
+    fn is_failover(error: &RedisError) -> bool {
+        error.is_io_error()
+            || error.is_connection_dropped()
+            || error.is_connection_refusal()
+            || error.kind() == ErrorKind::Server(ServerErrorKind::ReadOnly)
+    }
+
+    async fn reconnect(&self) -> RedisResult<()> {
+        let mut sentinel = self.sentinel.lock().await;
+        let manager = Self::connect_master(&mut sentinel, &self.config).await?;
+        *self.current.write().expect("sentinel connection poisoned") = manager;
+        // Tracking is per connection and does not carry over to the new master.
+        if let Some(cache) = &self.client_cache {
+            cache.reset();
+        }
+        Ok(())
+    }
+}
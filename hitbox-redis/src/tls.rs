@@ -0,0 +1,99 @@
+//! TLS settings for Redis connections.
+
+use redis::{ConnectionAddr, ConnectionInfo, TlsCertificates};
+
+/// TLS settings for [`RedisBackend`](crate::RedisBackend) connections.
+///
+/// Setting TLS on the builder makes every connection use TLS, including
+/// `redis://` URLs and Sentinel nodes. Without a CA certificate, the
+/// platform's native root certificates are trusted.
+///
+/// Certificates and keys are PEM encoded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use hitbox_redis::{ConnectionMode, RedisBackend, TlsConfig};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let tls = TlsConfig::new()
+///     .ca_cert(std::fs::read("ca.pem")?)
+///     .client_cert(std::fs::read("client.pem")?, std::fs::read("client.key")?);
+///
+/// let backend = RedisBackend::builder()
+///     .connection(ConnectionMode::single("rediss://redis.internal:6380/"))
+///     .tls(tls)
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    ca_cert: Option<Vec<u8>>,
+    client_cert: Option<Vec<u8>>,
+    client_key: Option<Vec<u8>>,
+}
+
+impl TlsConfig {
+    /// Creates TLS settings trusting the platform's root certificates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trusts only the given CA certificate instead of the platform roots.
+    pub fn ca_cert(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_cert = Some(pem.into());
+        self
+    }
+
+    /// Authenticates with a client certificate chain and its private key (mTLS).
+    pub fn client_cert(
+        mut self,
+        cert_pem: impl Into<Vec<u8>>,
+        key_pem: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.client_cert = Some(cert_pem.into());
+        self.client_key = Some(key_pem.into());
+        self
+    }
+
+    pub(crate) fn certificates(&self) -> TlsCertificates {
+        TlsCertificates {
+            client_tls: self.client_cert.clone().zip(self.client_key.clone()).map(
+                |(client_cert, client_key)| redis::ClientTlsConfig {
+                    client_cert,
+                    client_key,
+                },
+            ),
+            root_cert: self.ca_cert.clone(),
+        }
+    }
+
+    /// Switches a plain TCP address to TLS.
+    pub(crate) fn upgrade(addr: ConnectionAddr) -> ConnectionAddr {
+        match addr {
+            ConnectionAddr::Tcp(host, port) => ConnectionAddr::TcpTls {
+                host,
+                port,
+                insecure: false,
+                tls_params: None,
+            },
+            addr => addr,
+        }
+    }
+
+    /// Switches the address of `info` to TLS.
+    pub(crate) fn upgrade_info(info: ConnectionInfo) -> ConnectionInfo {
+        let addr = Self::upgrade(info.addr().clone());
+        info.set_addr(addr)
+    }
+}
+
+impl std::fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("ca_cert", &self.ca_cert.is_some())
+            .field("client_cert", &self.client_cert.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,646 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hitbox_backend::format::{Format, JsonFormat};
+use hitbox_backend::{
+    Backend, BackendError, BackendResult, CacheKeyFormat, Compressor, DeleteStatus,
+    PassthroughCompressor,
+};
+use hitbox_core::{BackendLabel, CacheKey, CacheValue, Raw};
+
+use crate::SqliteError;
+use crate::db::{Database, Entry};
+use crate::sweeper::Sweeper;
+
+/// Embedded cache backend storing entries in a SQLite table.
+///
+/// Use this when cache data must survive restarts and be inspectable with
+/// the `sqlite3` shell or any other SQLite tooling. Each entry is a row:
+///
+/// ```sql
+/// CREATE TABLE hitbox_cache (
+///     key BLOB PRIMARY KEY,
+///     prefix TEXT NOT NULL,       -- CacheKey prefix, for remove_prefix
+///     data BLOB NOT NULL,
+///     expire INTEGER,             -- Unix milliseconds
+///     stale INTEGER,              -- Unix milliseconds
+///     last_access INTEGER NOT NULL
+/// );
+/// ```
+///
+/// The database runs in WAL mode, so external readers do not block the
+/// cache. A background thread deletes expired rows and, with
+/// [`max_size`](SqliteBackendBuilder::max_size), evicts the least recently
+/// accessed rows until the database fits.
+///
+/// ```no_run
+/// use hitbox_sqlite::SqliteBackend;
+///
+/// let backend = SqliteBackend::builder()
+///     .path("/var/cache/myapp/cache.sqlite")
+///     .max_size(2 * 1024 * 1024 * 1024) // 2 GB
+///     .build()?;
+/// # Ok::<(), hitbox_sqlite::SqliteError>(())
+/// ```
+///
+/// Clones share one connection, so their statements run one at a time, and
+/// one sweeper thread, which exits once the last clone is dropped.
+#[derive(Clone)]
+pub struct SqliteBackend<S = JsonFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    db: Arc<Database>,
+    max_size: Option<u64>,
+    _sweeper: Arc<Sweeper>,
+    key_format: CacheKeyFormat,
+    serializer: S,
+    compressor: C,
+    label: BackendLabel,
+}
+
+impl<S, C> SqliteBackend<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Reads several entries in one transaction.
+    ///
+    /// Returns one result per key, in order.
+    pub async fn read_many(
+        &self,
+        keys: &[CacheKey],
+    ) -> BackendResult<Vec<Option<CacheValue<Raw>>>> {
+        let keys = self.storage_keys(keys)?;
+        self.blocking(move |db| db.get_many(&keys, Utc::now().timestamp_millis()))
+            .await
+    }
+
+    /// Writes several entries in one transaction.
+    pub async fn write_many(
+        &self,
+        entries: impl IntoIterator<Item = (CacheKey, CacheValue<Raw>)>,
+    ) -> BackendResult<()> {
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| {
+                Ok(Entry {
+                    key: self.key_format.serialize(&key)?,
+                    prefix: key.prefix().to_string(),
+                    value,
+                })
+            })
+            .collect::<Result<Vec<_>, SqliteError>>()?;
+        self.blocking(move |db| db.put_many(&entries, Utc::now().timestamp_millis()))
+            .await
+    }
+
+    /// Removes several entries in one transaction.
+    ///
+    /// Returns the number of entries that existed.
+    pub async fn remove_many(&self, keys: &[CacheKey]) -> BackendResult<usize> {
+        let keys = self.storage_keys(keys)?;
+        self.blocking(move |db| db.delete_many(&keys)).await
+    }
+
+    /// Removes every entry whose key has the given [`CacheKey::prefix`].
+    ///
+    /// Returns the number of removed entries.
+    pub async fn remove_prefix(&self, prefix: impl Into<String>) -> BackendResult<usize> {
+        let prefix = prefix.into();
+        self.blocking(move |db| db.delete_prefix(&prefix)).await
+    }
+
+    /// Runs a sweep now instead of waiting for the background thread.
+    ///
+    /// Deletes expired rows, then evicts least recently accessed rows while
+    /// the database exceeds `max_size`. Returns the number of deleted rows.
+    pub async fn sweep(&self) -> BackendResult<usize> {
+        let max_size = self.max_size;
+        self.blocking(move |db| db.sweep(Utc::now().timestamp_millis(), max_size))
+            .await
+    }
+
+    /// Bytes of the database file in use, excluding free pages.
+    pub async fn size(&self) -> BackendResult<u64> {
+        self.blocking(|db| db.used_size()).await
+    }
+
+    fn storage_keys(&self, keys: &[CacheKey]) -> Result<Vec<Vec<u8>>, SqliteError> {
+        keys.iter()
+            .map(|key| Ok(self.key_format.serialize(key)?))
+            .collect()
+    }
+
+    /// Runs `f` on the blocking thread pool.
+    async fn blocking<T, F>(&self, f: F) -> BackendResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Database) -> Result<T, SqliteError> + Send + 'static,
+    {
+        let db = self.db.clone();
+        tokio::task::spawn_blocking(move || f(&db))
+            .await
+            .map_err(|e| BackendError::InternalError(Box::new(e)))?
+            .map_err(BackendError::from)
+    }
+}
+
+impl SqliteBackend<JsonFormat, PassthroughCompressor> {
+    /// Starts building a new backend.
+    pub fn builder() -> SqliteBackendBuilder<JsonFormat, PassthroughCompressor> {
+        SqliteBackendBuilder::default()
+    }
+
+    /// In-memory backend for tests.
+    ///
+    /// Data is lost when dropped. Equivalent to `builder().build()`.
+    ///
+    /// ```
+    /// use hitbox_sqlite::SqliteBackend;
+    ///
+    /// let backend = SqliteBackend::in_memory()
+    ///     .expect("Failed to create in-memory backend");
+    /// ```
+    pub fn in_memory() -> Result<Self, SqliteError> {
+        Self::builder().build()
+    }
+}
+
+/// Builder for [`SqliteBackend`].
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use hitbox_sqlite::SqliteBackend;
+/// use hitbox_backend::format::BincodeFormat;
+///
+/// let backend = SqliteBackend::builder()
+///     .path("/var/cache/myapp/cache.sqlite")
+///     .table("responses")
+///     .max_size(512 * 1024 * 1024)  // 512 MB
+///     .sweep_interval(Duration::from_secs(10))
+///     .value_format(BincodeFormat)
+///     .build()?;
+/// # Ok::<(), hitbox_sqlite::SqliteError>(())
+/// ```
+pub struct SqliteBackendBuilder<S = JsonFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    path: Option<PathBuf>,
+    table: String,
+    max_size: Option<u64>,
+    sweep_interval: Duration,
+    key_format: CacheKeyFormat,
+    serializer: S,
+    compressor: C,
+    label: BackendLabel,
+}
+
+impl Default for SqliteBackendBuilder<JsonFormat, PassthroughCompressor> {
+    fn default() -> Self {
+        Self {
+            path: None,
+            table: "hitbox_cache".to_string(),
+            max_size: None,
+            sweep_interval: Duration::from_secs(30),
+            key_format: CacheKeyFormat::Bitcode,
+            serializer: JsonFormat,
+            compressor: PassthroughCompressor,
+            label: BackendLabel::new_static("sqlite"),
+        }
+    }
+}
+
+impl<S, C> SqliteBackendBuilder<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Stores the cache in the database file at `path`, creating it if needed.
+    ///
+    /// Without this, data lives only in memory and is lost on restart.
+    /// If path is a directory, creates `cache.sqlite` inside it.
+    pub fn path(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Name of the table holding cache entries.
+    ///
+    /// Lets several caches share one database file. Only ASCII letters,
+    /// digits and underscores are allowed.
+    ///
+    /// Default: `hitbox_cache`
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = table.into();
+        self
+    }
+
+    /// Caps the database size, evicting least recently accessed entries.
+    ///
+    /// Enforced by the sweeper, so the database can exceed the limit until
+    /// the next sweep. Access times are tracked with one-second resolution.
+    ///
+    /// Default: unbounded
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// How often the sweeper deletes expired entries and enforces
+    /// [`max_size`](Self::max_size).
+    ///
+    /// Expired entries are never returned, only their disk space waits
+    /// for the sweep. [`build`](Self::build) rejects a zero interval.
+    ///
+    /// Default: 30 seconds
+    pub fn sweep_interval(mut self, interval: Duration) -> Self {
+        self.sweep_interval = interval;
+        self
+    }
+
+    /// Cache key serialization format. Rarely needs changing.
+    ///
+    /// [`CacheKeyFormat::UrlEncoded`] keeps the `key` column readable.
+    pub fn key_format(mut self, format: CacheKeyFormat) -> Self {
+        self.key_format = format;
+        self
+    }
+
+    /// Identifies this backend in multi-tier setups and metrics.
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Value serialization format.
+    ///
+    /// `JsonFormat` (default) keeps the `data` column readable from the
+    /// `sqlite3` shell. `BincodeFormat` is faster and more compact.
+    pub fn value_format<NewS>(self, serializer: NewS) -> SqliteBackendBuilder<NewS, C>
+    where
+        NewS: Format,
+    {
+        SqliteBackendBuilder {
+            path: self.path,
+            table: self.table,
+            max_size: self.max_size,
+            sweep_interval: self.sweep_interval,
+            key_format: self.key_format,
+            serializer,
+            compressor: self.compressor,
+            label: self.label,
+        }
+    }
+
+    /// Compression for cached values.
+    pub fn compressor<NewC>(self, compressor: NewC) -> SqliteBackendBuilder<S, NewC>
+    where
+        NewC: Compressor,
+    {
+        SqliteBackendBuilder {
+            path: self.path,
+            table: self.table,
+            max_size: self.max_size,
+            sweep_interval: self.sweep_interval,
+            key_format: self.key_format,
+            serializer: self.serializer,
+            compressor,
+            label: self.label,
+        }
+    }
+
+    /// Creates the backend and starts the sweeper thread.
+    ///
+    /// Fails if the database can't be opened, the table name is invalid or
+    /// the sweep interval is zero.
+    pub fn build(self) -> Result<SqliteBackend<S, C>, SqliteError> {
+        if self.sweep_interval.is_zero() {
+            return Err(SqliteError::InvalidConfig(
+                "sweep interval must be non-zero".to_string(),
+            ));
+        }
+        let path = self.path.map(|mut path| {
+            if path.is_dir() {
+                path.push("cache.sqlite");
+            }
+            path
+        });
+        let db = Arc::new(Database::open(path.as_deref(), &self.table)?);
+        let sweeper = Sweeper::spawn(db.clone(), self.sweep_interval, self.max_size)?;
+
+        Ok(SqliteBackend {
+            db,
+            max_size: self.max_size,
+            _sweeper: Arc::new(sweeper),
+            key_format: self.key_format,
+            serializer: self.serializer,
+            compressor: self.compressor,
+            label: self.label,
+        })
+    }
+}
+
+#[async_trait]
+impl<S, C> Backend for SqliteBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        let mut values = self.read_many(std::slice::from_ref(key)).await?;
+        Ok(values.pop().flatten())
+    }
+
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        self.write_many([(key.clone(), value)]).await
+    }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        match self.remove_many(std::slice::from_ref(key)).await? {
+            0 => Ok(DeleteStatus::Missing),
+            _ => Ok(DeleteStatus::Deleted(1)),
+        }
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        &self.serializer
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        &self.key_format
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        &self.compressor
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+}
+
+// Explicit CacheBackend implementation using default trait methods
+impl<S, C> hitbox_backend::CacheBackend for SqliteBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use chrono::SubsecRound;
+    use hitbox_core::KeyPart;
+    use tempfile::TempDir;
+
+    fn value(data: &'static [u8]) -> CacheValue<Raw> {
+        CacheValue::new(
+            Bytes::from_static(data),
+            Some(Utc::now() + chrono::Duration::hours(1)),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read() {
+        let backend = SqliteBackend::in_memory().unwrap();
+        let key = CacheKey::from_str("test-key", "1");
+        let expire = Utc::now().trunc_subsecs(3) + chrono::Duration::hours(1);
+        let stale = Utc::now().trunc_subsecs(3) + chrono::Duration::minutes(30);
+
+        backend
+            .write(
+                &key,
+                CacheValue::new(Bytes::from_static(b"test-value"), Some(expire), Some(stale)),
+            )
+            .await
+            .unwrap();
+
+        let result = backend.read(&key).await.unwrap().unwrap();
+        assert_eq!(result.data().as_ref(), b"test-value");
+        assert_eq!(result.expire(), Some(expire));
+        assert_eq!(result.stale(), Some(stale));
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let backend = SqliteBackend::in_memory().unwrap();
+        let key = CacheKey::from_str("delete-key", "1");
+
+        assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+        backend.write(&key, value(b"test-value")).await.unwrap();
+        assert_eq!(
+            backend.remove(&key).await.unwrap(),
+            DeleteStatus::Deleted(1)
+        );
+        assert!(backend.read(&key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_not_returned_and_swept() {
+        let backend = SqliteBackend::in_memory().unwrap();
+        let key = CacheKey::from_str("expired-key", "1");
+        let expired = CacheValue::new(
+            Bytes::from_static(b"expired"),
+            Some(Utc::now() - chrono::Duration::seconds(10)),
+            None,
+        );
+
+        backend.write(&key, expired).await.unwrap();
+        backend
+            .write(&CacheKey::from_str("live-key", "1"), value(b"live"))
+            .await
+            .unwrap();
+        assert!(backend.read(&key).await.unwrap().is_none());
+
+        assert_eq!(backend.sweep().await.unwrap(), 1);
+        assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_deletes_expired_rows() {
+        let backend = SqliteBackend::builder()
+            .sweep_interval(Duration::from_millis(20))
+            .build()
+            .unwrap();
+        let key = CacheKey::from_str("expiring-key", "1");
+        let expiring = CacheValue::new(
+            Bytes::from_static(b"expiring"),
+            Some(Utc::now() + chrono::Duration::milliseconds(50)),
+            None,
+        );
+
+        backend.write(&key, expiring).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
+    }
+
+    #[tokio::test]
+    async fn test_persists_across_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let key = CacheKey::from_str("persist-key", "1");
+
+        {
+            let backend = SqliteBackend::builder()
+                .path(temp_dir.path())
+                .build()
+                .unwrap();
+            backend.write(&key, value(b"persist-value")).await.unwrap();
+        }
+
+        let backend = SqliteBackend::builder()
+            .path(temp_dir.path().join("cache.sqlite"))
+            .build()
+            .unwrap();
+        let result = backend.read(&key).await.unwrap().unwrap();
+        assert_eq!(result.data().as_ref(), b"persist-value");
+    }
+
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let backend = SqliteBackend::in_memory().unwrap();
+        let keys: Vec<_> = (0..3)
+            .map(|i| CacheKey::from_str("batch", &i.to_string()))
+            .collect();
+
+        backend
+            .write_many([
+                (keys[0].clone(), value(b"zero")),
+                (keys[2].clone(), value(b"two")),
+            ])
+            .await
+            .unwrap();
+
+        let values = backend.read_many(&keys).await.unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].as_ref().unwrap().data().as_ref(), b"zero");
+        assert!(values[1].is_none());
+        assert_eq!(values[2].as_ref().unwrap().data().as_ref(), b"two");
+
+        assert_eq!(backend.remove_many(&keys).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_remove_prefix() {
+        let backend = SqliteBackend::in_memory().unwrap();
+        let user = |id: &str| CacheKey::new("users", 1, vec![KeyPart::new("id", Some(id))]);
+        let order = CacheKey::new("orders", 1, vec![KeyPart::new("id", Some("1"))]);
+
+        backend.write(&user("1"), value(b"a")).await.unwrap();
+        backend.write(&user("2"), value(b"b")).await.unwrap();
+        backend.write(&order, value(b"c")).await.unwrap();
+
+        assert_eq!(backend.remove_prefix("users").await.unwrap(), 2);
+        assert!(backend.read(&user("1")).await.unwrap().is_none());
+        assert!(backend.read(&order).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_max_size_evicts_least_recently_accessed() {
+        let temp_dir = TempDir::new().unwrap();
+        let max_size = 1024 * 1024;
+        let backend = SqliteBackend::builder()
+            .path(temp_dir.path())
+            .max_size(max_size)
+            .build()
+            .unwrap();
+        let chunk = Bytes::from(vec![0u8; 64 * 1024]);
+        let key = |i: usize| CacheKey::from_str("chunk", &i.to_string());
+
+        for i in 0..48 {
+            backend
+                .write(&key(i), CacheValue::new(chunk.clone(), None, None))
+                .await
+                .unwrap();
+        }
+        // Oldest write, most recent access
+        backend
+            .db
+            .get_many(
+                &[backend.key_format.serialize(&key(0)).unwrap()],
+                Utc::now().timestamp_millis() + 60_000,
+            )
+            .unwrap();
+        assert!(backend.size().await.unwrap() > max_size);
+
+        assert!(backend.sweep().await.unwrap() > 0);
+        assert!(backend.size().await.unwrap() <= max_size);
+        assert!(backend.read(&key(0)).await.unwrap().is_some());
+        assert!(backend.read(&key(1)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_eviction_stops_near_max_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let max_size = 1024 * 1024;
+        let backend = SqliteBackend::builder()
+            .path(temp_dir.path())
+            .max_size(max_size)
+            .build()
+            .unwrap();
+        let row = Bytes::from(vec![0u8; 256]);
+        let entries = (0..12_000).map(|i| {
+            (
+                CacheKey::from_str("row", &i.to_string()),
+                CacheValue::new(row.clone(), None, None),
+            )
+        });
+        backend.write_many(entries).await.unwrap();
+        assert!(backend.size().await.unwrap() > 3 * max_size);
+
+        // Small rows share pages, so few pages are freed as rows are deleted
+        let deleted = backend.sweep().await.unwrap();
+        let size = backend.size().await.unwrap();
+        assert!(deleted < 10_000, "evicted {deleted} of 12000 rows");
+        assert!(
+            (max_size * 9 / 10..max_size * 11 / 10).contains(&size),
+            "evicted down to {size} bytes"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tables_are_isolated() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = SqliteBackend::builder()
+            .path(temp_dir.path())
+            .table("first")
+            .build()
+            .unwrap();
+        let second = SqliteBackend::builder()
+            .path(temp_dir.path())
+            .table("second")
+            .build()
+            .unwrap();
+        let key = CacheKey::from_str("shared-key", "1");
+
+        first.write(&key, value(b"first")).await.unwrap();
+        assert!(second.read(&key).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_invalid_table_name() {
+        let result = SqliteBackend::builder()
+            .table("cache; DROP TABLE x")
+            .build();
+        assert!(matches!(result, Err(SqliteError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_zero_sweep_interval() {
+        let result = SqliteBackend::builder()
+            .sweep_interval(Duration::ZERO)
+            .build();
+        assert!(matches!(result, Err(SqliteError::InvalidConfig(_))));
+    }
+}
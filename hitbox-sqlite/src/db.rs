@@ -0,0 +1,276 @@
+//! SQL access to the cache table.
+
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use bytes::Bytes;
+use chrono::DateTime;
+use hitbox_core::{CacheValue, Raw};
+use rusqlite::{Connection, OptionalExtension, params};
+
+use crate::SqliteError;
+
+/// Reads refresh `last_access` at most this often, so hot keys do not turn
+/// every read into a write.
+const ACCESS_RESOLUTION_MS: i64 = 1_000;
+
+/// Rows deleted per statement while sweeping, so reads are not blocked for long.
+const SWEEP_BATCH: usize = 256;
+
+/// A row to write.
+pub(crate) struct Entry {
+    pub key: Vec<u8>,
+    pub prefix: String,
+    pub value: CacheValue<Raw>,
+}
+
+/// Connection to the cache database and the statements for its table.
+pub(crate) struct Database {
+    connection: Mutex<Connection>,
+    sql: Statements,
+}
+
+/// SQL with the table name filled in.
+struct Statements {
+    select: String,
+    touch: String,
+    upsert: String,
+    delete: String,
+    delete_prefix: String,
+    delete_expired: String,
+    data_size: String,
+    evict: String,
+}
+
+impl Statements {
+    fn new(table: &str) -> Self {
+        Self {
+            select: format!("SELECT data, expire, stale, last_access FROM {table} WHERE key = ?1"),
+            touch: format!("UPDATE {table} SET last_access = ?2 WHERE key = ?1"),
+            upsert: format!(
+                "INSERT OR REPLACE INTO {table} (key, prefix, data, expire, stale, last_access) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+            ),
+            delete: format!("DELETE FROM {table} WHERE key = ?1"),
+            delete_prefix: format!("DELETE FROM {table} WHERE prefix = ?1"),
+            delete_expired: format!(
+                "DELETE FROM {table} WHERE key IN \
+                 (SELECT key FROM {table} WHERE expire <= ?1 LIMIT {SWEEP_BATCH})"
+            ),
+            data_size: format!("SELECT COALESCE(SUM(length(key) + length(data)), 0) FROM {table}"),
+            // Least recently accessed rows until their size reaches ?1 bytes,
+            // returning the size of each deleted row
+            evict: format!(
+                "DELETE FROM {table} WHERE key IN \
+                 (SELECT key FROM \
+                     (SELECT key, SUM(length(key) + length(data)) \
+                         OVER (ORDER BY last_access ROWS UNBOUNDED PRECEDING) \
+                         - length(key) - length(data) AS preceding \
+                      FROM {table}) \
+                  WHERE preceding < ?1 LIMIT {SWEEP_BATCH}) \
+                 RETURNING length(key) + length(data)"
+            ),
+        }
+    }
+}
+
+impl Database {
+    /// Opens or creates the database, in memory when `path` is `None`.
+    pub(crate) fn open(path: Option<&Path>, table: &str) -> Result<Self, SqliteError> {
+        if table.is_empty() || !table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(SqliteError::InvalidConfig(format!(
+                "table name {table:?} must only contain ASCII letters, digits and underscores"
+            )));
+        }
+
+        let connection = match path {
+            Some(path) => Connection::open(path)?,
+            None => Connection::open_in_memory()?,
+        };
+        // auto_vacuum only takes effect before the first table is created
+        connection.execute_batch(&format!(
+            "PRAGMA auto_vacuum = INCREMENTAL;
+             PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA busy_timeout = 5000;
+             CREATE TABLE IF NOT EXISTS {table} (
+                 key BLOB PRIMARY KEY,
+                 prefix TEXT NOT NULL,
+                 data BLOB NOT NULL,
+                 expire INTEGER,
+                 stale INTEGER,
+                 last_access INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS {table}_expire ON {table} (expire) WHERE expire IS NOT NULL;
+             CREATE INDEX IF NOT EXISTS {table}_prefix ON {table} (prefix);
+             CREATE INDEX IF NOT EXISTS {table}_last_access ON {table} (last_access);"
+        ))?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+            sql: Statements::new(table),
+        })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        // A panic mid-statement leaves nothing half-applied: SQLite rolls it back
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Reads the entries stored under `keys`, skipping expired ones.
+    pub(crate) fn get_many(
+        &self,
+        keys: &[Vec<u8>],
+        now: i64,
+    ) -> Result<Vec<Option<CacheValue<Raw>>>, SqliteError> {
+        let connection = self.connection();
+        let tx = connection.unchecked_transaction()?;
+        let mut values = Vec::with_capacity(keys.len());
+        {
+            let mut select = tx.prepare_cached(&self.sql.select)?;
+            let mut touch = tx.prepare_cached(&self.sql.touch)?;
+            for key in keys {
+                let row = select
+                    .query_row([key], |row| {
+                        Ok((
+                            row.get::<_, Vec<u8>>(0)?,
+                            row.get::<_, Option<i64>>(1)?,
+                            row.get::<_, Option<i64>>(2)?,
+                            row.get::<_, i64>(3)?,
+                        ))
+                    })
+                    .optional()?;
+
+                let value = match row {
+                    Some((_, Some(expire), _, _)) if expire <= now => None,
+                    Some((data, expire, stale, last_access)) => {
+                        if now - last_access >= ACCESS_RESOLUTION_MS {
+                            touch.execute(params![key, now])?;
+                        }
+                        Some(CacheValue::new(
+                            Bytes::from(data),
+                            expire.and_then(DateTime::from_timestamp_millis),
+                            stale.and_then(DateTime::from_timestamp_millis),
+                        ))
+                    }
+                    None => None,
+                };
+                values.push(value);
+            }
+        }
+        tx.commit()?;
+        Ok(values)
+    }
+
+    /// Inserts or replaces `entries` in one transaction.
+    pub(crate) fn put_many(&self, entries: &[Entry], now: i64) -> Result<(), SqliteError> {
+        let connection = self.connection();
+        let tx = connection.unchecked_transaction()?;
+        {
+            let mut upsert = tx.prepare_cached(&self.sql.upsert)?;
+            for entry in entries {
+                upsert.execute(params![
+                    entry.key,
+                    entry.prefix,
+                    entry.value.data().as_ref(),
+                    entry.value.expire().map(|expire| expire.timestamp_millis()),
+                    entry.value.stale().map(|stale| stale.timestamp_millis()),
+                    now,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Deletes `keys` in one transaction, returning how many existed.
+    pub(crate) fn delete_many(&self, keys: &[Vec<u8>]) -> Result<usize, SqliteError> {
+        let connection = self.connection();
+        let tx = connection.unchecked_transaction()?;
+        let mut deleted = 0;
+        {
+            let mut delete = tx.prepare_cached(&self.sql.delete)?;
+            for key in keys {
+                deleted += delete.execute([key])?;
+            }
+        }
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    /// Deletes every entry whose cache key has `prefix`.
+    pub(crate) fn delete_prefix(&self, prefix: &str) -> Result<usize, SqliteError> {
+        let connection = self.connection();
+        let deleted = connection
+            .prepare_cached(&self.sql.delete_prefix)?
+            .execute([prefix])?;
+        Ok(deleted)
+    }
+
+    /// Deletes expired rows, then evicts least recently accessed rows if the
+    /// database uses more than `max_size` bytes. Returns the number of
+    /// deleted rows.
+    ///
+    /// Eviction removes the share of row data the database is over the limit
+    /// by, so indexes and page overhead shrink along with it. Page usage is
+    /// not re-measured in between: deleting rows rarely frees whole pages, so
+    /// waiting for it to drop could evict until the table is empty. Pages
+    /// left partly filled can keep the database slightly above `max_size`
+    /// until the next sweep.
+    ///
+    /// Works in batches, releasing the connection between them.
+    pub(crate) fn sweep(&self, now: i64, max_size: Option<u64>) -> Result<usize, SqliteError> {
+        let mut deleted = 0;
+        loop {
+            let batch = self
+                .connection()
+                .prepare_cached(&self.sql.delete_expired)?
+                .execute([now])?;
+            deleted += batch;
+            if batch < SWEEP_BATCH {
+                break;
+            }
+        }
+
+        let used = self.used_size()?;
+        if let Some(max_size) = max_size
+            && used > max_size
+        {
+            let data: u64 = self
+                .connection()
+                .prepare_cached(&self.sql.data_size)?
+                .query_row([], |row| row.get(0))?;
+            let mut excess =
+                (u128::from(data) * u128::from(used - max_size) / u128::from(used)) as u64;
+            while excess > 0 {
+                let sizes = self
+                    .connection()
+                    .prepare_cached(&self.sql.evict)?
+                    .query_map([excess as i64], |row| row.get::<_, u64>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                if sizes.is_empty() {
+                    break;
+                }
+                deleted += sizes.len();
+                excess = excess.saturating_sub(sizes.iter().sum());
+            }
+        }
+
+        if deleted > 0 {
+            // Return the freed pages to the file system
+            self.connection()
+                .execute_batch("PRAGMA incremental_vacuum;")?;
+        }
+        Ok(deleted)
+    }
+
+    /// Bytes of the database file holding data, excluding free pages.
+    pub(crate) fn used_size(&self) -> Result<u64, SqliteError> {
+        let connection = self.connection();
+        let pragma =
+            |name: &str| connection.pragma_query_value(None, name, |row| row.get::<_, u64>(0));
+        Ok((pragma("page_count")? - pragma("freelist_count")?) * pragma("page_size")?)
+    }
+}
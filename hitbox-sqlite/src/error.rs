@@ -0,0 +1,28 @@
+use hitbox_backend::BackendError;
+use thiserror::Error;
+
+/// Errors that can occur when using [`SqliteBackend`](crate::SqliteBackend).
+#[derive(Debug, Error)]
+pub enum SqliteError {
+    /// An error from SQLite.
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// Failed to serialize a cache key.
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] hitbox_backend::format::FormatError),
+
+    /// An I/O error occurred, e.g. while starting the sweeper thread.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The provided configuration is invalid.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl From<SqliteError> for BackendError {
+    fn from(error: SqliteError) -> Self {
+        Self::InternalError(Box::new(error))
+    }
+}
@@ -0,0 +1,11 @@
+#![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod backend;
+mod db;
+mod error;
+mod sweeper;
+
+pub use backend::{SqliteBackend, SqliteBackendBuilder};
+pub use error::SqliteError;
@@ -0,0 +1,45 @@
+//! Background thread deleting expired rows and evicting over `max_size`.
+//!
+//! rusqlite only offers blocking calls, so sweeps get a thread of their own
+//! rather than holding a Tokio blocking thread for their whole duration.
+
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::db::Database;
+
+/// Owns the sweeper thread. Dropping it disconnects the channel the thread
+/// waits on, which wakes and stops the thread between sweeps.
+pub(crate) struct Sweeper {
+    _stop: Sender<()>,
+}
+
+impl Sweeper {
+    /// Starts sweeping `db` every `interval`.
+    ///
+    /// Each sweep takes the shared connection one batch at a time, so reads
+    /// and writes interleave with a long sweep instead of waiting it out.
+    pub(crate) fn spawn(
+        db: Arc<Database>,
+        interval: Duration,
+        max_size: Option<u64>,
+    ) -> std::io::Result<Self> {
+        let (stop, stopped) = mpsc::channel::<()>();
+        thread::Builder::new()
+            .name("hitbox-sqlite-sweeper".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    match db.sweep(Utc::now().timestamp_millis(), max_size) {
+                        Ok(0) => {}
+                        Ok(deleted) => tracing::debug!(deleted, "Swept SQLite cache rows"),
+                        Err(error) => tracing::warn!(%error, "SQLite cache sweep failed"),
+                    }
+                }
+            })?;
+        Ok(Self { _stop: stop })
+    }
+}
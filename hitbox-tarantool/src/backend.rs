@@ -1,147 +1,358 @@
+//! Tarantool backend implementation.
+
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use hitbox::{BackendLabel, CacheKey, CacheValue, Raw};
 use hitbox_backend::{
-    serializer::SerializableCachedValue, BackendError, BackendResult, CacheBackend, DeleteStatus,
+    Backend, BackendResult, CacheKeyFormat, Compressor, DeleteStatus, PassthroughCompressor,
+    format::{BincodeFormat, Format},
 };
-use hitbox_core::{CacheKey, CacheableResponse, CachedValue};
-use rusty_tarantool::tarantool::{Client, ClientConfig, ExecWithParamaters};
-use serde::{Deserialize, Serialize};
-use std::io::Error;
-use typed_builder::TypedBuilder;
+use rmpv::Value;
+use tokio::sync::Mutex;
+
+use crate::error::Error;
+use crate::iproto::Connection;
 
-const TARANTOOL_INIT_LUA: &str = include_str!("init.lua");
+/// Lua script creating the cache space, the expiry fiber and the `hitbox` functions.
+const INIT_LUA: &str = include_str!("init.lua");
 
-/// Tarantool cache backend based on rusty_tarantool crate.
+/// Tarantool cache backend.
+///
+/// Entries are stored in a memtx space as `{key, expire, stale, data}`
+/// tuples and accessed through the `hitbox.get`, `hitbox.set` and
+/// `hitbox.delete` Lua functions. Expiry is enforced by Tarantool: a fiber
+/// deletes expired tuples in the background and reads skip entries that
+/// expired in the meantime.
+///
+/// The space and functions are created by [`init`](Self::init), or on
+/// connect with [`TarantoolBackendBuilder::init_schema`]. Both evaluate Lua
+/// and need the `execute` privilege on `universe`; the script is idempotent.
+///
+/// The connection is established lazily on the first operation and
+/// re-established after it is lost. Cloning is cheap — clones share the
+/// connection.
 ///
 /// # Examples
-/// ```
-/// use hitbox_tarantool::Tarantool;
 ///
-/// #[tokio::main]
-/// async fn main() {
-///     let mut backend = Tarantool::builder().build();
-///     // backend.init().await.unwrap();
-/// }
+/// ```no_run
+/// use hitbox_tarantool::TarantoolBackend;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), hitbox_tarantool::Error> {
+/// let backend = TarantoolBackend::builder()
+///     .address("127.0.0.1:3301")
+///     .credentials("hitbox", "hitbox")
+///     .space("hitbox_cache")
+///     .build();
+///
+/// backend.init().await?;
+/// # Ok(())
+/// # }
 /// ```
-#[derive(Clone, TypedBuilder)]
-#[builder(build_method(vis="", name=__build))]
-pub struct Tarantool {
-    #[builder(default = "hitbox".to_string())]
-    user: String,
-    #[builder(default = "hitbox".to_string())]
-    password: String,
-    #[builder(default = "127.0.0.1".to_string())]
-    host: String,
-    #[builder(default = "3301".to_string())]
-    port: String,
+#[derive(Clone)]
+pub struct TarantoolBackend<S = BincodeFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    address: String,
+    credentials: Option<(String, String)>,
+    space: String,
+    init_schema: bool,
+    /// Current connection, replaced after it is lost.
+    connection: Arc<Mutex<Option<Arc<Connection>>>>,
+    key_format: CacheKeyFormat,
+    serializer: S,
+    compressor: C,
+    label: BackendLabel,
 }
 
-pub struct TarantoolBackend {
-    client: Client,
+impl TarantoolBackend<BincodeFormat, PassthroughCompressor> {
+    /// Creates a new builder for `TarantoolBackend`.
+    #[must_use]
+    pub fn builder() -> TarantoolBackendBuilder<BincodeFormat, PassthroughCompressor> {
+        TarantoolBackendBuilder::default()
+    }
 }
 
-#[allow(non_camel_case_types)]
-impl<
-        __user: ::typed_builder::Optional<String>,
-        __password: ::typed_builder::Optional<String>,
-        __host: ::typed_builder::Optional<String>,
-        __port: ::typed_builder::Optional<String>,
-    > TarantoolBuilder<(__user, __password, __host, __port)>
+impl<S, C> TarantoolBackend<S, C>
+where
+    S: Format,
+    C: Compressor,
 {
-    pub fn build(self) -> TarantoolBackend {
-        let t = self.__build();
-        let client =
-            ClientConfig::new(format!("{}:{}", t.host, t.port), t.user, t.password).build();
-        TarantoolBackend { client }
+    /// Creates the cache space and the Lua functions used by the backend.
+    ///
+    /// Idempotent. Requires the `execute` privilege on `universe`.
+    pub async fn init(&self) -> Result<(), Error> {
+        let connection = self.connection().await?;
+        Self::init_on(&connection, &self.space).await
     }
-}
 
-impl TarantoolBackend {
-    /// Init backend and configure tarantool instance
-    /// This function is idempotent
-    pub async fn init(&mut self) -> BackendResult<()> {
-        self.client
-            .eval(TARANTOOL_INIT_LUA, &("hitbox_cache",))
+    async fn init_on(connection: &Connection, space: &str) -> Result<(), Error> {
+        connection
+            .eval(INIT_LUA, vec![Value::from(space)])
             .await
-            .map_err(|err| BackendError::InternalError(Box::new(err)))?;
+            .map(|_| ())
+    }
+
+    /// Returns the current connection, connecting if there is none or it was lost.
+    async fn connection(&self) -> Result<Arc<Connection>, Error> {
+        let mut current = self.connection.lock().await;
+        if let Some(connection) = current.as_ref().filter(|c| !c.is_closed()) {
+            return Ok(connection.clone());
+        }
 
-        Ok(())
+        let credentials = self
+            .credentials
+            .as_ref()
+            .map(|(user, password)| (user.as_str(), password.as_str()));
+        let connection = Arc::new(Connection::connect(&self.address, credentials).await?);
+        if self.init_schema {
+            Self::init_on(&connection, &self.space).await?;
+        }
+        *current = Some(connection.clone());
+        Ok(connection)
     }
 
-    fn map_err(err: Error) -> BackendError {
-        BackendError::InternalError(Box::new(err))
+    async fn call(&self, function: &str, key: &CacheKey, args: Vec<Value>) -> BackendResult<Value> {
+        let key = self.key_format.serialize(key)?;
+        let args = [Value::from(self.space.as_str()), Value::Binary(key)]
+            .into_iter()
+            .chain(args)
+            .collect();
+        let results = self.connection().await?.call(function, args).await?;
+        Ok(results.into_iter().next().unwrap_or(Value::Nil))
     }
 }
 
-#[doc(hidden)]
-#[derive(Serialize, Deserialize)]
-pub struct CacheEntry<T> {
-    pub key: String,
-    pub ttl: Option<u32>,
-    pub value: SerializableCachedValue<T>,
+/// Builder for creating and configuring a [`TarantoolBackend`].
+///
+/// Use [`TarantoolBackend::builder`] to create a new builder instance.
+pub struct TarantoolBackendBuilder<S = BincodeFormat, C = PassthroughCompressor>
+where
+    S: Format,
+    C: Compressor,
+{
+    address: String,
+    credentials: Option<(String, String)>,
+    space: String,
+    init_schema: bool,
+    key_format: CacheKeyFormat,
+    serializer: S,
+    compressor: C,
+    label: BackendLabel,
 }
 
-#[async_trait]
-impl CacheBackend for TarantoolBackend {
-    async fn get<T>(&self, key: &CacheKey) -> BackendResult<Option<CachedValue<T::Cached>>>
+impl Default for TarantoolBackendBuilder<BincodeFormat, PassthroughCompressor> {
+    fn default() -> Self {
+        Self {
+            address: "127.0.0.1:3301".to_string(),
+            credentials: None,
+            space: "hitbox_cache".to_string(),
+            init_schema: false,
+            key_format: CacheKeyFormat::default(),
+            serializer: BincodeFormat,
+            compressor: PassthroughCompressor,
+            label: BackendLabel::new_static("tarantool"),
+        }
+    }
+}
+
+impl<S, C> TarantoolBackendBuilder<S, C>
+where
+    S: Format,
+    C: Compressor,
+{
+    /// Sets the `host:port` address of the Tarantool instance.
+    ///
+    /// # Default
+    ///
+    /// `"127.0.0.1:3301"`
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Authenticates as `user` instead of `guest`.
+    pub fn credentials(mut self, user: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((user.into(), password.into()));
+        self
+    }
+
+    /// Sets the name of the space holding cache entries.
+    ///
+    /// # Default
+    ///
+    /// `"hitbox_cache"`
+    pub fn space(mut self, space: impl Into<String>) -> Self {
+        self.space = space.into();
+        self
+    }
+
+    /// Runs [`TarantoolBackend::init`] on every new connection.
+    pub fn init_schema(mut self) -> Self {
+        self.init_schema = true;
+        self
+    }
+
+    /// Sets the cache key serialization format.
+    ///
+    /// # Default
+    ///
+    /// [`CacheKeyFormat::Bitcode`]
+    pub fn key_format(mut self, key_format: CacheKeyFormat) -> Self {
+        self.key_format = key_format;
+        self
+    }
+
+    /// Sets a custom label for this backend.
+    ///
+    /// # Default
+    ///
+    /// `"tarantool"`
+    pub fn label(mut self, label: impl Into<BackendLabel>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the cache value serialization format.
+    ///
+    /// # Default
+    ///
+    /// [`BincodeFormat`]
+    pub fn value_format<NewS>(self, serializer: NewS) -> TarantoolBackendBuilder<NewS, C>
     where
-        T: CacheableResponse,
-        <T as CacheableResponse>::Cached: serde::de::DeserializeOwned,
+        NewS: Format,
     {
-        self.client
-            .prepare_fn_call("hitbox.get")
-            .bind_ref(&(key.serialize()))
-            .map_err(TarantoolBackend::map_err)?
-            .execute()
-            .await
-            .map_err(TarantoolBackend::map_err)?
-            .decode_single::<Option<CacheEntry<T::Cached>>>()
-            .map_err(TarantoolBackend::map_err)
-            .map(|v| v.map(|v| v.value.into_cached_value()))
-    }
-
-    async fn delete(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
-        let result: bool = self
-            .client
-            .prepare_fn_call("hitbox.delete")
-            .bind_ref(&(key.serialize()))
-            .map_err(TarantoolBackend::map_err)?
-            .execute()
-            .await
-            .map_err(TarantoolBackend::map_err)?
-            .decode_single()
-            .map_err(TarantoolBackend::map_err)?;
-        match result {
-            true => Ok(DeleteStatus::Deleted(1)),
-            false => Ok(DeleteStatus::Missing),
+        TarantoolBackendBuilder {
+            address: self.address,
+            credentials: self.credentials,
+            space: self.space,
+            init_schema: self.init_schema,
+            key_format: self.key_format,
+            serializer,
+            compressor: self.compressor,
+            label: self.label,
         }
     }
 
-    async fn set<T>(
-        &self,
-        key: &CacheKey,
-        value: &CachedValue<T::Cached>,
-        ttl: Option<u32>,
-    ) -> BackendResult<()>
+    /// Sets the compression strategy for cache values.
+    ///
+    /// # Default
+    ///
+    /// [`PassthroughCompressor`] (no compression)
+    pub fn compressor<NewC>(self, compressor: NewC) -> TarantoolBackendBuilder<S, NewC>
     where
-        T: CacheableResponse + Send,
-        T::Cached: serde::Serialize + Send + Sync,
+        NewC: Compressor,
     {
-        let entry: CacheEntry<T::Cached> = CacheEntry {
-            key: key.serialize(),
-            ttl,
-            value: value.clone().into(),
+        TarantoolBackendBuilder {
+            address: self.address,
+            credentials: self.credentials,
+            space: self.space,
+            init_schema: self.init_schema,
+            key_format: self.key_format,
+            serializer: self.serializer,
+            compressor,
+            label: self.label,
+        }
+    }
+
+    /// Builds the [`TarantoolBackend`].
+    ///
+    /// The connection is established lazily on the first cache operation.
+    pub fn build(self) -> TarantoolBackend<S, C> {
+        TarantoolBackend {
+            address: self.address,
+            credentials: self.credentials,
+            space: self.space,
+            init_schema: self.init_schema,
+            connection: Arc::default(),
+            key_format: self.key_format,
+            serializer: self.serializer,
+            compressor: self.compressor,
+            label: self.label,
+        }
+    }
+}
+
+#[async_trait]
+impl<S, C> Backend for TarantoolBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
+    async fn read(&self, key: &CacheKey) -> BackendResult<Option<CacheValue<Raw>>> {
+        // hitbox.get returns {data, stale, ttl} or nil
+        let entry = match self.call("hitbox.get", key, Vec::new()).await? {
+            Value::Array(entry) => entry,
+            Value::Nil => return Ok(None),
+            _ => return Err(Error::Protocol("unexpected hitbox.get result").into()),
         };
-        self.client
-            .prepare_fn_call("hitbox.set")
-            .bind_ref(&entry)
-            .map_err(TarantoolBackend::map_err)?
-            .execute()
-            .await
-            .map(|_| ())
-            .map_err(TarantoolBackend::map_err)
+        let mut entry = entry.into_iter();
+
+        let data = match entry.next() {
+            Some(Value::Binary(data)) => Bytes::from(data),
+            // Tarantool before 2.10 returns varbinary fields as strings
+            Some(Value::String(data)) => Bytes::from(data.into_bytes()),
+            _ => return Err(Error::Protocol("cache entry without data").into()),
+        };
+        let stale = entry
+            .next()
+            .and_then(|stale| stale.as_i64())
+            .and_then(DateTime::from_timestamp_millis);
+        let expire = entry
+            .next()
+            .and_then(|ttl| ttl.as_i64())
+            .map(|ttl| Utc::now() + chrono::Duration::milliseconds(ttl));
+
+        Ok(Some(CacheValue::new(data, expire, stale)))
     }
 
-    async fn start(&self) -> BackendResult<()> {
-        Ok(())
+    async fn write(&self, key: &CacheKey, value: CacheValue<Raw>) -> BackendResult<()> {
+        let stale = value
+            .stale()
+            .map_or(Value::Nil, |stale| Value::from(stale.timestamp_millis()));
+        // An already expired entry gets the shortest possible TTL
+        let ttl = value.expire().map_or(Value::Nil, |_| {
+            Value::from(value.ttl().map_or(1, |ttl| ttl.as_millis().max(1) as u64))
+        });
+        let args = vec![Value::Binary(value.data().to_vec()), stale, ttl];
+
+        self.call("hitbox.set", key, args).await.map(|_| ())
     }
+
+    async fn remove(&self, key: &CacheKey) -> BackendResult<DeleteStatus> {
+        match self.call("hitbox.delete", key, Vec::new()).await?.as_bool() {
+            Some(true) => Ok(DeleteStatus::Deleted(1)),
+            Some(false) => Ok(DeleteStatus::Missing),
+            None => Err(Error::Protocol("unexpected hitbox.delete result").into()),
+        }
+    }
+
+    fn label(&self) -> BackendLabel {
+        self.label.clone()
+    }
+
+    fn value_format(&self) -> &dyn Format {
+        &self.serializer
+    }
+
+    fn key_format(&self) -> &CacheKeyFormat {
+        &self.key_format
+    }
+
+    fn compressor(&self) -> &dyn Compressor {
+        &self.compressor
+    }
+}
+
+// Explicit CacheBackend implementation using default trait methods
+impl<S, C> hitbox_backend::CacheBackend for TarantoolBackend<S, C>
+where
+    S: Format + Send + Sync,
+    C: Compressor + Send + Sync,
+{
 }
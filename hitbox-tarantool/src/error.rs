@@ -0,0 +1,48 @@
+//! Error types for Tarantool backend operations.
+//!
+//! All errors can be converted to [`BackendError`] for uniform error handling
+//! across different cache backends.
+//!
+//! [`BackendError`]: hitbox_backend::BackendError
+
+use hitbox_backend::BackendError;
+
+/// Error type for Tarantool backend operations.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Connecting to or talking with Tarantool failed.
+    #[error("Tarantool I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The connection was closed before the response arrived.
+    #[error("Tarantool connection closed")]
+    ConnectionClosed,
+
+    /// Tarantool answered with an error, e.g. a missing function or
+    /// insufficient privileges.
+    #[error("Tarantool error {code}: {message}")]
+    Server {
+        /// Tarantool error code.
+        code: u32,
+        /// Error message reported by Tarantool.
+        message: String,
+    },
+
+    /// A request could not be encoded.
+    #[error("Tarantool request encoding error: {0}")]
+    Encode(#[from] rmpv::encode::Error),
+
+    /// A response could not be decoded.
+    #[error("Tarantool response decoding error: {0}")]
+    Decode(#[from] rmpv::decode::Error),
+
+    /// A response did not follow the protocol or the expected shape.
+    #[error("Tarantool protocol error: {0}")]
+    Protocol(&'static str),
+}
+
+impl From<Error> for BackendError {
+    fn from(error: Error) -> Self {
+        Self::InternalError(Box::new(error))
+    }
+}
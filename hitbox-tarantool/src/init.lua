@@ -1,43 +1,59 @@
+-- Creates the cache space and the functions called by hitbox.
+-- Idempotent: safe to run on every start and for several spaces.
 local fiber = require("fiber")
 local log = require("log")
 
-local SCAN_INTERVAL = 0.1
-local MAX_TUPLES_FOR_DELETE = 1000
-
-box.cfg({})
+local EXPIRY_INTERVAL = 0.1
+local EXPIRY_BATCH = 1000
 
 local space_name = ...
 
-box.schema.space.create(space_name, { if_not_exists = true })
+local function now_ms()
+	return math.floor(fiber.time() * 1000)
+end
+
+box.schema.space.create(space_name, {
+	if_not_exists = true,
+	format = {
+		{ name = "key", type = "varbinary" },
+		{ name = "expire", type = "unsigned", is_nullable = true },
+		{ name = "stale", type = "integer", is_nullable = true },
+		{ name = "data", type = "varbinary" },
+	},
+})
 box.space[space_name]:create_index("primary", {
 	type = "HASH",
-	parts = { { 1, "string" } },
+	parts = { { 1, "varbinary" } },
 	if_not_exists = true,
 })
-box.space[space_name]:create_index("by_ttl", {
-	parts = { { 2, "integer" } },
+box.space[space_name]:create_index("expire", {
+	type = "TREE",
+	unique = false,
+	parts = { { 2, "unsigned", is_nullable = true } },
 	if_not_exists = true,
 })
 
-if not _G.__hitbox_cache_fiber then
-	_G.__hitbox_cache_fiber = fiber.create(function()
-		fiber.name("hitbox_cache_fiber")
+-- Deletes expired entries in the background, in batches
+_G.__hitbox_expiry_fibers = _G.__hitbox_expiry_fibers or {}
+if not _G.__hitbox_expiry_fibers[space_name] then
+	_G.__hitbox_expiry_fibers[space_name] = fiber.create(function()
+		fiber.name("hitbox_expiry_" .. space_name, { truncate = true })
 		while true do
 			box.ctl.wait_rw()
 
-			local ok, err = pcall(function()
-				local for_del = box.space[space_name].index.by_ttl
-					:pairs({ math.floor(fiber.time()) }, { iterator = "LE" })
-					:take(MAX_TUPLES_FOR_DELETE)
-					:totable()
-
-				box.atomic(function()
-					for _, t in pairs(for_del) do
-						box.space[space_name]:delete(t[1])
+			local ok, err = pcall(box.atomic, function()
+				local space = box.space[space_name]
+				local expired = {}
+				-- Entries without expiry sort first and end the scan
+				for _, t in space.index.expire:pairs({ now_ms() }, { iterator = "LE" }) do
+					if t[2] == nil or #expired >= EXPIRY_BATCH then
+						break
 					end
-				end)
-
-				return true
+					table.insert(expired, t[1])
+				end
+				for _, key in ipairs(expired) do
+					space:delete(key)
+				end
 			end)
 
 			if not ok then
@@ -45,29 +61,55 @@ if not _G.__hitbox_cache_fiber then
 			end
 
 			fiber.testcancel()
-			fiber.sleep(SCAN_INTERVAL)
+			fiber.sleep(EXPIRY_INTERVAL)
 		end
 	end)
 end
 
--- lua api for hitbox
-_G.hitbox = {
-	---Get cache entry by key
-	---@param key string
-	---@return table?
-	get = function(key)
-		return box.space[space_name]:get(key)
-	end,
-	---Insert cache entry
-	---@param entry table {key: string, ttl: number, value: any}
-	---@return table saved entry
-	set = function(entry)
-		return box.space[space_name]:replace(entry)
-	end,
-	---Delete cache entry
-	---@param key string
-	---@return boolean
-	delete = function(key)
-		return box.space[space_name]:delete(key) and true or false
-	end,
-}
+-- Lua API for hitbox
+_G.hitbox = _G.hitbox or {}
+
+---Get a cache entry
+---@param space string
+---@param key string
+---@return table? {data, stale, ttl} with the remaining TTL in milliseconds
+function hitbox.get(space, key)
+	local t = box.space[space]:get(key)
+	if t == nil then
+		return nil
+	end
+	local ttl = box.NULL
+	if t[2] ~= nil then
+		ttl = t[2] - now_ms()
+		-- Not deleted by the expiry fiber yet
+		if ttl <= 0 then
+			return nil
+		end
+	end
+	return { t[4], t[3], ttl }
+end
+
+---Insert or replace a cache entry
+---@param space string
+---@param key string
+---@param data string
+---@param stale number? stale timestamp in milliseconds
+---@param ttl number? TTL in milliseconds
+function hitbox.set(space, key, data, stale, ttl)
+	local expire = box.NULL
+	if ttl ~= nil then
+		expire = now_ms() + ttl
+	end
+	if stale == nil then
+		stale = box.NULL
+	end
+	box.space[space]:replace({ key, expire, stale, data })
+end
+
+---Delete a cache entry
+---@param space string
+---@param key string
+---@return boolean whether the entry existed
+function hitbox.delete(space, key)
+	return box.space[space]:delete(key) ~= nil
+end
@@ -0,0 +1,467 @@
+//! Minimal asynchronous client for the Tarantool binary protocol (IPROTO).
+//!
+//! Supports the requests the backend needs: authentication, `CALL` and
+//! `EVAL`. Requests are multiplexed over one connection and matched to
+//! responses by their sync number. Packets are written by a dedicated task,
+//! so a cancelled request never leaves a partial packet on the socket.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use rmpv::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use crate::error::Error;
+
+const GREETING_LEN: usize = 128;
+const SCRAMBLE_LEN: usize = 20;
+
+// Header keys
+const REQUEST_TYPE: u8 = 0x00;
+const SYNC: u8 = 0x01;
+
+// Request types
+const AUTH: u8 = 0x07;
+const EVAL: u8 = 0x08;
+const CALL: u8 = 0x0a;
+
+// Body keys
+const TUPLE: u8 = 0x21;
+const FUNCTION_NAME: u8 = 0x22;
+const USER_NAME: u8 = 0x23;
+const EXPR: u8 = 0x27;
+const DATA: u8 = 0x30;
+const ERROR_24: u8 = 0x31;
+
+/// Response code flag marking an error; the lower bits hold the error code.
+const TYPE_ERROR: u64 = 0x8000;
+/// Response type of out-of-band `box.session.push` messages.
+const TYPE_CHUNK: u64 = 0x80;
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, Error>>>>>;
+
+/// An authenticated connection to a Tarantool instance.
+pub(crate) struct Connection {
+    packets: mpsc::UnboundedSender<Vec<u8>>,
+    pending: Pending,
+    sync: AtomicU64,
+    closed: Arc<AtomicBool>,
+    reader: JoinHandle<()>,
+    writer: JoinHandle<()>,
+}
+
+impl Connection {
+    /// Connects to `address` and authenticates if `credentials` are given.
+    pub(crate) async fn connect(
+        address: &str,
+        credentials: Option<(&str, &str)>,
+    ) -> Result<Self, Error> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        let (read, write) = stream.into_split();
+        let mut read = BufReader::new(read);
+
+        let mut greeting = [0; GREETING_LEN];
+        read.read_exact(&mut greeting).await?;
+        let salt = Self::salt(&greeting)?;
+
+        let pending = Pending::default();
+        let closed = Arc::new(AtomicBool::new(false));
+        let reader = tokio::spawn(Self::read_loop(read, pending.clone(), closed.clone()));
+        let (packets, queued) = mpsc::unbounded_channel();
+        let writer = tokio::spawn(Self::write_loop(
+            write,
+            queued,
+            pending.clone(),
+            closed.clone(),
+        ));
+        let connection = Self {
+            packets,
+            pending,
+            sync: AtomicU64::new(0),
+            closed,
+            reader,
+            writer,
+        };
+
+        if let Some((user, password)) = credentials {
+            let body = vec![
+                (Value::from(USER_NAME), Value::from(user)),
+                (
+                    Value::from(TUPLE),
+                    Value::Array(vec![
+                        Value::from("chap-sha1"),
+                        Value::Binary(scramble(&salt, password).to_vec()),
+                    ]),
+                ),
+            ];
+            connection.request(AUTH, body).await?;
+        }
+        Ok(connection)
+    }
+
+    /// Whether the connection was lost and a new one is needed.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Calls a stored or global Lua function, returning its results.
+    pub(crate) async fn call(&self, function: &str, args: Vec<Value>) -> Result<Vec<Value>, Error> {
+        let body = vec![
+            (Value::from(FUNCTION_NAME), Value::from(function)),
+            (Value::from(TUPLE), Value::Array(args)),
+        ];
+        Self::results(self.request(CALL, body).await?)
+    }
+
+    /// Evaluates a Lua chunk, returning its results.
+    pub(crate) async fn eval(&self, expr: &str, args: Vec<Value>) -> Result<Vec<Value>, Error> {
+        let body = vec![
+            (Value::from(EXPR), Value::from(expr)),
+            (Value::from(TUPLE), Value::Array(args)),
+        ];
+        Self::results(self.request(EVAL, body).await?)
+    }
+
+    fn results(body: Value) -> Result<Vec<Value>, Error> {
+        match field(body, DATA) {
+            Some(Value::Array(values)) => Ok(values),
+            Some(_) => Err(Error::Protocol("response data is not an array")),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn request(&self, request_type: u8, body: Vec<(Value, Value)>) -> Result<Value, Error> {
+        if self.is_closed() {
+            return Err(Error::ConnectionClosed);
+        }
+        let sync = self.sync.fetch_add(1, Ordering::Relaxed);
+        let header = Value::Map(vec![
+            (Value::from(REQUEST_TYPE), Value::from(request_type)),
+            (Value::from(SYNC), Value::from(sync)),
+        ]);
+
+        let mut payload = Vec::new();
+        rmpv::encode::write_value(&mut payload, &header)?;
+        rmpv::encode::write_value(&mut payload, &Value::Map(body))?;
+        let mut packet = Vec::with_capacity(payload.len() + 5);
+        // The length prefix is always a 32-bit msgpack unsigned integer
+        packet.push(0xce);
+        packet.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        packet.extend_from_slice(&payload);
+
+        let (sender, receiver) = oneshot::channel();
+        self.lock_pending().insert(sync, sender);
+        // The reader may have failed the pending requests before the insert
+        if self.is_closed() {
+            self.lock_pending().remove(&sync);
+            return Err(Error::ConnectionClosed);
+        }
+        if self.packets.send(packet).is_err() {
+            self.lock_pending().remove(&sync);
+            return Err(Error::ConnectionClosed);
+        }
+
+        receiver.await.map_err(|_| Error::ConnectionClosed)?
+    }
+
+    fn lock_pending(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<Result<Value, Error>>>> {
+        self.pending.lock().expect("pending requests poisoned")
+    }
+
+    /// Writes queued packets until the connection fails, then fails pending requests.
+    async fn write_loop(
+        mut write: OwnedWriteHalf,
+        mut queued: mpsc::UnboundedReceiver<Vec<u8>>,
+        pending: Pending,
+        closed: Arc<AtomicBool>,
+    ) {
+        while let Some(packet) = queued.recv().await {
+            if let Err(error) = write.write_all(&packet).await {
+                tracing::debug!(%error, "Tarantool connection write failed");
+                closed.store(true, Ordering::Release);
+                // Dropping the senders fails the waiting requests
+                pending.lock().expect("pending requests poisoned").clear();
+                return;
+            }
+        }
+    }
+
+    /// Dispatches responses until the connection fails, then fails pending requests.
+    async fn read_loop(
+        mut read: BufReader<OwnedReadHalf>,
+        pending: Pending,
+        closed: Arc<AtomicBool>,
+    ) {
+        loop {
+            match Self::read_response(&mut read).await {
+                Ok(Some((sync, response))) => {
+                    let sender = pending
+                        .lock()
+                        .expect("pending requests poisoned")
+                        .remove(&sync);
+                    if let Some(sender) = sender {
+                        let _ = sender.send(response);
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    tracing::debug!(%error, "Tarantool connection closed");
+                    break;
+                }
+            }
+        }
+        closed.store(true, Ordering::Release);
+        // Dropping the senders fails the waiting requests
+        pending.lock().expect("pending requests poisoned").clear();
+    }
+
+    /// Reads one response. Returns `None` for messages that answer no request.
+    async fn read_response(
+        read: &mut BufReader<OwnedReadHalf>,
+    ) -> Result<Option<(u64, Result<Value, Error>)>, Error> {
+        let len = match read.read_u8().await? {
+            len @ 0x00..=0x7f => len as usize,
+            0xcc => read.read_u8().await? as usize,
+            0xcd => read.read_u16().await? as usize,
+            0xce => read.read_u32().await? as usize,
+            _ => return Err(Error::Protocol("invalid packet length")),
+        };
+        let mut packet = vec![0; len];
+        read.read_exact(&mut packet).await?;
+
+        let mut cursor = packet.as_slice();
+        let header = rmpv::decode::read_value(&mut cursor)?;
+        let body = if cursor.is_empty() {
+            Value::Map(Vec::new())
+        } else {
+            rmpv::decode::read_value(&mut cursor)?
+        };
+
+        let code = field(header.clone(), REQUEST_TYPE)
+            .and_then(|code| code.as_u64())
+            .ok_or(Error::Protocol("response without a code"))?;
+        let sync = field(header, SYNC)
+            .and_then(|sync| sync.as_u64())
+            .ok_or(Error::Protocol("response without a sync"))?;
+
+        Ok(match code {
+            0 => Some((sync, Ok(body))),
+            TYPE_CHUNK => None,
+            code if code & TYPE_ERROR != 0 => {
+                let message = field(body, ERROR_24)
+                    .and_then(|message| message.as_str().map(str::to_owned))
+                    .unwrap_or_default();
+                Some((
+                    sync,
+                    Err(Error::Server {
+                        code: (code & !TYPE_ERROR) as u32,
+                        message,
+                    }),
+                ))
+            }
+            _ => Some((sync, Err(Error::Protocol("unexpected response type")))),
+        })
+    }
+
+    /// Extracts the authentication salt from the server greeting.
+    fn salt(greeting: &[u8; GREETING_LEN]) -> Result<Vec<u8>, Error> {
+        let line = std::str::from_utf8(&greeting[GREETING_LEN / 2..])
+            .map_err(|_| Error::Protocol("invalid greeting"))?;
+        let salt = BASE64
+            .decode(line.trim())
+            .map_err(|_| Error::Protocol("invalid greeting salt"))?;
+        if salt.len() < SCRAMBLE_LEN {
+            return Err(Error::Protocol("greeting salt too short"));
+        }
+        Ok(salt)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.reader.abort();
+        self.writer.abort();
+    }
+}
+
+/// Returns the value of `key` in a msgpack map.
+fn field(map: Value, key: u8) -> Option<Value> {
+    match map {
+        Value::Map(entries) => entries
+            .into_iter()
+            .find(|(k, _)| k.as_u64() == Some(key as u64))
+            .map(|(_, v)| v),
+        _ => None,
+    }
+}
+
+/// Computes the `chap-sha1` scramble:
+/// `sha1(password) XOR sha1(salt + sha1(sha1(password)))`.
+fn scramble(salt: &[u8], password: &str) -> [u8; SCRAMBLE_LEN] {
+    let hash1 = sha1_smol::Sha1::from(password).digest().bytes();
+    let hash2 = sha1_smol::Sha1::from(hash1).digest().bytes();
+    let mut hasher = sha1_smol::Sha1::new();
+    hasher.update(&salt[..SCRAMBLE_LEN]);
+    hasher.update(&hash2);
+    let hash3 = hasher.digest().bytes();
+
+    let mut scramble = [0; SCRAMBLE_LEN];
+    for (i, byte) in scramble.iter_mut().enumerate() {
+        *byte = hash1[i] ^ hash3[i];
+    }
+    scramble
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_salt_from_greeting() {
+        let mut greeting = [b' '; GREETING_LEN];
+        let version = b"Tarantool 3.2.0 (Binary) 7b3f1b0e-0000-0000-0000-000000000000";
+        greeting[..version.len()].copy_from_slice(version);
+        greeting[63] = b'\n';
+        let salt = BASE64.encode([7u8; 32]);
+        greeting[64..64 + salt.len()].copy_from_slice(salt.as_bytes());
+        greeting[127] = b'\n';
+
+        assert_eq!(Connection::salt(&greeting).unwrap(), vec![7u8; 32]);
+    }
+
+    /// Serves one connection: greets, then answers each request with its
+    /// arguments, or an error for the `fail` function.
+    async fn serve(listener: tokio::net::TcpListener) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut greeting = format!("{:<63}\n", "Tarantool 3.2.0 (Binary)");
+        greeting.push_str(&format!("{:<63}\n", BASE64.encode([1u8; 32])));
+        stream.write_all(greeting.as_bytes()).await.unwrap();
+
+        loop {
+            if stream.read_u8().await.is_err() {
+                return;
+            }
+            let len = stream.read_u32().await.unwrap() as usize;
+            let mut packet = vec![0; len];
+            stream.read_exact(&mut packet).await.unwrap();
+            let mut cursor = packet.as_slice();
+            let header = rmpv::decode::read_value(&mut cursor).unwrap();
+            let body = rmpv::decode::read_value(&mut cursor).unwrap();
+            let sync = field(header.clone(), SYNC).unwrap();
+
+            let failed = field(body.clone(), FUNCTION_NAME) == Some(Value::from("fail"));
+            let (code, body) = if failed {
+                (
+                    TYPE_ERROR | 33,
+                    vec![(
+                        Value::from(ERROR_24),
+                        Value::from("Procedure 'fail' is not defined"),
+                    )],
+                )
+            } else {
+                let args = field(body, TUPLE).unwrap_or(Value::Array(Vec::new()));
+                (0, vec![(Value::from(DATA), args)])
+            };
+            let header = Value::Map(vec![
+                (Value::from(REQUEST_TYPE), Value::from(code)),
+                (Value::from(SYNC), sync),
+            ]);
+            let mut payload = Vec::new();
+            rmpv::encode::write_value(&mut payload, &header).unwrap();
+            rmpv::encode::write_value(&mut payload, &Value::Map(body)).unwrap();
+            let mut response = vec![0xce];
+            response.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+            response.extend_from_slice(&payload);
+            stream.write_all(&response).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_and_error_responses() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = tokio::spawn(serve(listener));
+
+        let connection = Connection::connect(&address, Some(("hitbox", "secret")))
+            .await
+            .unwrap();
+
+        let args = vec![Value::from("space"), Value::Binary(b"key".to_vec())];
+        let (first, second) = tokio::join!(
+            connection.call("hitbox.get", args.clone()),
+            connection.eval("return ...", vec![Value::Nil]),
+        );
+        assert_eq!(first.unwrap(), args);
+        assert_eq!(second.unwrap(), vec![Value::Nil]);
+
+        match connection.call("fail", Vec::new()).await {
+            Err(Error::Server { code, message }) => {
+                assert_eq!(code, 33);
+                assert!(message.contains("fail"));
+            }
+            other => panic!("expected a server error, got {other:?}"),
+        }
+
+        server.abort();
+        let _ = server.await;
+        assert!(connection.call("hitbox.get", Vec::new()).await.is_err());
+        assert!(connection.is_closed());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_request_does_not_corrupt_connection() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = tokio::spawn(serve(listener));
+        let connection = Connection::connect(&address, None).await.unwrap();
+
+        // Large enough that writing it cannot complete in a single poll
+        let large = vec![Value::Binary(vec![0; 8 << 20])];
+        let cancelled = tokio::time::timeout(
+            std::time::Duration::ZERO,
+            connection.call("hitbox.set", large),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        let args = vec![Value::from("after")];
+        let next = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            connection.call("echo", args.clone()),
+        )
+        .await
+        .expect("a torn packet stalls the connection");
+        assert_eq!(next.unwrap(), args);
+        assert!(!connection.is_closed());
+
+        server.abort();
+    }
+
+    #[test]
+    fn test_scramble_is_reversible_with_stored_hash() {
+        // The server stores sha1(sha1(password)) and checks that
+        // sha1(scramble XOR sha1(salt + stored)) equals the stored hash.
+        let salt = [3u8; 32];
+        let scramble = scramble(&salt, "secret");
+
+        let hash1 = sha1_smol::Sha1::from("secret").digest().bytes();
+        let stored = sha1_smol::Sha1::from(hash1).digest().bytes();
+        let mut hasher = sha1_smol::Sha1::new();
+        hasher.update(&salt[..SCRAMBLE_LEN]);
+        hasher.update(&stored);
+        let hash3 = hasher.digest().bytes();
+
+        let candidate: Vec<u8> = scramble.iter().zip(hash3).map(|(a, b)| a ^ b).collect();
+        assert_eq!(sha1_smol::Sha1::from(&candidate).digest().bytes(), stored);
+    }
+}
@@ -1,6 +1,11 @@
 #![doc = include_str!("../README.md")]
+#![warn(missing_docs)]
 
 pub mod backend;
+pub mod error;
+mod iproto;
 
 #[doc(inline)]
-pub use crate::backend::Tarantool;
+pub use crate::backend::{TarantoolBackend, TarantoolBackendBuilder};
+#[doc(inline)]
+pub use crate::error::Error;
@@ -1,272 +1,135 @@
-use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use hitbox_backend::{CacheBackend, DeleteStatus};
-use hitbox_core::{CacheKey, CachePolicy, CacheableResponse, CachedValue, PredicateResult};
-use hitbox_tarantool::{backend::CacheEntry, backend::TarantoolBackend, Tarantool};
-use once_cell::sync::Lazy;
-use rusty_tarantool::tarantool::{Client, ClientConfig, ExecWithParamaters};
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, str::FromStr, thread, time::Duration};
-use testcontainers::{clients, core::WaitFor, Container, Image};
-
-static DOCKER: Lazy<clients::Cli> = Lazy::new(clients::Cli::default);
-
-impl Image for TarantoolImage {
-    type Args = ();
-
-    fn name(&self) -> String {
-        "tarantool/tarantool".to_owned()
-    }
-
-    fn tag(&self) -> String {
-        "latest".to_owned()
-    }
-
-    fn ready_conditions(&self) -> Vec<WaitFor> {
-        vec![WaitFor::Healthcheck]
-    }
-
-    fn expose_ports(&self) -> Vec<u16> {
-        vec![3301]
-    }
-
-    fn env_vars(&self) -> Box<dyn Iterator<Item = (&String, &String)> + '_> {
-        Box::new(self.env_vars.iter())
-    }
-}
-
-#[derive(Debug)]
-struct TarantoolImage {
-    env_vars: HashMap<String, String>,
-}
-
-impl<'a> TarantoolImage {
-    async fn start() -> TarantoolContainer<'a> {
-        let container = DOCKER.run(TarantoolImage::default());
-        let port = &container.ports().map_to_host_port_ipv4(3301).unwrap();
-        let client =
-            ClientConfig::new(format!("{}:{}", "127.0.0.1", &port), "hitbox", "hitbox").build();
-        let mut backend = Tarantool::builder().port(port.to_string()).build();
-        backend.init().await.unwrap();
-        TarantoolContainer {
-            _container: container,
-            client,
-            backend,
-        }
-    }
-}
-
-struct TarantoolContainer<'a> {
-    _container: Container<'a, TarantoolImage>,
-    client: Client,
-    backend: TarantoolBackend,
-}
-
-impl<'a> TarantoolContainer<'a> {
-    async fn eval<T, R>(&self, cmd: &str, params: &T) -> R
-    where
-        T: Serialize,
-        R: Deserialize<'a>,
-    {
-        self.client
-            .eval(cmd, params)
-            .await
-            .unwrap()
-            .decode()
-            .unwrap()
-    }
-}
-
-impl Default for TarantoolImage {
-    fn default() -> Self {
-        let mut env_vars = HashMap::new();
-        env_vars.insert("TARANTOOL_USER_NAME".to_owned(), "hitbox".to_owned());
-        env_vars.insert("TARANTOOL_USER_PASSWORD".to_owned(), "hitbox".to_owned());
-
-        Self { env_vars }
-    }
-}
-
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
-struct Test {
-    a: i32,
-    b: String,
-}
-
-#[async_trait]
-impl CacheableResponse for Test {
-    type Cached = Self;
-    type Subject = Self;
-
-    async fn cache_policy<P>(self, predicates: P) -> hitbox_core::ResponseCachePolicy<Self>
-    where
-        P: hitbox_core::Predicate<Subject = Self::Subject> + Send + Sync,
-    {
-        match predicates.check(self).await {
-            PredicateResult::Cacheable(cacheable) => match cacheable.into_cached().await {
-                CachePolicy::Cacheable(res) => {
-                    CachePolicy::Cacheable(CachedValue::new(res, Utc::now()))
-                }
-                CachePolicy::NonCacheable(res) => CachePolicy::NonCacheable(res),
-            },
-            PredicateResult::NonCacheable(res) => CachePolicy::NonCacheable(res),
-        }
-    }
-    async fn into_cached(self) -> CachePolicy<Self::Cached, Self> {
-        CachePolicy::Cacheable(self)
-    }
-    async fn from_cached(cached: Self::Cached) -> Self {
-        cached
-    }
-}
-
-impl Default for Test {
-    fn default() -> Self {
-        Self {
-            a: 42,
-            b: "nope".to_owned(),
-        }
-    }
+//! Tests against a local Tarantool instance.
+//!
+//! Run with `cargo test -p hitbox-tarantool -- --ignored` after starting one,
+//! e.g. `docker run -p 3301:3301 -e TARANTOOL_USER_NAME=hitbox
+//! -e TARANTOOL_USER_PASSWORD=hitbox tarantool/tarantool`. The address and
+//! credentials are read from `TARANTOOL_ADDR`, `TARANTOOL_USER` and
+//! `TARANTOOL_PASSWORD`, defaulting to `127.0.0.1:3301` and `hitbox`/`hitbox`.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{SubsecRound, Utc};
+use hitbox::{CacheKey, CacheValue};
+use hitbox_backend::{Backend, DeleteStatus};
+use hitbox_tarantool::TarantoolBackend;
+
+async fn backend(space: &str) -> TarantoolBackend {
+    let env = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.into());
+    let backend = TarantoolBackend::builder()
+        .address(env("TARANTOOL_ADDR", "127.0.0.1:3301"))
+        .credentials(
+            env("TARANTOOL_USER", "hitbox"),
+            env("TARANTOOL_PASSWORD", "hitbox"),
+        )
+        .space(space)
+        .build();
+    backend
+        .init()
+        .await
+        .expect("failed to initialize Tarantool");
+    // Idempotent
+    backend
+        .init()
+        .await
+        .expect("failed to initialize Tarantool");
+    backend
 }
 
 #[tokio::test]
-async fn test_init() {
-    let t = TarantoolImage::start().await;
+#[ignore = "requires a local Tarantool instance"]
+async fn test_write_and_read() {
+    let backend = backend("hitbox_test_write_and_read").await;
+    let key = CacheKey::from_str("test_key", "1");
+    let expire = Utc::now() + chrono::Duration::seconds(60);
+    let stale = Utc::now() + chrono::Duration::seconds(30);
+    let value = CacheValue::new(Bytes::from_static(b"value"), Some(expire), Some(stale));
 
-    let space_exists: (bool,) = t
-        .eval(
-            "return box.space[...] and true or false",
-            &("hitbox_cache",),
-        )
-        .await;
-    assert!(space_exists.0);
+    backend.write(&key, value).await.unwrap();
 
-    let fiber_exists: (bool,) = t
-        .eval(
-            "return _G[...] and true or false",
-            &("__hitbox_cache_fiber",),
-        )
-        .await;
-    assert!(fiber_exists.0);
+    let read = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"value");
+    assert_eq!(read.stale(), Some(stale).map(|s| s.trunc_subsecs(3)));
+    let read_expire = read.expire().unwrap();
+    assert!((read_expire - expire).num_milliseconds().abs() < 1000);
 }
 
 #[tokio::test]
-async fn test_set() {
-    let t = TarantoolImage::start().await;
+#[ignore = "requires a local Tarantool instance"]
+async fn test_write_without_expiry() {
+    let backend = backend("hitbox_test_without_expiry").await;
     let key = CacheKey::from_str("test_key", "1");
-    let dt = "2012-12-12T12:12:12Z";
-    let ttl = 42;
-    let value = CachedValue::new(Test::default(), DateTime::from_str(dt).unwrap());
 
-    t.backend
-        .set::<Test>(&key, &value, Some(ttl))
-        .await
-        .unwrap();
-
-    let result = t
-        .client
-        .prepare_fn_call("box.space.hitbox_cache:get")
-        .bind_ref(&key.serialize())
-        .unwrap()
-        .execute()
+    backend
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"value"), None, None),
+        )
         .await
-        .unwrap()
-        .decode_single::<CacheEntry<Test>>()
         .unwrap();
 
-    assert_eq!(&result.ttl.unwrap(), &ttl);
-    assert_eq!(&result.value.into_cached_value().data, &Test::default());
+    let read = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.expire(), None);
+    assert_eq!(read.stale(), None);
 }
 
 #[tokio::test]
+#[ignore = "requires a local Tarantool instance"]
 async fn test_expire() {
-    let t = TarantoolImage::start().await;
+    let backend = backend("hitbox_test_expire").await;
     let key = CacheKey::from_str("test_key", "1");
-    let dt = "2012-12-12T12:12:12Z";
-    let value = CachedValue::new(Test::default(), DateTime::from_str(dt).unwrap());
-
-    t.backend.set::<Test>(&key, &value, Some(0)).await.unwrap();
-
-    thread::sleep(Duration::from_secs(1));
+    let expire = Utc::now() + chrono::Duration::milliseconds(200);
 
-    let result = t
-        .client
-        .prepare_fn_call("box.space.hitbox_cache:get")
-        .bind_ref(&key.serialize())
-        .unwrap()
-        .execute()
+    backend
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"value"), Some(expire), None),
+        )
         .await
-        .unwrap()
-        .decode_result_set::<CacheEntry<Test>>()
         .unwrap();
-    assert!(result.is_empty())
+    assert!(backend.read(&key).await.unwrap().is_some());
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(backend.read(&key).await.unwrap().is_none());
 }
 
 #[tokio::test]
-async fn test_delete() {
-    let t = TarantoolImage::start().await;
+#[ignore = "requires a local Tarantool instance"]
+async fn test_remove() {
+    let backend = backend("hitbox_test_remove").await;
     let key = CacheKey::from_str("test_key", "1");
-    let dt: DateTime<Utc> = DateTime::from_str("2012-12-12T12:12:12Z").unwrap();
-    let value = Test::default();
-    let cached_value = CachedValue::new(&value, dt);
-    let entry = CacheEntry {
-        key: key.serialize(),
-        ttl: Some(42),
-        value: cached_value.into(),
-    };
-
-    let status = t.backend.delete(&key).await.unwrap();
-    assert_eq!(status, DeleteStatus::Missing);
-
-    t.client
-        .prepare_fn_call("box.space.hitbox_cache:replace")
-        .bind_ref(&entry)
-        .unwrap()
-        .execute()
-        .await
-        .unwrap();
 
-    let status = t.backend.delete(&key).await.unwrap();
-    assert_eq!(status, DeleteStatus::Deleted(1));
+    // Left over from an interrupted run
+    backend.remove(&key).await.unwrap();
+    assert_eq!(backend.remove(&key).await.unwrap(), DeleteStatus::Missing);
 
-    let result = t
-        .client
-        .prepare_fn_call("box.space.hitbox_cache:get")
-        .bind_ref(&key.serialize())
-        .unwrap()
-        .execute()
+    backend
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"value"), None, None),
+        )
         .await
-        .unwrap()
-        .decode_result_set::<CacheEntry<Test>>()
         .unwrap();
-
-    assert!(result.is_empty())
+    assert_eq!(
+        backend.remove(&key).await.unwrap(),
+        DeleteStatus::Deleted(1)
+    );
+    assert!(backend.read(&key).await.unwrap().is_none());
 }
 
 #[tokio::test]
-async fn test_get() {
-    let t = TarantoolImage::start().await;
+#[ignore = "requires a local Tarantool instance"]
+async fn test_spaces_are_isolated() {
+    let first = backend("hitbox_test_isolated_a").await;
+    let second = backend("hitbox_test_isolated_b").await;
     let key = CacheKey::from_str("test_key", "1");
-    let dt: DateTime<Utc> = DateTime::from_str("2012-12-12T12:12:12Z").unwrap();
-
-    let value = Test::default();
-    let cached_value = CachedValue::new(&value, dt);
-    let entry = CacheEntry {
-        key: key.serialize(),
-        ttl: Some(42),
-        value: cached_value.into(),
-    };
 
-    t.client
-        .prepare_fn_call("box.space.hitbox_cache:replace")
-        .bind_ref(&entry)
-        .unwrap()
-        .execute()
+    first
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"value"), None, None),
+        )
         .await
         .unwrap();
-
-    let data = t.backend.get::<Test>(&key).await.unwrap().unwrap();
-
-    assert_eq!(data.data, value);
-    assert_eq!(data.expired, dt);
+    assert!(second.read(&key).await.unwrap().is_none());
 }
@@ -1,12 +1,20 @@
-//! Benchmarks comparing different serialization formats (JSON, Bincode, RON, Rkyv)
+//! Benchmarks comparing different serialization formats (JSON, Bincode, RON, Rkyv,
+//! MessagePack, CBOR, Postcard)
 //! All tests use PassthroughCompressor to isolate format performance
+//! Serialized sizes are printed after the benchmarks
 
 use bytes::Bytes;
 use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
 use hitbox::{CacheKey, CacheableResponse};
+#[cfg(feature = "cbor_format")]
+use hitbox_backend::format::CborFormat;
+#[cfg(feature = "msgpack_format")]
+use hitbox_backend::format::MsgPackFormat;
+#[cfg(feature = "postcard_format")]
+use hitbox_backend::format::PostcardFormat;
 #[cfg(feature = "rkyv_format")]
 use hitbox_backend::format::RkyvFormat;
-use hitbox_backend::format::{BincodeFormat, JsonFormat, RonFormat};
+use hitbox_backend::format::{BincodeFormat, Format, FormatExt, JsonFormat, RonFormat};
 use hitbox_backend::{Backend, CacheBackend, PassthroughCompressor};
 use hitbox_core::{CacheContext, CacheValue};
 use hitbox_http::{BufferedBody, CacheableHttpResponse};
@@ -149,6 +157,71 @@ fn format_write_benchmark(c: &mut Criterion) {
                 });
             });
         }
+
+        // MessagePack (if feature enabled)
+        #[cfg(feature = "msgpack_format")]
+        {
+            let backend = MokaBackend::builder()
+                .max_entries(10000)
+                .value_format(MsgPackFormat)
+                .compressor(PassthroughCompressor)
+                .build();
+
+            group.bench_with_input(
+                BenchmarkId::new("msgpack", size_name),
+                &size_bytes,
+                |b, _| {
+                    let mut counter = 0u64;
+                    b.to_async(&runtime).iter(|| {
+                        let key_num = counter;
+                        counter = counter.wrapping_add(1);
+                        bench_write_single(&backend, &response, key_num)
+                    });
+                },
+            );
+        }
+
+        // CBOR (if feature enabled)
+        #[cfg(feature = "cbor_format")]
+        {
+            let backend = MokaBackend::builder()
+                .max_entries(10000)
+                .value_format(CborFormat)
+                .compressor(PassthroughCompressor)
+                .build();
+
+            group.bench_with_input(BenchmarkId::new("cbor", size_name), &size_bytes, |b, _| {
+                let mut counter = 0u64;
+                b.to_async(&runtime).iter(|| {
+                    let key_num = counter;
+                    counter = counter.wrapping_add(1);
+                    bench_write_single(&backend, &response, key_num)
+                });
+            });
+        }
+
+        // Postcard (if feature enabled)
+        #[cfg(feature = "postcard_format")]
+        {
+            let backend = MokaBackend::builder()
+                .max_entries(10000)
+                .value_format(PostcardFormat)
+                .compressor(PassthroughCompressor)
+                .build();
+
+            group.bench_with_input(
+                BenchmarkId::new("postcard", size_name),
+                &size_bytes,
+                |b, _| {
+                    let mut counter = 0u64;
+                    b.to_async(&runtime).iter(|| {
+                        let key_num = counter;
+                        counter = counter.wrapping_add(1);
+                        bench_write_single(&backend, &response, key_num)
+                    });
+                },
+            );
+        }
     }
 
     group.finish();
@@ -288,10 +361,165 @@ fn format_read_benchmark(c: &mut Criterion) {
                 });
             });
         }
+
+        // MessagePack - populate cache first (if feature enabled)
+        #[cfg(feature = "msgpack_format")]
+        {
+            let backend = MokaBackend::builder()
+                .max_entries(10000)
+                .value_format(MsgPackFormat)
+                .compressor(PassthroughCompressor)
+                .build();
+
+            runtime.block_on(async {
+                for i in 0..100 {
+                    let key = CacheKey::from_str("bench", &format!("key-{}", i));
+                    let value = CacheValue::new(response.clone(), None, None);
+                    let mut ctx = CacheContext::default().boxed();
+                    backend
+                        .set::<BenchResponse>(&key, &value, &mut ctx)
+                        .await
+                        .unwrap();
+                }
+            });
+
+            group.bench_with_input(
+                BenchmarkId::new("msgpack", size_name),
+                &size_bytes,
+                |b, _| {
+                    let mut counter = 0u64;
+                    b.to_async(&runtime).iter(|| {
+                        let key_num = counter % 100;
+                        counter = counter.wrapping_add(1);
+                        bench_read_single(&backend, key_num)
+                    });
+                },
+            );
+        }
+
+        // CBOR - populate cache first (if feature enabled)
+        #[cfg(feature = "cbor_format")]
+        {
+            let backend = MokaBackend::builder()
+                .max_entries(10000)
+                .value_format(CborFormat)
+                .compressor(PassthroughCompressor)
+                .build();
+
+            runtime.block_on(async {
+                for i in 0..100 {
+                    let key = CacheKey::from_str("bench", &format!("key-{}", i));
+                    let value = CacheValue::new(response.clone(), None, None);
+                    let mut ctx = CacheContext::default().boxed();
+                    backend
+                        .set::<BenchResponse>(&key, &value, &mut ctx)
+                        .await
+                        .unwrap();
+                }
+            });
+
+            group.bench_with_input(BenchmarkId::new("cbor", size_name), &size_bytes, |b, _| {
+                let mut counter = 0u64;
+                b.to_async(&runtime).iter(|| {
+                    let key_num = counter % 100;
+                    counter = counter.wrapping_add(1);
+                    bench_read_single(&backend, key_num)
+                });
+            });
+        }
+
+        // Postcard - populate cache first (if feature enabled)
+        #[cfg(feature = "postcard_format")]
+        {
+            let backend = MokaBackend::builder()
+                .max_entries(10000)
+                .value_format(PostcardFormat)
+                .compressor(PassthroughCompressor)
+                .build();
+
+            runtime.block_on(async {
+                for i in 0..100 {
+                    let key = CacheKey::from_str("bench", &format!("key-{}", i));
+                    let value = CacheValue::new(response.clone(), None, None);
+                    let mut ctx = CacheContext::default().boxed();
+                    backend
+                        .set::<BenchResponse>(&key, &value, &mut ctx)
+                        .await
+                        .unwrap();
+                }
+            });
+
+            group.bench_with_input(
+                BenchmarkId::new("postcard", size_name),
+                &size_bytes,
+                |b, _| {
+                    let mut counter = 0u64;
+                    b.to_async(&runtime).iter(|| {
+                        let key_num = counter % 100;
+                        counter = counter.wrapping_add(1);
+                        bench_read_single(&backend, key_num)
+                    });
+                },
+            );
+        }
     }
 
     group.finish();
 }
 
-criterion_group!(benches, format_write_benchmark, format_read_benchmark);
+/// Prints the serialized size of the same response in every format.
+fn format_size_comparison(_c: &mut Criterion) {
+    let sizes = [
+        ("1KB", 1024),
+        ("10KB", 10 * 1024),
+        ("100KB", 100 * 1024),
+        ("1MB", 1024 * 1024),
+    ];
+
+    #[allow(unused_mut)]
+    let mut formats: Vec<(&str, Box<dyn Format>)> = vec![
+        ("json", Box::new(JsonFormat)),
+        ("bincode", Box::new(BincodeFormat)),
+        ("ron", Box::new(RonFormat)),
+    ];
+    #[cfg(feature = "rkyv_format")]
+    formats.push(("rkyv", Box::new(RkyvFormat::new())));
+    #[cfg(feature = "msgpack_format")]
+    formats.push(("msgpack", Box::new(MsgPackFormat)));
+    #[cfg(feature = "cbor_format")]
+    formats.push(("cbor", Box::new(CborFormat)));
+    #[cfg(feature = "postcard_format")]
+    formats.push(("postcard", Box::new(PostcardFormat)));
+
+    println!("\n=== Serialized Size (bytes) ===\n");
+    print!("{:<10}", "Format");
+    for (size_name, _) in sizes {
+        print!(" {:>10}", size_name);
+    }
+    println!();
+    println!("{:-<54}", "");
+
+    let responses: Vec<_> = sizes
+        .iter()
+        .map(|(_, size_bytes)| generate_response(*size_bytes))
+        .collect();
+    for (name, format) in &formats {
+        print!("{:<10}", name);
+        for response in &responses {
+            let serialized = format
+                .serialize(response, &CacheContext::default())
+                .unwrap();
+            print!(" {:>10}", serialized.len());
+        }
+        println!();
+    }
+    println!();
+}
+
+criterion_group!(
+    benches,
+    format_write_benchmark,
+    format_read_benchmark,
+    format_size_comparison
+);
 criterion_main!(benches);
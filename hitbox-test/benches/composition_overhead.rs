@@ -3,7 +3,9 @@ use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_m
 use hitbox::{CacheKey, CacheableResponse};
 use hitbox_backend::composition::policy::{CompositionPolicy, RefillPolicy};
 use hitbox_backend::format::BincodeFormat;
-use hitbox_backend::{Backend, CacheBackend, CompositionBackend, PassthroughCompressor};
+use hitbox_backend::{
+    Backend, CacheBackend, CompositionBackend, PassthroughCompressor, TieredBackend,
+};
 use hitbox_core::{CacheContext, CacheValue, Offload, SmolStr};
 use hitbox_http::{BufferedBody, CacheableHttpResponse};
 use hitbox_moka::MokaBackend;
@@ -770,6 +772,134 @@ fn bench_nested_3_dyn(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark flat tiered composition, equivalent to `nested_2_concrete`
+fn bench_tiered_3(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("tiered_3");
+
+    let payload_sizes = [("1KB", 1024), ("10KB", 10 * 1024), ("100KB", 100 * 1024)];
+
+    for (size_name, size_bytes) in &payload_sizes {
+        group.throughput(Throughput::Bytes(*size_bytes as u64));
+
+        // Same 3 Moka tiers as the nested composition, in one flat list
+        let backend = (0..3).fold(TieredBackend::new(BenchOffload), |backend, _| {
+            backend.tier(
+                MokaBackend::builder()
+                    .max_entries(10000)
+                    .value_format(BincodeFormat)
+                    .compressor(PassthroughCompressor)
+                    .build(),
+            )
+        });
+
+        let response = runtime.block_on(generate_response(*size_bytes));
+        let key = CacheKey::from_str("bench", "key1");
+        let value = CacheValue::new(response.clone(), None, None);
+
+        // Pre-populate for read benchmark
+        runtime
+            .block_on(async {
+                let mut ctx = CacheContext::default().boxed();
+                backend.set::<BenchResponse>(&key, &value, &mut ctx).await
+            })
+            .unwrap();
+
+        // Write benchmark
+        group.bench_with_input(
+            BenchmarkId::new("moka_write", size_name),
+            &(&backend, &key, &value),
+            |b, (backend, key, value)| {
+                b.to_async(&runtime).iter(|| async {
+                    let mut ctx = CacheContext::default().boxed();
+                    backend
+                        .set::<BenchResponse>(key, value, &mut ctx)
+                        .await
+                        .unwrap();
+                });
+            },
+        );
+
+        // Read benchmark
+        group.bench_with_input(
+            BenchmarkId::new("moka_read", size_name),
+            &(&backend, &key),
+            |b, (backend, key)| {
+                b.to_async(&runtime).iter(|| async {
+                    let mut ctx = CacheContext::default().boxed();
+                    backend.get::<BenchResponse>(key, &mut ctx).await.unwrap();
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Benchmark flat tiered composition, equivalent to `nested_3_concrete`
+fn bench_tiered_4(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("tiered_4");
+
+    let payload_sizes = [("1KB", 1024), ("10KB", 10 * 1024), ("100KB", 100 * 1024)];
+
+    for (size_name, size_bytes) in &payload_sizes {
+        group.throughput(Throughput::Bytes(*size_bytes as u64));
+
+        // Same 4 Moka tiers as the nested composition, in one flat list
+        let backend = (0..4).fold(TieredBackend::new(BenchOffload), |backend, _| {
+            backend.tier(
+                MokaBackend::builder()
+                    .max_entries(10000)
+                    .value_format(BincodeFormat)
+                    .compressor(PassthroughCompressor)
+                    .build(),
+            )
+        });
+
+        let response = runtime.block_on(generate_response(*size_bytes));
+        let key = CacheKey::from_str("bench", "key1");
+        let value = CacheValue::new(response.clone(), None, None);
+
+        // Pre-populate for read benchmark
+        runtime
+            .block_on(async {
+                let mut ctx = CacheContext::default().boxed();
+                backend.set::<BenchResponse>(&key, &value, &mut ctx).await
+            })
+            .unwrap();
+
+        // Write benchmark
+        group.bench_with_input(
+            BenchmarkId::new("moka_write", size_name),
+            &(&backend, &key, &value),
+            |b, (backend, key, value)| {
+                b.to_async(&runtime).iter(|| async {
+                    let mut ctx = CacheContext::default().boxed();
+                    backend
+                        .set::<BenchResponse>(key, value, &mut ctx)
+                        .await
+                        .unwrap();
+                });
+            },
+        );
+
+        // Read benchmark
+        group.bench_with_input(
+            BenchmarkId::new("moka_read", size_name),
+            &(&backend, &key),
+            |b, (backend, key)| {
+                b.to_async(&runtime).iter(|| async {
+                    let mut ctx = CacheContext::default().boxed();
+                    backend.get::<BenchResponse>(key, &mut ctx).await.unwrap();
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_direct_moka,
@@ -781,6 +911,8 @@ criterion_group!(
     bench_nested_2_dyn,
     bench_nested_3_concrete,
     bench_nested_3_dyn,
+    bench_tiered_3,
+    bench_tiered_4,
 );
 
 criterion_main!(benches);
@@ -114,6 +114,7 @@ async fn test_moka_all_combinations() {
             },
             backend: Moka {
                 max_capacity: 1000,
+                namespace: None,
                 label: None,
             },
         };
@@ -150,6 +151,7 @@ async fn test_feoxdb_all_combinations() {
             },
             backend: FeOxDb {
                 path: None,
+                namespace: None,
                 label: None,
             },
         };
@@ -198,6 +200,9 @@ async fn test_redis_all_combinations() {
             },
             backend: Redis {
                 connection_string: connection_string.clone(),
+                sentinel: None,
+                tls: None,
+                namespace: None,
                 label: None,
             },
         };
@@ -1 +1,5 @@
 mod comprehensive_tests;
+mod redis_chunking;
+mod redis_client_side_cache;
+mod redis_expiry;
+mod redis_namespace;
@@ -0,0 +1,61 @@
+use bytes::Bytes;
+use hitbox::{CacheKey, CacheValue};
+use hitbox_backend::Backend;
+use hitbox_redis::{ConnectionMode, RedisBackend};
+use testcontainers::{ContainerAsync, runners::AsyncRunner};
+use testcontainers_modules::redis::Redis as RedisContainer;
+
+#[tokio::test]
+async fn test_redis_chunked_values_roundtrip() {
+    let container: ContainerAsync<RedisContainer> = RedisContainer::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+
+    let host = container.get_host().await.expect("failed to get host");
+    let host_port = container
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get port");
+    let connection_string = format!("redis://{}:{}", host, host_port);
+
+    let chunked = RedisBackend::builder()
+        .connection(ConnectionMode::single(connection_string.clone()))
+        .chunk_size(64 * 1024)
+        .build()
+        .expect("failed to create backend");
+    let plain = RedisBackend::builder()
+        .connection(ConnectionMode::single(connection_string))
+        .build()
+        .expect("failed to create backend");
+
+    let large: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+    let key = CacheKey::from_str("chunking", "large");
+    chunked
+        .write(
+            &key,
+            CacheValue::new(Bytes::from(large.clone()), None, None),
+        )
+        .await
+        .unwrap();
+
+    // Chunked entries are readable with or without chunking enabled.
+    for backend in [&chunked, &plain] {
+        let read = backend.read(&key).await.unwrap().unwrap();
+        assert_eq!(read.data().as_ref(), large.as_slice());
+    }
+
+    // Overwriting with a small value drops the chunks.
+    chunked
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"small"), None, None),
+        )
+        .await
+        .unwrap();
+    let read = chunked.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"small");
+
+    chunked.remove(&key).await.unwrap();
+    assert!(chunked.read(&key).await.unwrap().is_none());
+}
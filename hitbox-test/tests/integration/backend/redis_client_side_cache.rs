@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use hitbox::{CacheKey, CacheValue};
+use hitbox_backend::Backend;
+use hitbox_redis::{ConnectionMode, RedisBackend};
+use testcontainers::{ContainerAsync, runners::AsyncRunner};
+use testcontainers_modules::redis::Redis as RedisContainer;
+
+fn value(data: &'static [u8]) -> CacheValue<Bytes> {
+    CacheValue::new(
+        Bytes::from_static(data),
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        None,
+    )
+}
+
+#[tokio::test]
+async fn test_redis_client_side_cache_is_invalidated_by_other_clients() {
+    let container: ContainerAsync<RedisContainer> = RedisContainer::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+
+    let host = container.get_host().await.expect("failed to get host");
+    let host_port = container
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get port");
+    let connection_string = format!("redis://{}:{}", host, host_port);
+
+    let cached = RedisBackend::builder()
+        .connection(ConnectionMode::single(connection_string.clone()))
+        .client_side_cache(100)
+        .build()
+        .expect("failed to create backend");
+    let other = RedisBackend::builder()
+        .connection(ConnectionMode::single(connection_string))
+        .build()
+        .expect("failed to create backend");
+
+    let key = CacheKey::from_str("client_side_cache", "1");
+    other.write(&key, value(b"old")).await.unwrap();
+
+    // The first read populates the near-cache, the second is served from it.
+    for _ in 0..2 {
+        let read = cached.read(&key).await.unwrap().unwrap();
+        assert_eq!(read.data().as_ref(), b"old");
+    }
+
+    // A write from another client is pushed to the tracking connection.
+    other.write(&key, value(b"new")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let read = cached.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"new");
+
+    other.remove(&key).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(cached.read(&key).await.unwrap().is_none());
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use hitbox::{CacheKey, CacheValue};
+use hitbox_backend::Backend;
+use hitbox_redis::{ConnectionMode, ExpiryMode, RedisBackend};
+use testcontainers::{ContainerAsync, runners::AsyncRunner};
+use testcontainers_modules::redis::Redis as RedisContainer;
+
+#[tokio::test]
+async fn test_redis_sub_second_expiry() {
+    let container: ContainerAsync<RedisContainer> = RedisContainer::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+
+    let host = container.get_host().await.expect("failed to get host");
+    let host_port = container
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get port");
+    let connection_string = format!("redis://{}:{}", host, host_port);
+
+    for expiry in [ExpiryMode::Relative, ExpiryMode::Absolute] {
+        let backend = RedisBackend::builder()
+            .connection(ConnectionMode::single(connection_string.clone()))
+            .expiry(expiry)
+            .build()
+            .expect("failed to create backend");
+
+        let key = CacheKey::from_str("expiry", &format!("{expiry:?}"));
+        let value = CacheValue::new(
+            Bytes::from_static(b"short"),
+            Some(Utc::now() + chrono::Duration::milliseconds(300)),
+            None,
+        );
+        backend.write(&key, value).await.unwrap();
+
+        let read = backend.read(&key).await.unwrap();
+        assert!(
+            read.is_some(),
+            "{expiry:?}: entry should exist before expiry"
+        );
+        assert!(
+            read.unwrap().expire().unwrap() <= Utc::now() + chrono::Duration::milliseconds(300),
+            "{expiry:?}: TTL should keep millisecond precision"
+        );
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert!(
+            backend.read(&key).await.unwrap().is_none(),
+            "{expiry:?}: sub-second TTL should expire"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_redis_overwrite_replaces_stale_and_expiry() {
+    let container: ContainerAsync<RedisContainer> = RedisContainer::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+
+    let host = container.get_host().await.expect("failed to get host");
+    let host_port = container
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get port");
+
+    let backend = RedisBackend::builder()
+        .connection(ConnectionMode::single(format!(
+            "redis://{}:{}",
+            host, host_port
+        )))
+        .build()
+        .expect("failed to create backend");
+
+    let key = CacheKey::from_str("expiry", "overwrite");
+    let first = CacheValue::new(
+        Bytes::from_static(b"first"),
+        Some(Utc::now() + chrono::Duration::seconds(60)),
+        Some(Utc::now() + chrono::Duration::seconds(30)),
+    );
+    backend.write(&key, first).await.unwrap();
+
+    let second = CacheValue::new(Bytes::from_static(b"second"), None, None);
+    backend.write(&key, second).await.unwrap();
+
+    let read = backend.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"second");
+    assert_eq!(read.stale(), None);
+    assert_eq!(read.expire(), None);
+}
@@ -0,0 +1,54 @@
+use bytes::Bytes;
+use hitbox::{CacheKey, CacheValue};
+use hitbox_backend::Backend;
+use hitbox_redis::{ConnectionMode, RedisBackend};
+use testcontainers::{ContainerAsync, runners::AsyncRunner};
+use testcontainers_modules::redis::Redis as RedisContainer;
+
+#[tokio::test]
+async fn test_redis_namespaces_do_not_collide() {
+    let container: ContainerAsync<RedisContainer> = RedisContainer::default()
+        .start()
+        .await
+        .expect("failed to start Redis container");
+
+    let host = container.get_host().await.expect("failed to get host");
+    let host_port = container
+        .get_host_port_ipv4(6379)
+        .await
+        .expect("failed to get port");
+    let connection_string = format!("redis://{}:{}", host, host_port);
+
+    let backend = |namespace: &str| {
+        RedisBackend::builder()
+            .connection(ConnectionMode::single(connection_string.clone()))
+            .namespace(namespace)
+            .build()
+            .expect("failed to create backend")
+    };
+    let orders = backend("orders");
+    let users = backend("users");
+
+    let key = CacheKey::from_str("id", "1");
+    orders
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"order"), None, None),
+        )
+        .await
+        .unwrap();
+    users
+        .write(
+            &key,
+            CacheValue::new(Bytes::from_static(b"user"), None, None),
+        )
+        .await
+        .unwrap();
+
+    let read = orders.read(&key).await.unwrap().unwrap();
+    assert_eq!(read.data().as_ref(), b"order");
+
+    users.remove(&key).await.unwrap();
+    assert!(users.read(&key).await.unwrap().is_none());
+    assert!(orders.read(&key).await.unwrap().is_some());
+}
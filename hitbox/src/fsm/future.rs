@@ -298,6 +298,7 @@ where
 
                         // Create revalidation future using the existing FSM
                         // ReqP and E are phantom types in revalidation path
+                        let offload_key = cache_key.clone();
                         let revalidate_future: CacheFuture<'_, _, _, _, _, ReqP, _, E, _, _> =
                             CacheFuture::revalidate(
                                 backend,
@@ -308,9 +309,10 @@ where
                                 policy,
                             );
 
-                        this.offload.spawn("revalidate", async move {
-                            let _ = revalidate_future.await;
-                        });
+                        this.offload
+                            .spawn_for_key("revalidate", &offload_key, async move {
+                                let _ = revalidate_future.await;
+                            });
                     }
 
                     result.transition.into_state(&*this.span)
@@ -112,6 +112,22 @@ lazy_static! {
         );
         "hitbox_offload_task_duration_seconds"
     };
+    /// Track number of offload tasks dropped by a full pool queue.
+    pub static ref OFFLOAD_TASKS_DROPPED: &'static str = {
+        metrics::describe_counter!(
+            "hitbox_offload_tasks_dropped_total",
+            "Total number of offload tasks dropped because their pool queue was full."
+        );
+        "hitbox_offload_tasks_dropped_total"
+    };
+    /// Gauge of offload tasks waiting to start, per pool.
+    pub static ref OFFLOAD_POOL_QUEUED: &'static str = {
+        metrics::describe_gauge!(
+            "hitbox_offload_pool_queued",
+            "Number of offload tasks waiting for a pool permit."
+        );
+        "hitbox_offload_pool_queued"
+    };
     /// Track number of offload revalidations completed.
     pub static ref OFFLOAD_REVALIDATION_COMPLETED: &'static str = {
         metrics::describe_counter!(
@@ -14,11 +14,13 @@ use tracing::{Instrument, debug, info_span, warn};
 use crate::CacheKey;
 
 use super::policy::{OffloadConfig, TimeoutPolicy};
+use super::pool::{Admission, Evicted, Pool, TaskRoute};
 
 #[cfg(feature = "metrics")]
 use crate::metrics::{
-    OFFLOAD_TASK_DURATION, OFFLOAD_TASKS_ACTIVE, OFFLOAD_TASKS_COMPLETED,
-    OFFLOAD_TASKS_DEDUPLICATED, OFFLOAD_TASKS_SPAWNED, OFFLOAD_TASKS_TIMEOUT,
+    OFFLOAD_POOL_QUEUED, OFFLOAD_TASK_DURATION, OFFLOAD_TASKS_ACTIVE, OFFLOAD_TASKS_COMPLETED,
+    OFFLOAD_TASKS_DEDUPLICATED, OFFLOAD_TASKS_DROPPED, OFFLOAD_TASKS_SPAWNED,
+    OFFLOAD_TASKS_TIMEOUT,
 };
 
 /// Key for identifying offloaded tasks.
@@ -78,11 +80,24 @@ struct OffloadManagerInner {
     config: OffloadConfig,
     tasks: DashMap<OffloadKey, OffloadHandle>,
     key_counter: AtomicU64,
+    pools: Vec<Arc<Pool>>,
+    default_pool: Arc<Pool>,
+}
+
+impl OffloadManagerInner {
+    /// Pick the pool for a task: the first matching named pool, or the default one.
+    fn route(&self, route: &TaskRoute<'_>) -> &Arc<Pool> {
+        self.pools
+            .iter()
+            .find(|pool| pool.matches(route))
+            .unwrap_or(&self.default_pool)
+    }
 }
 
 /// Manager for offloading tasks to background execution.
 ///
-/// Supports task deduplication, timeout policies, and metrics collection.
+/// Supports task deduplication, timeout policies, per-pool concurrency and
+/// rate limits (see [`PoolConfig`](super::PoolConfig)), and metrics collection.
 #[derive(Clone, Debug)]
 pub struct OffloadManager {
     inner: Arc<OffloadManagerInner>,
@@ -91,11 +106,20 @@ pub struct OffloadManager {
 impl OffloadManager {
     /// Create a new OffloadManager with the given configuration.
     pub fn new(config: OffloadConfig) -> Self {
+        let pools = config
+            .pools
+            .iter()
+            .cloned()
+            .map(|pool| Arc::new(Pool::new(pool)))
+            .collect();
+        let default_pool = Arc::new(Pool::default_pool(config.max_concurrent_tasks));
         Self {
             inner: Arc::new(OffloadManagerInner {
                 config,
                 tasks: DashMap::new(),
                 key_counter: AtomicU64::new(0),
+                pools,
+                default_pool,
             }),
         }
     }
@@ -128,23 +152,54 @@ impl OffloadManager {
         F: Future<Output = ()> + Send + 'static,
     {
         let key = self.next_key(kind);
-        self.spawn_with_key(key.clone(), task);
+        self.spawn_routed(key.clone(), None, task);
+        key
+    }
+
+    /// Spawn a task with auto-generated key on behalf of a cache key.
+    ///
+    /// Behaves like [`spawn`](Self::spawn), except that pools matching on
+    /// [`key_prefix`](super::PoolConfig::key_prefix) see the prefix of `cache_key`.
+    /// The task is not deduplicated against other tasks for the same cache key.
+    pub fn spawn_for_key<F>(
+        &self,
+        kind: impl Into<SmolStr>,
+        cache_key: &CacheKey,
+        task: F,
+    ) -> OffloadKey
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let key = self.next_key(kind);
+        self.spawn_routed(key.clone(), Some(cache_key.prefix()), task);
         key
     }
 
     /// Spawn a task with a specific key.
     ///
     /// If a task with the same key is already in flight and deduplication
-    /// is enabled, the new task will be skipped.
+    /// is enabled, the new task will be skipped. The task is also skipped
+    /// if its pool queue is full and the pool drops new tasks on overflow.
     ///
-    /// Returns `true` if the task was spawned, `false` if it was deduplicated.
+    /// Returns `true` if the task was spawned, `false` if it was deduplicated
+    /// or dropped.
     pub fn spawn_with_key<K, F>(&self, key: K, task: F) -> bool
     where
         K: Into<OffloadKey>,
         F: Future<Output = ()> + Send + 'static,
     {
         let key = key.into();
+        let key_prefix = match &key {
+            OffloadKey::Cache(cache_key) => Some(SmolStr::new(cache_key.prefix())),
+            OffloadKey::Generated { .. } => None,
+        };
+        self.spawn_routed(key, key_prefix.as_deref(), task)
+    }
 
+    fn spawn_routed<F>(&self, key: OffloadKey, key_prefix: Option<&str>, task: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
         // Check for deduplication (only for Cache keys)
         if self.inner.config.deduplicate
             && matches!(&key, OffloadKey::Cache(_))
@@ -157,10 +212,37 @@ impl OffloadManager {
             return false;
         }
 
-        #[cfg(feature = "metrics")]
         let key_type = key.key_type();
+        let pool = self
+            .inner
+            .route(&TaskRoute {
+                kind: &key_type,
+                key_prefix,
+            })
+            .clone();
+
+        let Some(admission) = pool.admit(self.inner.key_counter.fetch_add(1, Ordering::Relaxed))
+        else {
+            debug!(
+                ?key,
+                pool = %pool.name(),
+                overflow = pool.overflow().label(),
+                "Task dropped - pool queue is full"
+            );
+            #[cfg(feature = "metrics")]
+            metrics::counter!(
+                *OFFLOAD_TASKS_DROPPED,
+                "key_type" => key_type.to_string(),
+                "pool" => pool.name().to_string(),
+                "reason" => pool.overflow().label()
+            )
+            .increment(1);
+            return false;
+        };
+        #[cfg(feature = "metrics")]
+        Self::record_queued(&pool);
 
-        let handle = self.spawn_inner(task, key.clone());
+        let handle = self.spawn_inner(task, key.clone(), pool, admission);
         self.inner.tasks.insert(key, handle);
 
         #[cfg(feature = "metrics")]
@@ -179,6 +261,19 @@ impl OffloadManager {
         self.inner.tasks.iter().filter(|e| !e.is_finished()).count()
     }
 
+    /// Get the number of tasks waiting to start in the named pool.
+    ///
+    /// Use [`DEFAULT_POOL`](super::DEFAULT_POOL) for tasks matching no
+    /// configured pool. Returns `None` if there is no pool with that name.
+    pub fn queued_task_count(&self, pool: &str) -> Option<usize> {
+        self.inner
+            .pools
+            .iter()
+            .chain(std::iter::once(&self.inner.default_pool))
+            .find(|p| p.name() == pool)
+            .map(|p| p.queued())
+    }
+
     /// Get the total number of tracked tasks (including finished).
     pub fn total_task_count(&self) -> usize {
         self.inner.tasks.len()
@@ -241,7 +336,52 @@ impl OffloadManager {
         }
     }
 
-    fn spawn_inner<F>(&self, task: F, key: OffloadKey) -> OffloadHandle
+    /// Wait for the pool to let the task start.
+    ///
+    /// On eviction the task is forgotten and `None` is returned; otherwise the
+    /// returned guard holds the pool permit for as long as the task runs.
+    async fn wait_turn(
+        inner: &OffloadManagerInner,
+        key: &OffloadKey,
+        pool: &Pool,
+        admission: Admission,
+    ) -> Option<Option<tokio::sync::OwnedSemaphorePermit>> {
+        let ticket = match admission {
+            Admission::Ready(permit) => return Some(permit),
+            Admission::Queued(ticket) => ticket,
+        };
+        let result = ticket.acquire().await;
+        #[cfg(feature = "metrics")]
+        Self::record_queued(pool);
+        match result {
+            Ok(permit) => Some(permit),
+            Err(Evicted) => {
+                debug!(?key, pool = %pool.name(), "Task dropped - evicted from pool queue");
+                inner.tasks.remove(key);
+                #[cfg(feature = "metrics")]
+                {
+                    let key_type = key.key_type().to_string();
+                    metrics::counter!(
+                        *OFFLOAD_TASKS_DROPPED,
+                        "key_type" => key_type.clone(),
+                        "pool" => pool.name().to_string(),
+                        "reason" => "drop_oldest"
+                    )
+                    .increment(1);
+                    metrics::gauge!(*OFFLOAD_TASKS_ACTIVE, "key_type" => key_type).decrement(1.0);
+                }
+                None
+            }
+        }
+    }
+
+    fn spawn_inner<F>(
+        &self,
+        task: F,
+        key: OffloadKey,
+        pool: Arc<Pool>,
+        admission: Admission,
+    ) -> OffloadHandle
     where
         F: Future<Output = ()> + Send + 'static,
     {
@@ -253,11 +393,16 @@ impl OffloadManager {
             "offload_task",
             key_type = %key_type,
             key = ?key,
+            pool = %pool.name(),
         );
 
         let handle = match timeout_policy {
             TimeoutPolicy::None => tokio::spawn(
                 async move {
+                    let Some(_permit) = Self::wait_turn(&inner, &key, &pool, admission).await
+                    else {
+                        return;
+                    };
                     #[cfg(feature = "metrics")]
                     let start = Instant::now();
                     task.await;
@@ -269,6 +414,10 @@ impl OffloadManager {
             ),
             TimeoutPolicy::Cancel(duration) => tokio::spawn(
                 async move {
+                    let Some(_permit) = Self::wait_turn(&inner, &key, &pool, admission).await
+                    else {
+                        return;
+                    };
                     #[cfg(feature = "metrics")]
                     let start = Instant::now();
                     match tokio::time::timeout(duration, task).await {
@@ -288,6 +437,10 @@ impl OffloadManager {
             ),
             TimeoutPolicy::Warn(duration) => tokio::spawn(
                 async move {
+                    let Some(_permit) = Self::wait_turn(&inner, &key, &pool, admission).await
+                    else {
+                        return;
+                    };
                     let start = Instant::now();
                     task.await;
                     let elapsed = start.elapsed();
@@ -320,6 +473,12 @@ impl OffloadManager {
             .record(duration);
     }
 
+    #[cfg(feature = "metrics")]
+    fn record_queued(pool: &Pool) {
+        metrics::gauge!(*OFFLOAD_POOL_QUEUED, "pool" => pool.name().to_string())
+            .set(pool.queued() as f64);
+    }
+
     #[cfg(feature = "metrics")]
     fn record_timeout(start: Instant, key_type: &SmolStr) {
         let duration = start.elapsed().as_secs_f64();
@@ -343,4 +502,11 @@ impl hitbox_core::Offload<'static> for OffloadManager {
     {
         OffloadManager::spawn(self, kind, future);
     }
+
+    fn spawn_for_key<F>(&self, kind: impl Into<SmolStr>, key: &CacheKey, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        OffloadManager::spawn_for_key(self, kind, key, future);
+    }
 }
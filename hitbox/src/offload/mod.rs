@@ -17,10 +17,19 @@
 //!     // Revalidation logic here
 //! });
 //! ```
+//!
+//! # Pools
+//!
+//! Tasks can be split into [`PoolConfig`] pools by task kind or cache key
+//! prefix. Each pool has its own concurrency limit, optional [`RateLimit`]
+//! and a bounded queue whose [`OverflowPolicy`] decides what happens when
+//! it fills up.
 
 mod manager;
 mod policy;
+mod pool;
 
 pub use manager::{OffloadHandle, OffloadKey, OffloadManager};
 pub use policy::{OffloadConfig, OffloadConfigBuilder, TimeoutPolicy};
+pub use pool::{DEFAULT_POOL, OverflowPolicy, PoolConfig, PoolMatcher, RateLimit};
 pub use smol_str::SmolStr;
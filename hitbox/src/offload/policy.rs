@@ -2,6 +2,8 @@
 
 use std::time::Duration;
 
+use super::pool::PoolConfig;
+
 /// Policy for handling task timeouts.
 #[derive(Debug, Clone, Default)]
 pub enum TimeoutPolicy {
@@ -17,13 +19,16 @@ pub enum TimeoutPolicy {
 /// Configuration for the OffloadManager.
 #[derive(Debug, Clone)]
 pub struct OffloadConfig {
-    /// Maximum number of concurrent offloaded tasks.
+    /// Maximum number of concurrent offloaded tasks in the default pool,
+    /// i.e. tasks not routed into any of [`pools`](Self::pools).
     /// None means unlimited.
     pub max_concurrent_tasks: Option<usize>,
     /// Timeout policy for spawned tasks.
     pub timeout_policy: TimeoutPolicy,
     /// Enable task deduplication by key.
     pub deduplicate: bool,
+    /// Named pools with their own limits, matched in order.
+    pub pools: Vec<PoolConfig>,
 }
 
 impl Default for OffloadConfig {
@@ -32,6 +37,7 @@ impl Default for OffloadConfig {
             max_concurrent_tasks: None,
             timeout_policy: TimeoutPolicy::None,
             deduplicate: true,
+            pools: Vec::new(),
         }
     }
 }
@@ -49,6 +55,7 @@ pub struct OffloadConfigBuilder {
     max_concurrent_tasks: Option<usize>,
     timeout_policy: TimeoutPolicy,
     deduplicate: bool,
+    pools: Vec<PoolConfig>,
 }
 
 impl OffloadConfigBuilder {
@@ -58,6 +65,7 @@ impl OffloadConfigBuilder {
             max_concurrent_tasks: None,
             timeout_policy: TimeoutPolicy::None,
             deduplicate: true,
+            pools: Vec::new(),
         }
     }
 
@@ -90,12 +98,22 @@ impl OffloadConfigBuilder {
         }
     }
 
+    /// Add a named pool.
+    ///
+    /// Pools are matched in the order they are added; the first pool whose
+    /// matchers accept a task receives it.
+    pub fn pool(mut self, pool: PoolConfig) -> Self {
+        self.pools.push(pool);
+        self
+    }
+
     /// Build the OffloadConfig.
     pub fn build(self) -> OffloadConfig {
         OffloadConfig {
             max_concurrent_tasks: self.max_concurrent_tasks,
             timeout_policy: self.timeout_policy,
             deduplicate: self.deduplicate,
+            pools: self.pools,
         }
     }
 }
@@ -0,0 +1,444 @@
+//! Offload pools: per-kind and per-prefix concurrency, rate limits and queues.
+//!
+//! Every offloaded task is routed into exactly one pool. Named pools are
+//! configured with [`PoolConfig`] and matched in declaration order by task kind
+//! or [`CacheKey`](crate::CacheKey) prefix. Tasks that match no named pool go
+//! to the default pool, which is bounded by
+//! [`OffloadConfig::max_concurrent_tasks`](super::OffloadConfig::max_concurrent_tasks).
+//!
+//! Giving a noisy endpoint its own pool keeps its background revalidations
+//! from starving everybody else's.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::future::{Either, select};
+use smol_str::SmolStr;
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+
+/// Name of the pool receiving tasks that match no configured pool.
+pub const DEFAULT_POOL: &str = "default";
+
+/// Behavior when a task arrives and the pool queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Reject the incoming task.
+    #[default]
+    DropNewest,
+    /// Cancel the task that has been waiting the longest and queue the new one.
+    DropOldest,
+    /// Start the incoming task right away, bypassing the pool's concurrency
+    /// and rate limits.
+    ///
+    /// `spawn` is synchronous, so the task still runs on the runtime rather
+    /// than on the caller's stack, but it never waits in the queue.
+    RunInline,
+}
+
+impl OverflowPolicy {
+    /// Metrics label of the policy.
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Self::DropNewest => "drop_newest",
+            Self::DropOldest => "drop_oldest",
+            Self::RunInline => "run_inline",
+        }
+    }
+}
+
+/// Rate limit for starting tasks in a pool.
+///
+/// Allows bursts of up to `permits` task starts, refilled evenly over `per`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    permits: u32,
+    per: Duration,
+}
+
+impl RateLimit {
+    /// Create a rate limit of `permits` task starts per `per` interval.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is zero or `per` is zero.
+    pub fn new(permits: u32, per: Duration) -> Self {
+        assert!(permits > 0, "rate limit permits must be greater than zero");
+        assert!(
+            !per.is_zero(),
+            "rate limit interval must be greater than zero"
+        );
+        Self { permits, per }
+    }
+
+    /// Create a rate limit of `permits` task starts per second.
+    pub fn per_second(permits: u32) -> Self {
+        Self::new(permits, Duration::from_secs(1))
+    }
+
+    /// Number of task starts allowed per interval.
+    pub fn permits(&self) -> u32 {
+        self.permits
+    }
+
+    /// Length of the interval.
+    pub fn per(&self) -> Duration {
+        self.per
+    }
+}
+
+/// Rule selecting which tasks belong to a pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolMatcher {
+    /// Match tasks spawned with this kind (e.g., `"revalidate"`).
+    Kind(SmolStr),
+    /// Match tasks whose cache key prefix starts with this string.
+    KeyPrefix(SmolStr),
+}
+
+impl PoolMatcher {
+    fn matches(&self, route: &TaskRoute<'_>) -> bool {
+        match self {
+            Self::Kind(kind) => route.kind == kind.as_str(),
+            Self::KeyPrefix(prefix) => route
+                .key_prefix
+                .is_some_and(|key_prefix| key_prefix.starts_with(prefix.as_str())),
+        }
+    }
+}
+
+/// Configuration of a named offload pool.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use hitbox::offload::{OffloadConfig, OverflowPolicy, PoolConfig, RateLimit};
+///
+/// let config = OffloadConfig::builder()
+///     .max_concurrent_tasks(64)
+///     .pool(
+///         PoolConfig::new("search")
+///             .key_prefix("search")
+///             .max_concurrent_tasks(4)
+///             .rate_limit(RateLimit::per_second(20))
+///             .queue_capacity(100)
+///             .overflow(OverflowPolicy::DropOldest),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Pool name, used in metrics labels and tracing.
+    pub name: SmolStr,
+    /// Rules selecting tasks for this pool. A task matching any rule joins the pool.
+    pub matchers: Vec<PoolMatcher>,
+    /// Maximum number of tasks of this pool running at once.
+    /// None means unlimited.
+    pub max_concurrent_tasks: Option<usize>,
+    /// Optional limit on how fast tasks of this pool may start.
+    pub rate_limit: Option<RateLimit>,
+    /// Maximum number of tasks waiting to start.
+    /// None means unbounded.
+    pub queue_capacity: Option<usize>,
+    /// What to do with a task arriving at a full queue.
+    pub overflow: OverflowPolicy,
+}
+
+impl PoolConfig {
+    /// Create an unbounded pool with the given name and no matchers.
+    pub fn new(name: impl Into<SmolStr>) -> Self {
+        Self {
+            name: name.into(),
+            matchers: Vec::new(),
+            max_concurrent_tasks: None,
+            rate_limit: None,
+            queue_capacity: None,
+            overflow: OverflowPolicy::default(),
+        }
+    }
+
+    /// Route tasks of the given kind into this pool.
+    pub fn kind(mut self, kind: impl Into<SmolStr>) -> Self {
+        self.matchers.push(PoolMatcher::Kind(kind.into()));
+        self
+    }
+
+    /// Route tasks whose cache key prefix starts with `prefix` into this pool.
+    pub fn key_prefix(mut self, prefix: impl Into<SmolStr>) -> Self {
+        self.matchers.push(PoolMatcher::KeyPrefix(prefix.into()));
+        self
+    }
+
+    /// Set maximum concurrent tasks.
+    pub fn max_concurrent_tasks(self, max: usize) -> Self {
+        Self {
+            max_concurrent_tasks: Some(max),
+            ..self
+        }
+    }
+
+    /// Set rate limit.
+    pub fn rate_limit(self, rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit: Some(rate_limit),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of waiting tasks.
+    pub fn queue_capacity(self, capacity: usize) -> Self {
+        Self {
+            queue_capacity: Some(capacity),
+            ..self
+        }
+    }
+
+    /// Set overflow policy.
+    pub fn overflow(self, overflow: OverflowPolicy) -> Self {
+        Self { overflow, ..self }
+    }
+}
+
+/// Routing information of a task being spawned.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TaskRoute<'a> {
+    pub(crate) kind: &'a str,
+    pub(crate) key_prefix: Option<&'a str>,
+}
+
+/// Generic cell rate limiter: `permits` starts per `per`, with bursts up to `permits`.
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    tolerance: Duration,
+    theoretical_arrival: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        let interval = limit.per / limit.permits;
+        Self {
+            interval,
+            tolerance: limit.per.saturating_sub(interval),
+            theoretical_arrival: Mutex::new(None),
+        }
+    }
+
+    /// Reserve a start slot if one is open right now.
+    fn try_reserve(&self) -> bool {
+        let now = Instant::now();
+        let mut tat = self
+            .theoretical_arrival
+            .lock()
+            .expect("rate limiter poisoned");
+        let arrival = tat.map_or(now, |tat| tat.max(now));
+        if arrival
+            .checked_sub(self.tolerance)
+            .is_some_and(|start| start > now)
+        {
+            return false;
+        }
+        *tat = Some(arrival + self.interval);
+        true
+    }
+
+    /// Reserve the next start slot and return the instant it opens.
+    fn reserve(&self) -> Instant {
+        let now = Instant::now();
+        let mut tat = self
+            .theoretical_arrival
+            .lock()
+            .expect("rate limiter poisoned");
+        let arrival = tat.map_or(now, |tat| tat.max(now));
+        *tat = Some(arrival + self.interval);
+        arrival
+            .checked_sub(self.tolerance)
+            .map_or(now, |start| start.max(now))
+    }
+}
+
+/// Runtime state of an offload pool.
+#[derive(Debug)]
+pub(crate) struct Pool {
+    name: SmolStr,
+    matchers: Vec<PoolMatcher>,
+    semaphore: Option<Arc<Semaphore>>,
+    rate_limiter: Option<RateLimiter>,
+    queue_capacity: Option<usize>,
+    overflow: OverflowPolicy,
+    queue: Mutex<VecDeque<(u64, Arc<Notify>)>>,
+}
+
+/// How an accepted task gets to start.
+#[derive(Debug)]
+pub(crate) enum Admission {
+    /// Task starts right away, holding the permit (if the pool has one).
+    Ready(Option<OwnedSemaphorePermit>),
+    /// Task waits in the queue for a permit.
+    Queued(QueueTicket),
+}
+
+impl Pool {
+    pub(crate) fn new(config: PoolConfig) -> Self {
+        Self {
+            name: config.name,
+            matchers: config.matchers,
+            semaphore: config
+                .max_concurrent_tasks
+                .map(|max| Arc::new(Semaphore::new(max))),
+            rate_limiter: config.rate_limit.map(RateLimiter::new),
+            queue_capacity: config.queue_capacity,
+            overflow: config.overflow,
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn default_pool(max_concurrent_tasks: Option<usize>) -> Self {
+        let config = PoolConfig::new(DEFAULT_POOL);
+        match max_concurrent_tasks {
+            Some(max) => Self::new(config.max_concurrent_tasks(max)),
+            None => Self::new(config),
+        }
+    }
+
+    pub(crate) fn name(&self) -> &SmolStr {
+        &self.name
+    }
+
+    pub(crate) fn overflow(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    pub(crate) fn matches(&self, route: &TaskRoute<'_>) -> bool {
+        self.matchers.iter().any(|matcher| matcher.matches(route))
+    }
+
+    /// Number of tasks waiting to start.
+    pub(crate) fn queued(&self) -> usize {
+        self.queue
+            .lock()
+            .expect("offload pool queue poisoned")
+            .len()
+    }
+
+    fn is_unbounded(&self) -> bool {
+        self.semaphore.is_none() && self.rate_limiter.is_none()
+    }
+
+    /// Decide whether a new task may join this pool.
+    ///
+    /// A task starts right away when nobody is waiting and both a permit and
+    /// a rate limit slot are free; only the others count against the queue
+    /// capacity. With [`OverflowPolicy::DropOldest`] the longest-waiting task
+    /// is notified of its eviction and gives up its place. Returns `None` if
+    /// the task must not be spawned.
+    pub(crate) fn admit(self: &Arc<Self>, ticket: u64) -> Option<Admission> {
+        if self.is_unbounded() {
+            return Some(Admission::Ready(None));
+        }
+
+        let mut queue = self.queue.lock().expect("offload pool queue poisoned");
+        if queue.is_empty()
+            && let Some(permit) = self.try_start()
+        {
+            return Some(Admission::Ready(permit));
+        }
+        if self
+            .queue_capacity
+            .is_some_and(|capacity| queue.len() >= capacity)
+        {
+            match self.overflow {
+                OverflowPolicy::DropNewest => return None,
+                OverflowPolicy::RunInline => return Some(Admission::Ready(None)),
+                OverflowPolicy::DropOldest => match queue.pop_front() {
+                    Some((_, evicted)) => evicted.notify_one(),
+                    // Zero-capacity queue: nothing to evict, nowhere to wait.
+                    None => return None,
+                },
+            }
+        }
+        let evicted = Arc::new(Notify::new());
+        queue.push_back((ticket, evicted.clone()));
+        drop(queue);
+
+        Some(Admission::Queued(QueueTicket {
+            pool: self.clone(),
+            ticket,
+            evicted,
+        }))
+    }
+
+    /// Take a permit and a rate limit slot without waiting.
+    ///
+    /// Returns `None` if either is unavailable, leaving both untouched.
+    fn try_start(&self) -> Option<Option<OwnedSemaphorePermit>> {
+        let permit = match &self.semaphore {
+            Some(semaphore) => Some(semaphore.clone().try_acquire_owned().ok()?),
+            None => None,
+        };
+        match &self.rate_limiter {
+            Some(rate_limiter) if !rate_limiter.try_reserve() => None,
+            _ => Some(permit),
+        }
+    }
+}
+
+/// The waiting task was pushed out of a full queue by a newer one.
+#[derive(Debug)]
+pub(crate) struct Evicted;
+
+/// A task's place in a pool queue.
+///
+/// Dropping the ticket (e.g., when the waiting task is aborted) removes it
+/// from the queue.
+#[derive(Debug)]
+pub(crate) struct QueueTicket {
+    pool: Arc<Pool>,
+    ticket: u64,
+    evicted: Arc<Notify>,
+}
+
+impl QueueTicket {
+    /// Wait until the pool lets this task start.
+    ///
+    /// Returns the concurrency permit, which must be held while the task runs,
+    /// or [`Evicted`] if a newer task took this one's place in the queue.
+    pub(crate) async fn acquire(self) -> Result<Option<OwnedSemaphorePermit>, Evicted> {
+        let pool = self.pool.clone();
+        let admitted = async move {
+            let permit = match &pool.semaphore {
+                Some(semaphore) => Some(
+                    semaphore
+                        .clone()
+                        .acquire_owned()
+                        .await
+                        .expect("offload pool semaphore is never closed"),
+                ),
+                None => None,
+            };
+            if let Some(rate_limiter) = &pool.rate_limiter {
+                let start = rate_limiter.reserve();
+                tokio::time::sleep_until(start.into()).await;
+            }
+            permit
+        };
+        let evicted = self.evicted.notified();
+
+        // Eviction is checked first, so an evicted task never starts even if
+        // a permit is free by the time it is polled.
+        match select(std::pin::pin!(evicted), std::pin::pin!(admitted)).await {
+            Either::Left(((), _)) => Err(Evicted),
+            Either::Right((permit, _)) => Ok(permit),
+        }
+    }
+}
+
+impl Drop for QueueTicket {
+    fn drop(&mut self) {
+        let mut queue = self.pool.queue.lock().expect("offload pool queue poisoned");
+        if let Some(position) = queue.iter().position(|(ticket, _)| *ticket == self.ticket) {
+            queue.remove(position);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use hitbox::offload::{
+    DEFAULT_POOL, OffloadConfig, OffloadKey, OffloadManager, OverflowPolicy, PoolConfig, RateLimit,
+};
+use hitbox::{CacheKey, KeyPart};
+use tokio::sync::{Semaphore, oneshot};
+
+fn cache_key(prefix: &str, id: &str) -> CacheKey {
+    CacheKey::new(prefix, 0, vec![KeyPart::new("id", Some(id))])
+}
+
+/// Tracks how many tasks run at the same time.
+#[derive(Clone, Default)]
+struct Concurrency {
+    current: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+impl Concurrency {
+    async fn run(&self, duration: Duration) {
+        let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(duration).await;
+        self.current.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+}
+
+/// Blocks tasks until the test releases them all at once.
+#[derive(Clone)]
+struct Gate(Arc<Semaphore>);
+
+impl Gate {
+    fn new() -> Self {
+        Self(Arc::new(Semaphore::new(0)))
+    }
+
+    async fn wait(&self) {
+        // Closing the semaphore wakes every waiter with an error.
+        let _ = self.0.acquire().await;
+    }
+
+    fn release(&self) {
+        self.0.close();
+    }
+}
+
+#[tokio::test]
+async fn test_default_pool_respects_max_concurrent_tasks() {
+    let manager = OffloadManager::new(OffloadConfig::builder().max_concurrent_tasks(2).build());
+    let concurrency = Concurrency::default();
+
+    for _ in 0..6 {
+        let concurrency = concurrency.clone();
+        manager.spawn("test", async move {
+            concurrency.run(Duration::from_millis(20)).await;
+        });
+    }
+
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert_eq!(concurrency.peak(), 2);
+}
+
+#[tokio::test]
+async fn test_key_prefix_pool_does_not_starve_default_pool() {
+    let manager = OffloadManager::new(
+        OffloadConfig::builder()
+            .pool(
+                PoolConfig::new("search")
+                    .key_prefix("search")
+                    .max_concurrent_tasks(1),
+            )
+            .build(),
+    );
+    let gate = Gate::new();
+
+    // Occupy the only permit of the "search" pool and queue another task behind it.
+    for id in ["1", "2"] {
+        let gate = gate.clone();
+        assert!(manager.spawn_with_key(cache_key("search", id), async move {
+            gate.wait().await;
+        }));
+    }
+    assert_eq!(manager.queued_task_count("search"), Some(1));
+
+    // A task for another prefix goes to the default pool and runs right away.
+    let (done, ran) = oneshot::channel();
+    manager.spawn_for_key("revalidate", &cache_key("users", "1"), async move {
+        let _ = done.send(());
+    });
+
+    tokio::time::timeout(Duration::from_secs(1), ran)
+        .await
+        .expect("default pool task should not wait for the search pool")
+        .unwrap();
+    assert_eq!(manager.queued_task_count("search"), Some(1));
+    assert_eq!(manager.queued_task_count(DEFAULT_POOL), Some(0));
+
+    gate.release();
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+}
+
+#[tokio::test]
+async fn test_kind_pool_matches_spawn_kind() {
+    let manager = OffloadManager::new(
+        OffloadConfig::builder()
+            .pool(
+                PoolConfig::new("warmup")
+                    .kind("warmup")
+                    .max_concurrent_tasks(1),
+            )
+            .build(),
+    );
+    let concurrency = Concurrency::default();
+
+    for _ in 0..3 {
+        let concurrency = concurrency.clone();
+        manager.spawn("warmup", async move {
+            concurrency.run(Duration::from_millis(20)).await;
+        });
+    }
+
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert_eq!(concurrency.peak(), 1);
+}
+
+/// Spawns a blocked task into a single-permit pool with a queue of one,
+/// then a queued task, and returns the flag set by the queued task.
+fn saturate(manager: &OffloadManager, gate: &Gate) -> Arc<AtomicBool> {
+    let running = gate.clone();
+    assert!(
+        manager.spawn_with_key(cache_key("api", "running"), async move {
+            running.wait().await;
+        })
+    );
+
+    let queued_ran = Arc::new(AtomicBool::new(false));
+    let flag = queued_ran.clone();
+    assert!(
+        manager.spawn_with_key(cache_key("api", "queued"), async move {
+            flag.store(true, Ordering::SeqCst);
+        })
+    );
+    assert_eq!(manager.queued_task_count("api"), Some(1));
+    queued_ran
+}
+
+fn bounded_pool(overflow: OverflowPolicy) -> OffloadManager {
+    OffloadManager::new(
+        OffloadConfig::builder()
+            .pool(
+                PoolConfig::new("api")
+                    .key_prefix("api")
+                    .max_concurrent_tasks(1)
+                    .queue_capacity(1)
+                    .overflow(overflow),
+            )
+            .build(),
+    )
+}
+
+#[tokio::test]
+async fn test_overflow_drop_newest() {
+    let manager = bounded_pool(OverflowPolicy::DropNewest);
+    let gate = Gate::new();
+    let queued_ran = saturate(&manager, &gate);
+
+    let newest_ran = Arc::new(AtomicBool::new(false));
+    let flag = newest_ran.clone();
+    let spawned = manager.spawn_with_key(cache_key("api", "newest"), async move {
+        flag.store(true, Ordering::SeqCst);
+    });
+    assert!(!spawned);
+
+    gate.release();
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert!(queued_ran.load(Ordering::SeqCst));
+    assert!(!newest_ran.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_overflow_drop_oldest() {
+    let manager = bounded_pool(OverflowPolicy::DropOldest);
+    let gate = Gate::new();
+    let queued_ran = saturate(&manager, &gate);
+
+    let newest_ran = Arc::new(AtomicBool::new(false));
+    let flag = newest_ran.clone();
+    assert!(
+        manager.spawn_with_key(cache_key("api", "newest"), async move {
+            flag.store(true, Ordering::SeqCst);
+        })
+    );
+
+    gate.release();
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert!(!queued_ran.load(Ordering::SeqCst));
+    assert!(newest_ran.load(Ordering::SeqCst));
+    assert!(!manager.is_in_flight(&OffloadKey::Cache(cache_key("api", "queued"))));
+}
+
+#[tokio::test]
+async fn test_overflow_run_inline() {
+    let manager = bounded_pool(OverflowPolicy::RunInline);
+    let gate = Gate::new();
+    let queued_ran = saturate(&manager, &gate);
+
+    let (done, ran) = oneshot::channel();
+    assert!(
+        manager.spawn_with_key(cache_key("api", "newest"), async move {
+            let _ = done.send(());
+        })
+    );
+
+    // Runs while the pool permit is still held by the first task.
+    tokio::time::timeout(Duration::from_secs(1), ran)
+        .await
+        .expect("inline task should not wait for the permit")
+        .unwrap();
+    assert!(!queued_ran.load(Ordering::SeqCst));
+
+    gate.release();
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert!(queued_ran.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_burst_only_queues_tasks_without_a_permit() {
+    let manager = OffloadManager::new(
+        OffloadConfig::builder()
+            .pool(
+                PoolConfig::new("api")
+                    .key_prefix("api")
+                    .max_concurrent_tasks(2)
+                    .queue_capacity(1),
+            )
+            .build(),
+    );
+    let gate = Gate::new();
+
+    // Spawned back to back, before any task is polled.
+    for id in ["1", "2", "3"] {
+        let gate = gate.clone();
+        assert!(manager.spawn_with_key(cache_key("api", id), async move {
+            gate.wait().await;
+        }));
+    }
+    assert_eq!(manager.queued_task_count("api"), Some(1));
+    assert!(!manager.spawn_with_key(cache_key("api", "4"), async {}));
+
+    gate.release();
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+}
+
+#[tokio::test]
+async fn test_burst_with_run_inline_respects_max_concurrent_tasks() {
+    let manager = OffloadManager::new(
+        OffloadConfig::builder()
+            .pool(
+                PoolConfig::new("api")
+                    .key_prefix("api")
+                    .max_concurrent_tasks(2)
+                    .queue_capacity(1)
+                    .overflow(OverflowPolicy::RunInline),
+            )
+            .build(),
+    );
+    let concurrency = Concurrency::default();
+
+    for id in ["1", "2", "3"] {
+        let concurrency = concurrency.clone();
+        assert!(manager.spawn_with_key(cache_key("api", id), async move {
+            concurrency.run(Duration::from_millis(20)).await;
+        }));
+    }
+
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert_eq!(concurrency.peak(), 2);
+}
+
+#[tokio::test]
+async fn test_pool_rate_limit() {
+    let manager = OffloadManager::new(
+        OffloadConfig::builder()
+            .pool(
+                PoolConfig::new("limited")
+                    .kind("limited")
+                    .rate_limit(RateLimit::new(2, Duration::from_millis(200))),
+            )
+            .build(),
+    );
+    let started = Arc::new(AtomicUsize::new(0));
+    let begin = Instant::now();
+
+    for _ in 0..4 {
+        let started = started.clone();
+        manager.spawn("limited", async move {
+            started.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    // The burst of two starts immediately, the rest are spaced by 100ms.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    assert!(manager.wait_all_timeout(Duration::from_secs(5)).await);
+    assert_eq!(started.load(Ordering::SeqCst), 4);
+    assert!(begin.elapsed() >= Duration::from_millis(200));
+}